@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Fields, LitStr};
+
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+	let data = match &input.data {
+		Data::Enum(data) => data,
+		_ => return Err(syn::Error::new(Span::call_site(), "FromName can only be derived for enums")),
+	};
+	let rename_all = match attr(&input.attrs, "rename_all")? {
+		Some(lit) => Some(RenameRule::from_lit(&lit)?),
+		None => None,
+	};
+
+	let mut variants = Vec::new();
+	let mut names = Vec::new();
+	let mut seen = HashMap::new();
+	for variant in &data.variants {
+		if !matches!(variant.fields, Fields::Unit) {
+			return Err(syn::Error::new_spanned(variant, "FromName only supports unit variants"));
+		}
+		let name = match attr(&variant.attrs, "rename")? {
+			Some(lit) => lit.value(),
+			None => {
+				let ident = variant.ident.to_string();
+				rename_all.map_or(ident.clone(), |rule| rule.apply(&ident))
+			},
+		};
+		if let Some(other) = seen.insert(name.clone(), variant.ident.to_string()) {
+			return Err(syn::Error::new_spanned(&variant.ident, format!("variant {} has the same name {:?} as {}", variant.ident, name, other)));
+		}
+		variants.push(&variant.ident);
+		names.push(name);
+	}
+
+	let ident = &input.ident;
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+	let len = names.len();
+	let indices = 0..len;
+	let match_indices = 0..len;
+	Ok(quote! {
+		impl #impl_generics #ident #ty_generics #where_clause {
+			/// Returns the name of the variant.
+			#[allow(dead_code)]
+			#[inline]
+			pub fn name(&self) -> &'static str {
+				match *self {
+					#(#ident::#variants => #names,)*
+				}
+			}
+		}
+		impl #impl_generics ::core::str::FromStr for #ident #ty_generics #where_clause {
+			type Err = ();
+			fn from_str(name: &str) -> ::core::result::Result<Self, ()> {
+				const LEN: usize = #len;
+				const SEEDS_LEN: usize = ::mphf::__lookup_table::seeds_len(LEN);
+				const NAMES: [&str; LEN] = [#(#names),*];
+				static SEEDS: [u32; SEEDS_LEN] = ::mphf::__lookup_table::build(&NAMES);
+				static KEYS: [&str; LEN] = ::mphf::__lookup_table::reorder(&NAMES, &NAMES, &SEEDS);
+				// The position of the variant in the enum, the variants need not be Copy
+				static VARIANTS: [usize; LEN] = ::mphf::__lookup_table::reorder(&NAMES, &[#(#indices),*], &SEEDS);
+				match ::mphf::index(name, &SEEDS, LEN) {
+					::core::option::Option::Some(i) if KEYS[i] == name => match VARIANTS[i] {
+						#(#match_indices => ::core::result::Result::Ok(#ident::#variants),)*
+						_ => ::core::result::Result::Err(()),
+					},
+					_ => ::core::result::Result::Err(()),
+				}
+			}
+		}
+	})
+}
+
+// The string of `#[mphf(key = "...")]`
+fn attr(attrs: &[Attribute], key: &str) -> syn::Result<Option<LitStr>> {
+	let mut value = None;
+	for attr in attrs.iter().filter(|attr| attr.path().is_ident("mphf")) {
+		attr.parse_nested_meta(|meta| {
+			if meta.path.is_ident(key) {
+				value = Some(meta.value()?.parse()?);
+				Ok(())
+			}
+			else {
+				Err(meta.error(format!("unknown mphf attribute, expected `{}`", key)))
+			}
+		})?;
+	}
+	Ok(value)
+}
+
+// The case conversions of serde, the variant names are PascalCase
+#[derive(Copy, Clone)]
+enum RenameRule {
+	Lower,
+	Upper,
+	Pascal,
+	Camel,
+	Snake,
+	ScreamingSnake,
+	Kebab,
+	ScreamingKebab,
+}
+
+impl RenameRule {
+	fn from_lit(lit: &LitStr) -> syn::Result<RenameRule> {
+		Ok(match &lit.value()[..] {
+			"lowercase" => RenameRule::Lower,
+			"UPPERCASE" => RenameRule::Upper,
+			"PascalCase" => RenameRule::Pascal,
+			"camelCase" => RenameRule::Camel,
+			"snake_case" => RenameRule::Snake,
+			"SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+			"kebab-case" => RenameRule::Kebab,
+			"SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebab,
+			_ => return Err(syn::Error::new(lit.span(), "unknown rename_all rule, expected one of \"lowercase\", \"UPPERCASE\", \"PascalCase\", \"camelCase\", \"snake_case\", \"SCREAMING_SNAKE_CASE\", \"kebab-case\" or \"SCREAMING-KEBAB-CASE\"")),
+		})
+	}
+	fn apply(self, variant: &str) -> String {
+		let separated = |separator: char| {
+			let mut name = String::new();
+			for (i, c) in variant.char_indices() {
+				if i > 0 && c.is_uppercase() {
+					name.push(separator);
+				}
+				name.extend(c.to_lowercase());
+			}
+			name
+		};
+		match self {
+			RenameRule::Lower => variant.to_lowercase(),
+			RenameRule::Upper => variant.to_uppercase(),
+			RenameRule::Pascal => String::from(variant),
+			RenameRule::Camel => {
+				let mut chars = variant.chars();
+				chars.next().map_or(String::new(), |first| first.to_lowercase().chain(chars).collect())
+			},
+			RenameRule::Snake => separated('_'),
+			RenameRule::ScreamingSnake => separated('_').to_uppercase(),
+			RenameRule::Kebab => separated('-'),
+			RenameRule::ScreamingKebab => separated('-').to_uppercase(),
+		}
+	}
+}
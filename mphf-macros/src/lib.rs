@@ -0,0 +1,106 @@
+/*!
+Procedural macros of the mphf crate, use them through the macros feature of mphf.
+*/
+
+use std::collections::HashSet;
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, DeriveInput, Expr, LitStr, Token};
+
+mod from_name;
+
+struct Entry {
+	key: LitStr,
+	value: Expr,
+}
+
+impl Parse for Entry {
+	fn parse(input: ParseStream) -> syn::Result<Entry> {
+		let key = input.parse()?;
+		input.parse::<Token![=>]>()?;
+		let value = input.parse()?;
+		Ok(Entry { key, value })
+	}
+}
+
+struct Entries(Punctuated<Entry, Token![,]>);
+struct Keys(Punctuated<LitStr, Token![,]>);
+
+impl Parse for Entries {
+	fn parse(input: ParseStream) -> syn::Result<Entries> {
+		Punctuated::parse_terminated(input).map(Entries)
+	}
+}
+
+impl Parse for Keys {
+	fn parse(input: ParseStream) -> syn::Result<Keys> {
+		Punctuated::parse_terminated(input).map(Keys)
+	}
+}
+
+// Duplicates are reported at the second literal instead of failing the const evaluation of the seeds
+fn check_duplicates<'a, I: Iterator<Item = &'a LitStr>>(keys: I) -> syn::Result<()> {
+	let mut seen = HashSet::new();
+	for key in keys {
+		let value = key.value();
+		if !seen.insert(value.clone()) {
+			return Err(syn::Error::new(key.span(), format!("duplicate key {:?}", value)));
+		}
+	}
+	Ok(())
+}
+
+/// Builds an `mphf::Map` at compile time, see the documentation of `mphf::map!`.
+#[proc_macro]
+pub fn map(input: TokenStream) -> TokenStream {
+	let Entries(entries) = parse_macro_input!(input as Entries);
+	if let Err(err) = check_duplicates(entries.iter().map(|entry| &entry.key)) {
+		return err.to_compile_error().into();
+	}
+
+	let len = entries.len();
+	let keys = entries.iter().map(|entry| &entry.key);
+	let values = entries.iter().map(|entry| &entry.value);
+	quote!({
+		const LEN: usize = #len;
+		const SEEDS_LEN: usize = ::mphf::__lookup_table::seeds_len(LEN);
+		const KEYS: [&str; LEN] = [#(#keys),*];
+		const SEEDS: [u32; SEEDS_LEN] = ::mphf::__lookup_table::build(&KEYS);
+		::mphf::Map {
+			__seeds: &SEEDS,
+			__keys: &::mphf::__lookup_table::reorder(&KEYS, &KEYS, &SEEDS),
+			__values: &::mphf::__lookup_table::reorder(&KEYS, &[#(#values),*], &SEEDS),
+		}
+	}).into()
+}
+
+/// Builds an `mphf::Set` at compile time, see the documentation of `mphf::set!`.
+#[proc_macro]
+pub fn set(input: TokenStream) -> TokenStream {
+	let Keys(keys) = parse_macro_input!(input as Keys);
+	if let Err(err) = check_duplicates(keys.iter()) {
+		return err.to_compile_error().into();
+	}
+
+	let len = keys.len();
+	let keys = keys.iter();
+	quote!({
+		const LEN: usize = #len;
+		const SEEDS_LEN: usize = ::mphf::__lookup_table::seeds_len(LEN);
+		const KEYS: [&str; LEN] = [#(#keys),*];
+		const SEEDS: [u32; SEEDS_LEN] = ::mphf::__lookup_table::build(&KEYS);
+		::mphf::Set {
+			__seeds: &SEEDS,
+			__keys: &::mphf::__lookup_table::reorder(&KEYS, &KEYS, &SEEDS),
+		}
+	}).into()
+}
+
+/// Derives `FromStr` and `name` for an enum of unit variants, see the documentation of `mphf::FromName`.
+#[proc_macro_derive(FromName, attributes(mphf))]
+pub fn derive_from_name(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	from_name::derive(input).unwrap_or_else(|err| err.to_compile_error()).into()
+}
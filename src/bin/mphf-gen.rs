@@ -0,0 +1,187 @@
+/*!
+Generates mphf tables for build systems other than Cargo.
+
+```text
+mphf-gen --name keywords --keys keys.txt --values values.txt --seeds-len 128 --max-seed 100000 --format rust -o keywords.rs
+```
+
+See `mphf-gen --help` for the arguments.
+*/
+
+use std::{env, fs, process};
+use std::collections::HashMap;
+use mphf::codegen::{CsvOptions, Options};
+
+const USAGE: &str = "\
+Usage: mphf-gen --name NAME (--keys FILE [--values FILE] | --csv FILE [--header]) --seeds-len N --max-seed N [OPTIONS]
+
+Inputs:
+  --keys FILE        Keys, one per line
+  --values FILE      Values, one per line in the order of the keys
+  --csv FILE         Keys in the first and values in the second column
+  --header           Skips the first row of the csv file
+
+Build:
+  --name NAME        Name of the generated table
+  --seeds-len N      Number of buckets
+  --max-seed N       Number of seeds tried per bucket
+
+Output:
+  --format FORMAT    rust (default), c, cpp, go, typescript, python or json
+  --package NAME     Package of the go output, defaults to the name
+  --verify-keys      Lookups of keys outside the table return nothing
+  -o, --output FILE  Writes the output to the file instead of stdout
+  -h, --help         Prints this help
+";
+
+#[derive(Default)]
+struct Args {
+	name: Option<String>,
+	keys: Option<String>,
+	values: Option<String>,
+	csv: Option<String>,
+	header: bool,
+	seeds_len: Option<usize>,
+	max_seed: Option<u32>,
+	format: Option<String>,
+	package: Option<String>,
+	verify_keys: bool,
+	output: Option<String>,
+}
+
+fn main() {
+	let args = match parse_args(env::args().skip(1)) {
+		Ok(Some(args)) => args,
+		Ok(None) => {
+			print!("{}", USAGE);
+			return;
+		},
+		Err(err) => {
+			eprintln!("mphf-gen: {}\n\n{}", err, USAGE);
+			process::exit(2);
+		},
+	};
+	if let Err(err) = run(args) {
+		eprintln!("mphf-gen: {}", err);
+		process::exit(1);
+	}
+}
+
+// Returns None when the help is requested
+fn parse_args<I: Iterator<Item = String>>(mut iter: I) -> Result<Option<Args>, String> {
+	let mut args = Args::default();
+	while let Some(arg) = iter.next() {
+		if arg == "-h" || arg == "--help" {
+			return Ok(None);
+		}
+		if arg == "--header" {
+			args.header = true;
+			continue;
+		}
+		if arg == "--verify-keys" {
+			args.verify_keys = true;
+			continue;
+		}
+		let value = iter.next().ok_or_else(|| format!("missing value of {}", arg))?;
+		let number_err = |_| format!("invalid number {:?} for {}", value, arg);
+		match &arg[..] {
+			"--name" => args.name = Some(value),
+			"--keys" => args.keys = Some(value),
+			"--values" => args.values = Some(value),
+			"--csv" => args.csv = Some(value),
+			"--seeds-len" => args.seeds_len = Some(value.parse().map_err(number_err)?),
+			"--max-seed" => args.max_seed = Some(value.parse().map_err(number_err)?),
+			"--format" => args.format = Some(value),
+			"--package" => args.package = Some(value),
+			"-o" | "--output" => args.output = Some(value),
+			_ => return Err(format!("unknown argument {}", arg)),
+		}
+	}
+	Ok(Some(args))
+}
+
+fn run(args: Args) -> Result<(), String> {
+	let name = args.name.ok_or("missing --name")?;
+	let seeds_len = args.seeds_len.ok_or("missing --seeds-len")?;
+	let max_seed = args.max_seed.ok_or("missing --max-seed")?;
+	let (keys, values) = match (&args.keys, &args.csv) {
+		(Some(keys), None) => {
+			let keys = read_lines(keys)?;
+			let values = match &args.values {
+				Some(values) => read_lines(values)?,
+				None => Vec::new(),
+			};
+			if let Some((key, lines)) = duplicate(&keys) {
+				return Err(format!("duplicate key {:?} on lines {} and {}", key, lines.0, lines.1));
+			}
+			(keys, values)
+		},
+		(None, Some(csv)) if args.values.is_none() => {
+			let file = fs::File::open(csv).map_err(|err| format!("error reading {}: {}", csv, err))?;
+			let options = CsvOptions { has_header: args.header, ..CsvOptions::default() };
+			let entries = mphf::codegen::load_csv(file, options).map_err(|err| format!("{}: {}", csv, err))?;
+			(entries.keys, entries.values)
+		},
+		(Some(_), Some(_)) => return Err(String::from("--keys and --csv cannot be used together")),
+		(None, Some(_)) => return Err(String::from("--values cannot be used with --csv")),
+		(None, None) => return Err(String::from("missing --keys or --csv")),
+	};
+
+	let key_refs = keys.iter().map(String::as_str).collect::<Vec<&str>>();
+	let value_refs = values.iter().map(String::as_str).collect::<Vec<&str>>();
+	let options = Options {
+		name: &name,
+		keys: &key_refs,
+		values: &value_refs,
+		has_values: args.values.is_some() || args.csv.is_some(),
+		verify_keys: args.verify_keys,
+		seeds_len,
+		max_seed,
+		..Options::default()
+	};
+	let code = match args.format.as_deref().unwrap_or("rust") {
+		"rust" => options.rust(),
+		"c" => options.c(),
+		"cpp" => options.cpp(),
+		"go" => options.go(args.package.as_deref().unwrap_or(&name)),
+		"typescript" => options.typescript(),
+		"python" => options.python(),
+		"json" => options.json(),
+		format => return Err(format!("unknown format {:?}", format)),
+	};
+	let code = code.map_err(|err| err.to_string())?;
+
+	// The same build again for the stats, building is deterministic
+	if let Ok((_, infos)) = mphf::build_verbose(&key_refs, seeds_len, max_seed) {
+		let empty = seeds_len - infos.len();
+		let largest = infos.iter().map(|info| info.seed_found).max().unwrap_or(0);
+		let attempts = infos.iter().map(|info| info.attempts as u64).sum::<u64>();
+		eprintln!("mphf-gen: {} keys in {} buckets, {} empty, largest seed {}, {} seeds tried", keys.len(), seeds_len, empty, largest, attempts);
+	}
+
+	match &args.output {
+		Some(path) => fs::write(path, code).map_err(|err| format!("error writing {}: {}", path, err)),
+		None => {
+			print!("{}", code);
+			Ok(())
+		},
+	}
+}
+
+// A trailing newline does not start another line, line endings may be CRLF
+fn read_lines(path: &str) -> Result<Vec<String>, String> {
+	let text = fs::read_to_string(path).map_err(|err| format!("error reading {}: {}", path, err))?;
+	Ok(text.lines().map(String::from).collect())
+}
+
+// The first key appearing twice and its one based line numbers
+fn duplicate(keys: &[String]) -> Option<(&str, (usize, usize))> {
+	let mut lines = HashMap::new();
+	for (i, key) in keys.iter().enumerate() {
+		if let Some(&first) = lines.get(key) {
+			return Some((key, (first, i + 1)));
+		}
+		lines.insert(key, i + 1);
+	}
+	None
+}
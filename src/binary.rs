@@ -0,0 +1,1207 @@
+/*!
+Compact binary format for [`Mphf`] tables.
+
+The format is a fixed 36 byte header followed by the seeds:
+
+| Offset | Size | Field                                      |
+|--------|------|--------------------------------------------|
+| 0      | 4    | Magic bytes `MPHF`                         |
+| 4      | 1    | Format version, currently `2`              |
+| 5      | 1    | Hash kind, `0` for 32-bit murmur3          |
+| 6      | 2    | Reserved, must be zero                     |
+| 8      | 4    | Number of keys                             |
+| 12     | 4    | Number of seeds                            |
+| 16     | 4    | Number of values, equal to the number of keys |
+| 20     | 4    | First level bucket seed, must be zero      |
+| 24     | 8    | Digest of the keys, zero if not recorded   |
+| 32     | 4    | CRC-32 checksum                            |
+| 36     | 4×N  | Seeds                                      |
+
+All multi-byte fields are unsigned integers stored little-endian, regardless of the host.
+
+The checksum is the CRC-32 (IEEE, as used by zlib) of the first 32 bytes of the header followed by the seeds.
+
+The number of values and the first level bucket seed describe the build parameters, other values are reserved for future tables and rejected.
+The digest of the keys is computed by [`key_digest`], tables written with their keys can be checked against a list of keys with [`MphfRef::verify_keys`].
+
+Empty buckets have the seed `0xFFFFFFFF`.
+A key is looked up by hashing it with seed `0` modulo the number of seeds to select its seed,
+then hashing it again with that seed modulo the number of keys to get its index.
+The hash is [murmur3](crate::hash) over the UTF-8 bytes of the key.
+
+Version `1` has a 20 byte header without the number of values, the bucket seed and the digest, the checksum at offset 16 covers the first 16 bytes.
+
+### Example
+
+The table for the keys `red`, `green`, `blue`, `cyan`, `magenta`, `yellow`, `black`, `white` and `orange` with a single seed:
+
+```text
+4D 50 48 46              magic "MPHF"
+02                       version 2
+00                       hash kind murmur3
+00 00                    reserved
+09 00 00 00              9 keys
+01 00 00 00              1 seed
+09 00 00 00              9 values
+00 00 00 00              bucket seed 0
+80 FD C0 C4 25 F4 E8 82  key digest
+FE 45 45 AD              checksum
+37 02 00 00              seed 567
+```
+
+### Reproducibility
+
+The encoded bytes are a pure function of the key set, the build parameters and the format version.
+The seed search tries the buckets largest first with ties broken by bucket index and tries the seeds in increasing order,
+neither depends on the order of the input keys. The header has no timestamps and the key digest does not depend on the order of the keys.
+
+### Versioning
+
+Tables are always written in the latest format [`VERSION`].
+Readers accept every version from [`MIN_VERSION`] up to [`VERSION`], versions outside this range are rejected with [`DecodeError::UnsupportedVersion`].
+A new format version must keep reading at least the version before it, the tests pin a golden blob for every supported version.
+
+### Migrating headerless seeds
+
+Seeds persisted as bare little-endian words without a header are wrapped with [`Mphf::from_legacy_seeds`] given the number of keys.
+Write them back with [`Mphf::to_bytes`] or [`Mphf::write_to`] to migrate to the current format.
+
+### `no_std`
+
+Decoding with [`MphfRef`] and encoding borrowed seeds with [`encode_into`] only need `core`, they are available without the `std` feature.
+The owned [`Mphf`], streaming and the functions returning a `Vec` need `std`.
+*/
+
+use core::{convert::TryFrom, fmt};
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use super::{Mphf, Seeds};
+use super::crc32::Crc32;
+
+/// Magic bytes at the start of every table.
+pub const MAGIC: [u8; 4] = *b"MPHF";
+/// The format version written by this crate.
+pub const VERSION: u8 = 2;
+/// The oldest format version read by this crate.
+pub const MIN_VERSION: u8 = 1;
+/// Hash kind of the 32-bit murmur3 hash.
+pub const HASH_MURMUR3_32: u8 = 0;
+
+const HEADER_LEN: usize = 36;
+const HEADER_LEN_V1: usize = 20;
+
+/// Default maximum number of seeds accepted by [`Mphf::read_from`].
+///
+/// Limits the allocation to 64 MiB regardless of what the header claims.
+pub const DEFAULT_MAX_SEEDS: usize = 1 << 24;
+
+/// Limits on the size of decoded tables.
+///
+/// Checked against the header before anything is allocated so a hostile header cannot trigger a huge allocation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Limits {
+	/// Maximum number of keys.
+	pub max_keys: usize,
+	/// Maximum number of seeds.
+	pub max_seeds: usize,
+}
+
+impl Limits {
+	/// No limits, decoding from a byte slice never allocates more than the slice holds.
+	pub const NONE: Limits = Limits { max_keys: usize::MAX, max_seeds: usize::MAX };
+
+	// Returns the number exceeding the limits, if any.
+	fn exceeded(&self, header: &Header) -> Option<usize> {
+		if header.len > self.max_keys {
+			return Some(header.len);
+		}
+		if header.seeds_len > self.max_seeds {
+			return Some(header.seeds_len);
+		}
+		None
+	}
+}
+
+/// Any number of keys and at most [`DEFAULT_MAX_SEEDS`] seeds.
+impl Default for Limits {
+	#[inline]
+	fn default() -> Limits {
+		Limits { max_keys: usize::MAX, max_seeds: DEFAULT_MAX_SEEDS }
+	}
+}
+
+/// Error decoding a binary table.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+	/// The input ended before the end of the table.
+	Truncated,
+	/// The input does not start with the magic bytes.
+	BadMagic,
+	/// The format version is not supported.
+	UnsupportedVersion(u8),
+	/// The hash kind is not supported.
+	UnsupportedHash(u8),
+	/// The input continues after the end of the table.
+	TrailingBytes,
+	/// The checksum does not match the contents.
+	ChecksumMismatch,
+	/// The header or seeds are inconsistent.
+	Invalid,
+	/// The table was built for a different set of keys.
+	KeyMismatch,
+	/// The table exceeds the configured [`Limits`], holds the offending count.
+	TooLarge(usize),
+}
+
+impl fmt::Display for DecodeError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			DecodeError::Truncated => f.write_str("truncated mphf table"),
+			DecodeError::BadMagic => f.write_str("not an mphf table"),
+			DecodeError::UnsupportedVersion(version) => write!(f, "unsupported mphf format version {}", version),
+			DecodeError::UnsupportedHash(hash) => write!(f, "unsupported mphf hash kind {}", hash),
+			DecodeError::TrailingBytes => f.write_str("trailing bytes after mphf table"),
+			DecodeError::ChecksumMismatch => f.write_str("mphf table checksum mismatch"),
+			DecodeError::Invalid => f.write_str("invalid mphf table"),
+			DecodeError::KeyMismatch => f.write_str("mphf table does not belong to the keys"),
+			DecodeError::TooLarge(count) => write!(f, "mphf table with {} entries exceeds the size limits", count),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+/// Error encoding a binary table into a buffer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EncodeError {
+	/// The buffer is smaller than the `needed` bytes of the encoded table.
+	BufferTooSmall { needed: usize },
+	/// The number of keys or seeds does not fit in the 32-bit fields of the header, holds the offending count.
+	TooLarge { count: usize },
+}
+
+impl fmt::Display for EncodeError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			EncodeError::BufferTooSmall { needed } => write!(f, "buffer too small for mphf table, need {} bytes", needed),
+			EncodeError::TooLarge { count } => write!(f, "mphf table with {} entries is too large for the binary format", count),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EncodeError {}
+
+/// Error reading a binary table from a stream.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum LoadError {
+	/// The underlying reader failed.
+	Io(io::Error),
+	/// The table is malformed or exceeds the configured [`Limits`].
+	Decode(DecodeError),
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for LoadError {
+	fn from(err: io::Error) -> LoadError {
+		// A short read means the stream ended before the table did
+		if err.kind() == io::ErrorKind::UnexpectedEof {
+			LoadError::Decode(DecodeError::Truncated)
+		}
+		else {
+			LoadError::Io(err)
+		}
+	}
+}
+#[cfg(feature = "std")]
+impl From<DecodeError> for LoadError {
+	fn from(err: DecodeError) -> LoadError {
+		LoadError::Decode(err)
+	}
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for LoadError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			LoadError::Io(err) => write!(f, "error reading mphf table: {}", err),
+			LoadError::Decode(err) => err.fmt(f),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LoadError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			LoadError::Io(err) => Some(err),
+			LoadError::Decode(err) => Some(err),
+		}
+	}
+}
+
+#[inline]
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+	u32::from_le_bytes([bytes[offset + 0], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+}
+#[inline]
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+	read_u32(bytes, offset) as u64 | (read_u32(bytes, offset + 4) as u64) << 32
+}
+
+/// Computes the digest of a set of keys.
+///
+/// The digest is the wrapping sum over the keys of `hash(key, 1) << 32 | hash(key, 2)` and does not depend on the order of the keys.
+/// Tables record the digest of the keys they were built for to detect seeds paired with the wrong keys.
+pub fn key_digest<S: AsRef<str>>(keys: &[S]) -> u64 {
+	digest(keys.iter().map(|key| key.as_ref().as_bytes()))
+}
+
+/// Computes the digest of a set of byte string keys, a string has the same digest as its bytes.
+pub fn key_digest_bytes(keys: &[&[u8]]) -> u64 {
+	digest(keys.iter().copied())
+}
+
+fn digest<'a, I: Iterator<Item = &'a [u8]>>(keys: I) -> u64 {
+	keys.fold(0u64, |digest, key| {
+		let hash = (crate::hash(key, 1) as u64) << 32 | crate::hash(key, 2) as u64;
+		digest.wrapping_add(hash)
+	})
+}
+
+/// Encodes the seeds alone as a little-endian `u32` count followed by the seeds as little-endian words.
+///
+/// The seeds carry no header, checksum or key count, use the [binary format](crate::binary) to persist whole tables.
+/// Portable across hosts for sending seeds over a socket or storing them in a database blob.
+/// Panics if there are more than `u32::MAX` seeds.
+///
+/// # Examples
+///
+/// ```
+/// let seeds = mphf::build(&["hello", "goodbye", "cat", "dog"], 2, 10000).unwrap();
+/// let bytes = mphf::binary::seeds_to_bytes(&seeds);
+/// assert_eq!(bytes, [2, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0]);
+/// assert_eq!(mphf::binary::bytes_to_seeds(&bytes), Ok(seeds));
+/// ```
+#[cfg(feature = "std")]
+pub fn seeds_to_bytes(seeds: &[u32]) -> Vec<u8> {
+	let mut bytes = Vec::with_capacity(4 + seeds.len() * 4);
+	bytes.extend_from_slice(&encode_count(seeds.len()).expect("too many seeds to encode"));
+	for &seed in seeds {
+		bytes.extend_from_slice(&seed.to_le_bytes());
+	}
+	bytes
+}
+
+/// Decodes the seeds encoded by [`seeds_to_bytes`].
+///
+/// Fails if the input is not a whole number of words, if the count does not match the number of seeds or if there are no seeds.
+#[cfg(feature = "std")]
+pub fn bytes_to_seeds(bytes: &[u8]) -> Result<Seeds, DecodeError> {
+	if bytes.len() < 4 || bytes.len() % 4 != 0 {
+		return Err(DecodeError::Truncated);
+	}
+	let count = read_u32(bytes, 0) as usize;
+	let words = bytes.len() / 4 - 1;
+	if words < count {
+		return Err(DecodeError::Truncated);
+	}
+	if words > count {
+		return Err(DecodeError::TrailingBytes);
+	}
+	if count == 0 {
+		return Err(DecodeError::Invalid);
+	}
+	Ok(Seeds::from(bytes[4..].chunks_exact(4).map(|chunk| read_u32(chunk, 0)).collect::<Vec<u32>>()))
+}
+
+// Decoded header fields.
+struct Header {
+	len: usize,
+	seeds_len: usize,
+	values_len: usize,
+	bucket_seed: u32,
+	key_digest: u64,
+	crc: u32,
+	// Size of the header in bytes
+	size: usize,
+}
+
+impl Header {
+	// Computes the checksum of the header fields before it and the encoded seeds.
+	fn checksum(&self, header: &[u8], seeds: &[u8]) -> u32 {
+		let mut crc = Crc32::new();
+		crc.update(&header[..self.size - 4]);
+		crc.update(seeds);
+		crc.finish()
+	}
+	// Checks the build parameters are supported, after verifying the checksum.
+	fn check(&self) -> Result<(), DecodeError> {
+		if self.values_len != self.len || self.bucket_seed != 0 {
+			return Err(DecodeError::Invalid);
+		}
+		Ok(())
+	}
+}
+
+// Returns the size of the header in the given format version.
+#[inline]
+fn header_len(version: u8) -> usize {
+	if version == 1 { HEADER_LEN_V1 } else { HEADER_LEN }
+}
+
+// Decodes the header in any supported format version.
+fn decode_header(bytes: &[u8]) -> Result<Header, DecodeError> {
+	if bytes.len() < 4 {
+		return Err(DecodeError::Truncated);
+	}
+	if bytes[0..4] != MAGIC {
+		return Err(DecodeError::BadMagic);
+	}
+	if bytes.len() < 8 {
+		return Err(DecodeError::Truncated);
+	}
+	if bytes[4] < MIN_VERSION || bytes[4] > VERSION {
+		return Err(DecodeError::UnsupportedVersion(bytes[4]));
+	}
+	if bytes[5] != HASH_MURMUR3_32 {
+		return Err(DecodeError::UnsupportedHash(bytes[5]));
+	}
+	if bytes[6] != 0 || bytes[7] != 0 {
+		return Err(DecodeError::Invalid);
+	}
+	let size = header_len(bytes[4]);
+	if bytes.len() < size {
+		return Err(DecodeError::Truncated);
+	}
+	let len = read_u32(bytes, 8) as usize;
+	let seeds_len = read_u32(bytes, 12) as usize;
+	if bytes[4] == 1 {
+		return Ok(Header { len, seeds_len, values_len: len, bucket_seed: 0, key_digest: 0, crc: read_u32(bytes, 16), size });
+	}
+	let values_len = read_u32(bytes, 16) as usize;
+	let bucket_seed = read_u32(bytes, 20);
+	let key_digest = read_u64(bytes, 24);
+	let crc = read_u32(bytes, 32);
+	Ok(Header { len, seeds_len, values_len, bucket_seed, key_digest, crc, size })
+}
+
+// Converts a count to its 32-bit header field.
+#[inline]
+fn encode_count(count: usize) -> Result<[u8; 4], EncodeError> {
+	u32::try_from(count).map(u32::to_le_bytes).map_err(|_| EncodeError::TooLarge { count })
+}
+
+// Encodes the header of a table with the seeds and number of keys.
+fn encode_header(seeds: &[u32], len: usize, key_digest: u64) -> Result<[u8; HEADER_LEN], EncodeError> {
+	let mut header = [0u8; HEADER_LEN];
+	header[0..4].copy_from_slice(&MAGIC);
+	header[4] = VERSION;
+	header[5] = HASH_MURMUR3_32;
+	header[8..12].copy_from_slice(&encode_count(len)?);
+	header[12..16].copy_from_slice(&encode_count(seeds.len())?);
+	header[16..20].copy_from_slice(&encode_count(len)?);
+	header[24..32].copy_from_slice(&key_digest.to_le_bytes());
+	let mut crc = Crc32::new();
+	crc.update(&header[..32]);
+	for &seed in seeds {
+		crc.update(&seed.to_le_bytes());
+	}
+	header[32..36].copy_from_slice(&crc.finish().to_le_bytes());
+	Ok(header)
+}
+
+/// Returns the size in bytes of a table with `seeds_len` seeds in the [binary format](crate::binary).
+#[inline]
+pub const fn encoded_len(seeds_len: usize) -> usize {
+	seeds_len.saturating_mul(4).saturating_add(HEADER_LEN)
+}
+
+/// Encodes a table given its seeds and number of keys into the start of the buffer without allocating.
+///
+/// Only needs `core`, for targets which have the seeds but no allocator to build an [`Mphf`] with.
+/// Returns the number of bytes written, the rest of the buffer is left untouched.
+/// Fails if the buffer is smaller than [`encoded_len`] or if there are more than `u32::MAX` keys or seeds, nothing is written then.
+/// The seeds are not validated, seeds which do not describe a table of `len` keys are rejected when decoded.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = [0u8; 64];
+/// let size = mphf::binary::encode_into(&[0, 1], 4, &mut buf).unwrap();
+/// assert_eq!(size, mphf::binary::encoded_len(2));
+/// let view = mphf::MphfRef::from_bytes(&buf[..size]).unwrap();
+/// assert_eq!(view.len(), 4);
+/// ```
+pub fn encode_into(seeds: &[u32], len: usize, buf: &mut [u8]) -> Result<usize, EncodeError> {
+	let header = encode_header(seeds, len, 0)?;
+	let needed = encoded_len(seeds.len());
+	if buf.len() < needed {
+		return Err(EncodeError::BufferTooSmall { needed });
+	}
+	buf[..HEADER_LEN].copy_from_slice(&header);
+	for (chunk, &seed) in buf[HEADER_LEN..needed].chunks_exact_mut(4).zip(seeds) {
+		chunk.copy_from_slice(&seed.to_le_bytes());
+	}
+	Ok(needed)
+}
+
+#[cfg(feature = "std")]
+impl Mphf {
+	fn encode(&self, key_digest: u64) -> Vec<u8> {
+		let seeds = self.seeds();
+		let mut bytes = Vec::with_capacity(HEADER_LEN + seeds.len() * 4);
+		bytes.extend_from_slice(&encode_header(seeds, self.len(), key_digest).expect("mphf table too large for the binary format"));
+		for &seed in seeds.iter() {
+			bytes.extend_from_slice(&seed.to_le_bytes());
+		}
+		bytes
+	}
+
+	/// Encodes the table in the [binary format](crate::binary).
+	///
+	/// The key set digest is not recorded, see [`to_bytes_with_keys`](Mphf::to_bytes_with_keys).
+	///
+	/// # Panics
+	///
+	/// Panics if the table has more than `u32::MAX` keys, which the binary format cannot store.
+	/// Use [`encode_into`](Mphf::encode_into) to handle the error.
+	pub fn to_bytes(&self) -> Vec<u8> {
+		self.encode(0)
+	}
+
+	/// Encodes the table in the [binary format](crate::binary) recording the [digest](key_digest) of its keys.
+	///
+	/// The keys must be the ones the table was built for.
+	/// Panics the same as [`to_bytes`](Mphf::to_bytes).
+	pub fn to_bytes_with_keys<S: AsRef<str>>(&self, keys: &[S]) -> Vec<u8> {
+		debug_assert_eq!(keys.len(), self.len());
+		self.encode(key_digest(keys))
+	}
+
+	/// Returns the size in bytes of the table in the [binary format](crate::binary).
+	#[inline]
+	pub fn encoded_len(&self) -> usize {
+		encoded_len(self.seeds().len())
+	}
+
+	/// Encodes the table in the [binary format](crate::binary) into the start of the buffer without allocating.
+	///
+	/// The same as the free function [`encode_into`](crate::binary::encode_into) with the seeds and number of keys of the table.
+	/// The key set digest is not recorded, the bytes are the same as [`to_bytes`](Mphf::to_bytes).
+	/// Decode the table in place with [`MphfRef::from_bytes`].
+	pub fn encode_into(&self, buf: &mut [u8]) -> Result<usize, EncodeError> {
+		encode_into(self.seeds(), self.len(), buf)
+	}
+
+	/// Decodes a table from the [binary format](crate::binary).
+	pub fn from_bytes(bytes: &[u8]) -> Result<Mphf, DecodeError> {
+		MphfRef::from_bytes(bytes).map(|mphf| mphf.to_owned())
+	}
+
+	/// Decodes a table from the [binary format](crate::binary) rejecting tables exceeding the limits.
+	pub fn from_bytes_with_limits(bytes: &[u8], limits: &Limits) -> Result<Mphf, DecodeError> {
+		MphfRef::from_bytes_with_limits(bytes, limits).map(|mphf| mphf.to_owned())
+	}
+
+	/// Decodes a table from the [binary format](crate::binary) and verifies it belongs to the keys.
+	///
+	/// See [`MphfRef::verify_keys`].
+	pub fn from_bytes_with_keys<S: AsRef<str>>(bytes: &[u8], keys: &[S]) -> Result<Mphf, DecodeError> {
+		let mphf = MphfRef::from_bytes(bytes)?;
+		mphf.verify_keys(keys)?;
+		Ok(mphf.to_owned())
+	}
+
+	/// Decodes a table from the [binary format](crate::binary) without verifying the checksum.
+	///
+	/// Dangerous: a corrupted seed is not detected and silently maps keys to the wrong index.
+	/// Only use this when the bytes are known to be intact.
+	pub fn from_bytes_unchecked(bytes: &[u8]) -> Result<Mphf, DecodeError> {
+		MphfRef::from_bytes_unchecked(bytes).map(|mphf| mphf.to_owned())
+	}
+
+	/// Wraps a headerless dump of the seeds as little-endian words for a set of `key_count` keys.
+	///
+	/// Migrates tables persisted before the [binary format](crate::binary) existed, there is no checksum to verify.
+	/// Fails with the same checks as [`from_seeds`](Mphf::from_seeds).
+	pub fn from_legacy_seeds(bytes: &[u8], key_count: usize) -> Result<Mphf, DecodeError> {
+		if bytes.len() % 4 != 0 {
+			return Err(DecodeError::Truncated);
+		}
+		let seeds = bytes.chunks_exact(4).map(|chunk| read_u32(chunk, 0)).collect::<Vec<u32>>();
+		Mphf::from_seeds(Seeds::from(seeds), key_count).ok_or(DecodeError::Invalid)
+	}
+
+	/// Writes the table in the [binary format](crate::binary).
+	///
+	/// The key set digest is not recorded, see [`write_to_with_keys`](Mphf::write_to_with_keys).
+	/// Tables with more than `u32::MAX` keys fail with [`InvalidInput`](io::ErrorKind::InvalidInput) before anything is written.
+	pub fn write_to<W: io::Write>(&self, w: W) -> io::Result<()> {
+		self.write(0, w)
+	}
+
+	/// Writes the table in the [binary format](crate::binary) recording the [digest](key_digest) of its keys.
+	///
+	/// The keys must be the ones the table was built for.
+	pub fn write_to_with_keys<S: AsRef<str>, W: io::Write>(&self, keys: &[S], w: W) -> io::Result<()> {
+		debug_assert_eq!(keys.len(), self.len());
+		self.write(key_digest(keys), w)
+	}
+
+	fn write<W: io::Write>(&self, key_digest: u64, mut w: W) -> io::Result<()> {
+		let header = encode_header(self.seeds(), self.len(), key_digest).map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+		w.write_all(&header)?;
+		let mut buf = [0u8; 1024];
+		for chunk in self.seeds().chunks(buf.len() / 4) {
+			for (i, &seed) in chunk.iter().enumerate() {
+				buf[i * 4..i * 4 + 4].copy_from_slice(&seed.to_le_bytes());
+			}
+			w.write_all(&buf[..chunk.len() * 4])?;
+		}
+		Ok(())
+	}
+
+	/// Reads a table in the [binary format](crate::binary).
+	///
+	/// Stops reading at the end of the table, the stream may continue with other data.
+	/// Tables exceeding the [default limits](Limits::default) are rejected.
+	pub fn read_from<R: io::Read>(r: R) -> Result<Mphf, LoadError> {
+		Mphf::read_from_with_limits(r, &Limits::default())
+	}
+
+	/// Reads a table in the [binary format](crate::binary) with at most `max_seeds` seeds.
+	///
+	/// The limit is checked before allocating so a corrupt header cannot trigger a huge allocation.
+	pub fn read_from_limited<R: io::Read>(r: R, max_seeds: usize) -> Result<Mphf, LoadError> {
+		Mphf::read_from_with_limits(r, &Limits { max_seeds, ..Limits::default() })
+	}
+
+	/// Reads a table in the [binary format](crate::binary) rejecting tables exceeding the limits.
+	///
+	/// The limits are checked before allocating so a corrupt header cannot trigger a huge allocation.
+	pub fn read_from_with_limits<R: io::Read>(r: R, limits: &Limits) -> Result<Mphf, LoadError> {
+		Mphf::read(r, limits).map(|(mphf, _)| mphf)
+	}
+
+	// Reads a table and the recorded key set digest.
+	pub(crate) fn read<R: io::Read>(mut r: R, limits: &Limits) -> Result<(Mphf, u64), LoadError> {
+		let mut header = [0u8; HEADER_LEN];
+		r.read_exact(&mut header[..8])?;
+		// Validate the fixed fields before reading the rest of the header
+		if let Err(err) = decode_header(&header[..8]) {
+			if err != DecodeError::Truncated {
+				return Err(err.into());
+			}
+		}
+		let size = header_len(header[4]);
+		r.read_exact(&mut header[8..size])?;
+		let header_bytes = &header[..size];
+		let header = decode_header(header_bytes)?;
+		if let Some(count) = limits.exceeded(&header) {
+			return Err(DecodeError::TooLarge(count).into());
+		}
+
+		let size = header.seeds_len.checked_mul(4).ok_or(DecodeError::TooLarge(header.seeds_len))?;
+		let mut bytes = vec![0u8; size];
+		r.read_exact(&mut bytes)?;
+		if header.checksum(header_bytes, &bytes) != header.crc {
+			return Err(LoadError::Decode(DecodeError::ChecksumMismatch));
+		}
+		header.check()?;
+		let seeds = bytes.chunks_exact(4).map(|chunk| read_u32(chunk, 0)).collect::<Vec<u32>>();
+		let mphf = Mphf::from_seeds(Seeds::from(seeds), header.len).ok_or(DecodeError::Invalid)?;
+		Ok((mphf, header.key_digest))
+	}
+}
+
+/// Borrowed [`Mphf`] table in the [binary format](crate::binary).
+///
+/// Reads the seeds directly from the underlying bytes without copying them.
+/// The bytes do not need to be aligned.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MphfRef<'a> {
+	seeds: &'a [u8],
+	len: usize,
+	key_digest: u64,
+}
+
+impl<'a> MphfRef<'a> {
+	/// Validates the bytes as a table in the [binary format](crate::binary).
+	pub fn from_bytes(bytes: &'a [u8]) -> Result<MphfRef<'a>, DecodeError> {
+		MphfRef::decode(bytes, true, &Limits::NONE)
+	}
+
+	/// Validates the bytes as a table in the [binary format](crate::binary) rejecting tables exceeding the limits.
+	pub fn from_bytes_with_limits(bytes: &'a [u8], limits: &Limits) -> Result<MphfRef<'a>, DecodeError> {
+		MphfRef::decode(bytes, true, limits)
+	}
+
+	/// Validates the bytes as a table in the [binary format](crate::binary) without verifying the checksum.
+	///
+	/// Dangerous: a corrupted seed is not detected and silently maps keys to the wrong index.
+	/// Only use this when the bytes are known to be intact.
+	pub fn from_bytes_unchecked(bytes: &'a [u8]) -> Result<MphfRef<'a>, DecodeError> {
+		MphfRef::decode(bytes, false, &Limits::NONE)
+	}
+
+	pub(crate) fn decode(bytes: &'a [u8], verify: bool, limits: &Limits) -> Result<MphfRef<'a>, DecodeError> {
+		let header = decode_header(bytes)?;
+		if let Some(count) = limits.exceeded(&header) {
+			return Err(DecodeError::TooLarge(count));
+		}
+		let seeds_len = header.seeds_len;
+
+		let seeds = &bytes[header.size..];
+		let size = seeds_len.checked_mul(4).ok_or(DecodeError::Truncated)?;
+		if seeds.len() < size {
+			return Err(DecodeError::Truncated);
+		}
+		if seeds.len() != size {
+			return Err(DecodeError::TrailingBytes);
+		}
+		if verify && header.checksum(bytes, seeds) != header.crc {
+			return Err(DecodeError::ChecksumMismatch);
+		}
+		header.check()?;
+
+		// Same checks as Mphf::from_seeds
+		if seeds_len == 0 {
+			return Err(DecodeError::Invalid);
+		}
+		let len = header.len;
+		let used = (0..seeds_len).filter(|&i| read_u32(seeds, i * 4) != u32::MAX).count();
+		if used > len || (len > 0 && used == 0) {
+			return Err(DecodeError::Invalid);
+		}
+
+		Ok(MphfRef { seeds, len, key_digest: header.key_digest })
+	}
+
+	// Wraps a table previously validated by `decode`.
+	#[cfg(feature = "mmap")]
+	#[inline]
+	pub(crate) fn from_validated(bytes: &'a [u8]) -> MphfRef<'a> {
+		let header = decode_header(bytes).unwrap();
+		MphfRef { seeds: &bytes[header.size..], len: header.len, key_digest: header.key_digest }
+	}
+
+	/// Returns the number of keys.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.len
+	}
+	/// Returns true if built over an empty set of keys.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+	/// Returns the number of seeds.
+	#[inline]
+	pub fn seeds_len(&self) -> usize {
+		self.seeds.len() / 4
+	}
+	/// Returns the seed at the given index.
+	#[inline]
+	pub fn seed(&self, index: usize) -> Option<u32> {
+		let start = index.checked_mul(4)?;
+		let bytes = self.seeds.get(start..start.checked_add(4)?)?;
+		Some(read_u32(bytes, 0))
+	}
+	/// Returns the recorded [digest](key_digest) of the keys, if any.
+	#[inline]
+	pub fn key_digest(&self) -> Option<u64> {
+		if self.key_digest == 0 { None } else { Some(self.key_digest) }
+	}
+
+	/// Verifies the table belongs to the keys.
+	///
+	/// Checks the number of keys and the recorded [digest](key_digest) of the keys.
+	/// Tables without a recorded digest only have their number of keys checked.
+	pub fn verify_keys<S: AsRef<str>>(&self, keys: &[S]) -> Result<(), DecodeError> {
+		if keys.len() != self.len {
+			return Err(DecodeError::KeyMismatch);
+		}
+		if let Some(digest) = self.key_digest() {
+			if key_digest(keys) != digest {
+				return Err(DecodeError::KeyMismatch);
+			}
+		}
+		Ok(())
+	}
+
+	/// Returns the index of the given key.
+	#[inline]
+	pub fn index(&self, key: &str) -> Option<usize> {
+		if self.len == 0 {
+			return None;
+		}
+		let key = key.as_bytes();
+		let h0 = crate::hash(key, 0) as usize % self.seeds_len();
+		let seed = self.seed(h0)?;
+		if seed == u32::MAX {
+			return None;
+		}
+		return Some(crate::hash(key, seed) as usize % self.len);
+	}
+	/// Gets the value of the given key from the values in mphf order.
+	#[inline]
+	pub fn get<'v, T>(&self, key: &str, values: &'v [T]) -> Option<&'v T> {
+		values.get(self.index(key)?)
+	}
+
+	/// Copies the seeds into an owned table.
+	#[cfg(feature = "std")]
+	pub fn to_owned(&self) -> Mphf {
+		let seeds = (0..self.seeds_len()).map(|i| read_u32(self.seeds, i * 4)).collect::<Vec<u32>>();
+		// Already validated
+		Mphf::from_seeds(Seeds::from(seeds), self.len).unwrap()
+	}
+}
+
+#[cfg(test)]
+const GOLDEN: [u8; 44] = [
+	b'M', b'P', b'H', b'F', 2, 0, 0, 0,
+	4, 0, 0, 0, 2, 0, 0, 0,
+	4, 0, 0, 0, 0, 0, 0, 0,
+	0, 0, 0, 0, 0, 0, 0, 0,
+	0x37, 0xfc, 0xf8, 0xcd,
+	0, 0, 0, 0, 1, 0, 0, 0,
+];
+#[cfg(all(test, feature = "std"))]
+const GOLDEN_V1: [u8; 28] = [
+	b'M', b'P', b'H', b'F', 1, 0, 0, 0,
+	4, 0, 0, 0, 2, 0, 0, 0,
+	0xe4, 0xab, 0x8b, 0x2f,
+	0, 0, 0, 0, 1, 0, 0, 0,
+];
+
+// Updates the checksum after tampering with the contents.
+#[cfg(all(test, feature = "std"))]
+fn fix_checksum(bytes: &mut [u8]) {
+	let mut crc = Crc32::new();
+	crc.update(&bytes[..32]);
+	crc.update(&bytes[HEADER_LEN..]);
+	bytes[32..36].copy_from_slice(&crc.finish().to_le_bytes());
+}
+
+// The same table in every supported format version.
+#[cfg(all(test, feature = "std"))]
+const GOLDEN_VERSIONS: [(u8, &[u8]); 2] = [
+	(1, &GOLDEN_V1),
+	(2, &GOLDEN),
+];
+
+#[cfg(feature = "std")]
+#[test]
+fn test_golden() {
+	let mphf = Mphf::build(&["hello", "goodbye", "cat", "dog"], 2, 10000).unwrap();
+	assert_eq!(mphf.to_bytes(), GOLDEN);
+	assert_eq!(Mphf::from_bytes(&GOLDEN), Ok(mphf.clone()));
+
+	for version in MIN_VERSION..=VERSION {
+		let &(_, bytes) = GOLDEN_VERSIONS.iter().find(|&&(v, _)| v == version).expect("missing golden blob");
+		assert_eq!(bytes[4], version);
+		assert_eq!(Mphf::from_bytes(bytes).as_ref(), Ok(&mphf), "version {}", version);
+		assert_eq!(Mphf::read_from(bytes).as_ref().ok(), Some(&mphf), "version {}", version);
+	}
+}
+
+#[test]
+fn test_decode_core() {
+	// Decoding does not need std
+	let view = MphfRef::from_bytes(&GOLDEN).unwrap();
+	assert_eq!((view.len(), view.seeds_len()), (4, 2));
+	assert_eq!((view.seed(0), view.seed(1), view.seed(2)), (Some(0), Some(1), None));
+	let mut indices = [false; 4];
+	for &key in &["hello", "goodbye", "cat", "dog"] {
+		indices[view.index(key).unwrap()] = true;
+	}
+	assert_eq!(indices, [true; 4]);
+	assert_eq!(view.verify_keys(&["a", "b", "c", "d"]), Ok(()));
+
+	let mut bytes = GOLDEN;
+	bytes[HEADER_LEN] ^= 0x10;
+	assert_eq!(MphfRef::from_bytes(&bytes), Err(DecodeError::ChecksumMismatch));
+	assert_eq!(MphfRef::from_bytes(&GOLDEN[..HEADER_LEN]), Err(DecodeError::Truncated));
+
+	// Encoding from borrowed seeds does not need std either
+	let mut buf = [0u8; GOLDEN.len()];
+	assert_eq!(encoded_len(2), GOLDEN.len());
+	assert_eq!(encode_into(&[0, 1], 4, &mut buf), Ok(GOLDEN.len()));
+	assert_eq!(buf, GOLDEN);
+	let mut buf = [0xaau8; 64];
+	assert_eq!(encode_into(&[0, 1], 4, &mut buf), Ok(GOLDEN.len()));
+	assert_eq!(buf[..GOLDEN.len()], GOLDEN);
+	assert!(buf[GOLDEN.len()..].iter().all(|&byte| byte == 0xaa));
+	let mut buf = [0xaau8; GOLDEN.len() - 1];
+	assert_eq!(encode_into(&[0, 1], 4, &mut buf), Err(EncodeError::BufferTooSmall { needed: GOLDEN.len() }));
+	assert!(buf.iter().all(|&byte| byte == 0xaa));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_key_digest() {
+	let keys_a = ["hello", "goodbye", "cat", "dog"];
+	let keys_b = ["hello", "goodbye", "cat", "bird"];
+	assert_eq!(key_digest(&keys_a), key_digest(&["dog", "cat", "goodbye", "hello"]));
+	assert_ne!(key_digest(&keys_a), key_digest(&keys_b));
+	assert_eq!(key_digest_bytes(&[b"dog", b"hello", b"cat", b"goodbye"]), key_digest(&keys_a));
+
+	let mphf = Mphf::build(&keys_a, 2, 10000).unwrap();
+	let bytes = mphf.to_bytes_with_keys(&keys_a);
+	let view = MphfRef::from_bytes(&bytes).unwrap();
+	assert_eq!(view.key_digest(), Some(key_digest(&keys_a)));
+	assert_eq!(view.verify_keys(&keys_a), Ok(()));
+	assert_eq!(Mphf::from_bytes_with_keys(&bytes, &keys_a).as_ref(), Ok(&mphf));
+
+	// Seeds of table A paired with the keys of table B
+	assert_eq!(view.verify_keys(&keys_b), Err(DecodeError::KeyMismatch));
+	assert_eq!(Mphf::from_bytes_with_keys(&bytes, &keys_b), Err(DecodeError::KeyMismatch));
+	assert_eq!(Mphf::from_bytes_with_keys(&bytes, &keys_a[..3]), Err(DecodeError::KeyMismatch));
+
+	// Without a recorded digest only the number of keys is checked
+	let view = MphfRef::from_bytes(&GOLDEN).unwrap();
+	assert_eq!(view.key_digest(), None);
+	assert_eq!(view.verify_keys(&keys_b), Ok(()));
+	assert_eq!(view.verify_keys(&keys_b[..3]), Err(DecodeError::KeyMismatch));
+
+	let mut stream = Vec::new();
+	mphf.write_to_with_keys(&keys_a, &mut stream).unwrap();
+	assert_eq!(stream, bytes);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_reproducible() {
+	let keys = (0..200).map(|i| format!("key{}", i)).collect::<Vec<String>>();
+	let mut keys = keys.iter().map(|key| key.as_str()).collect::<Vec<&str>>();
+	let expected = Mphf::build(&keys, 50, 100000).unwrap().to_bytes_with_keys(&keys);
+
+	// Shuffle the keys with a xorshift rng
+	let mut rng = 0x2545F4914F6CDD1Du64;
+	for _ in 0..100 {
+		for i in (1..keys.len()).rev() {
+			rng ^= rng << 13;
+			rng ^= rng >> 7;
+			rng ^= rng << 17;
+			keys.swap(i, (rng % (i as u64 + 1)) as usize);
+		}
+		assert_eq!(Mphf::build(&keys, 50, 100000).unwrap().to_bytes_with_keys(&keys), expected);
+	}
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_legacy_seeds() {
+	let mphf = Mphf::build(&["hello", "goodbye", "cat", "dog"], 2, 10000).unwrap();
+	let legacy = [0, 0, 0, 0, 1, 0, 0, 0];
+	assert_eq!(Mphf::from_legacy_seeds(&legacy, 4).as_ref(), Ok(&mphf));
+	assert_eq!(Mphf::from_legacy_seeds(&legacy, 4).unwrap().to_bytes(), GOLDEN);
+
+	assert_eq!(Mphf::from_legacy_seeds(&legacy[..7], 4), Err(DecodeError::Truncated));
+	assert_eq!(Mphf::from_legacy_seeds(&[], 0), Err(DecodeError::Invalid));
+	assert_eq!(Mphf::from_legacy_seeds(&legacy, 1), Err(DecodeError::Invalid));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_seeds_bytes() {
+	let seeds = crate::build(&["hello", "goodbye", "cat", "dog", "bird"], 3, 10000).unwrap();
+	let bytes = seeds_to_bytes(&seeds);
+	assert_eq!(bytes.len(), 16);
+	assert_eq!(bytes[0..4], [3, 0, 0, 0]);
+	assert_eq!(bytes_to_seeds(&bytes), Ok(seeds));
+	assert_eq!(bytes_to_seeds(&seeds_to_bytes(&[u32::MAX, 7])).unwrap().as_slice(), &[u32::MAX, 7]);
+
+	assert_eq!(bytes_to_seeds(&[]), Err(DecodeError::Truncated));
+	assert_eq!(bytes_to_seeds(&bytes[..15]), Err(DecodeError::Truncated));
+	assert_eq!(bytes_to_seeds(&bytes[..12]), Err(DecodeError::Truncated));
+	assert_eq!(bytes_to_seeds(&[bytes.as_slice(), &[0; 4]].concat()), Err(DecodeError::TrailingBytes));
+	assert_eq!(bytes_to_seeds(&[0, 0, 0, 0]), Err(DecodeError::Invalid));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_decode_errors() {
+	for i in 0..GOLDEN.len() {
+		assert_eq!(Mphf::from_bytes(&GOLDEN[..i]), Err(DecodeError::Truncated), "truncated to {} bytes", i);
+	}
+
+	let mut bytes = GOLDEN;
+	bytes[0] = b'X';
+	assert_eq!(Mphf::from_bytes(&bytes), Err(DecodeError::BadMagic));
+
+	let mut bytes = GOLDEN;
+	bytes[4] = VERSION + 1;
+	assert_eq!(Mphf::from_bytes(&bytes), Err(DecodeError::UnsupportedVersion(VERSION + 1)));
+	bytes[4] = MIN_VERSION - 1;
+	assert_eq!(Mphf::from_bytes(&bytes), Err(DecodeError::UnsupportedVersion(MIN_VERSION - 1)));
+
+	let mut bytes = GOLDEN;
+	bytes[5] = 1;
+	assert_eq!(Mphf::from_bytes(&bytes), Err(DecodeError::UnsupportedHash(1)));
+
+	let mut bytes = GOLDEN;
+	bytes[6] = 1;
+	assert_eq!(Mphf::from_bytes(&bytes), Err(DecodeError::Invalid));
+
+	let mut bytes = GOLDEN.to_vec();
+	bytes.push(0);
+	assert_eq!(Mphf::from_bytes(&bytes), Err(DecodeError::TrailingBytes));
+
+	// Claims three seeds but only has two
+	let mut bytes = GOLDEN;
+	bytes[12] = 3;
+	assert_eq!(Mphf::from_bytes(&bytes), Err(DecodeError::Truncated));
+
+	// Claims one seed but has two
+	let mut bytes = GOLDEN;
+	bytes[12] = 1;
+	assert_eq!(Mphf::from_bytes(&bytes), Err(DecodeError::TrailingBytes));
+
+	// Two buckets in use but only one key, with a matching number of values so only the seeds are inconsistent
+	let mut bytes = GOLDEN;
+	bytes[8] = 1;
+	bytes[16] = 1;
+	assert_eq!(Mphf::from_bytes(&bytes), Err(DecodeError::ChecksumMismatch));
+	fix_checksum(&mut bytes);
+	assert_eq!(Mphf::from_bytes(&bytes), Err(DecodeError::Invalid));
+
+	// Unsupported build parameters
+	let mut bytes = GOLDEN;
+	bytes[16] = 5;
+	fix_checksum(&mut bytes);
+	assert_eq!(Mphf::from_bytes(&bytes), Err(DecodeError::Invalid));
+	let mut bytes = GOLDEN;
+	bytes[20] = 1;
+	fix_checksum(&mut bytes);
+	assert_eq!(Mphf::from_bytes(&bytes), Err(DecodeError::Invalid));
+	assert!(matches!(Mphf::read_from(&bytes[..]), Err(LoadError::Decode(DecodeError::Invalid))));
+
+	// No seeds at all
+	let mut bytes = GOLDEN[..HEADER_LEN].to_vec();
+	bytes[12] = 0;
+	fix_checksum(&mut bytes);
+	assert_eq!(Mphf::from_bytes(&bytes), Err(DecodeError::Invalid));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_checksum() {
+	// Flipping any byte is detected
+	for i in 0..GOLDEN.len() {
+		let mut bytes = GOLDEN;
+		bytes[i] ^= 0x10;
+		assert!(Mphf::from_bytes(&bytes).is_err(), "flipped byte {}", i);
+		assert!(Mphf::read_from(&bytes[..]).is_err(), "flipped byte {}", i);
+	}
+
+	// Flipping a seed is only caught by the checksum
+	let mut bytes = GOLDEN;
+	bytes[HEADER_LEN] ^= 0x10;
+	assert_eq!(Mphf::from_bytes(&bytes), Err(DecodeError::ChecksumMismatch));
+	assert!(matches!(Mphf::read_from(&bytes[..]), Err(LoadError::Decode(DecodeError::ChecksumMismatch))));
+	let mphf = Mphf::from_bytes_unchecked(&bytes).unwrap();
+	assert_eq!(mphf.seeds().as_slice(), &[0x10, 1]);
+	assert!(MphfRef::from_bytes_unchecked(&bytes).is_ok());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_mphf_ref() {
+	let keys = (0..200).map(|i| format!("key{}", i)).collect::<Vec<String>>();
+	let keys = keys.iter().map(|key| key.as_str()).collect::<Vec<&str>>();
+	let mphf = Mphf::build(&keys, 50, 100000).unwrap();
+	let bytes = mphf.to_bytes();
+
+	// Shift the blob by one byte to misalign the seeds
+	let mut shifted = vec![0];
+	shifted.extend_from_slice(&bytes);
+
+	for bytes in &[&bytes[..], &shifted[1..]] {
+		let view = MphfRef::from_bytes(bytes).unwrap();
+		assert_eq!(view.len(), mphf.len());
+		assert_eq!(view.seeds_len(), mphf.seeds().len());
+		for &key in &keys {
+			assert_eq!(view.index(key), mphf.index(key));
+		}
+		assert_eq!(view.index("not a key"), mphf.index("not a key"));
+		assert_eq!(view.to_owned(), mphf);
+	}
+
+	assert_eq!(MphfRef::from_bytes(&bytes[..bytes.len() - 1]), Err(DecodeError::Truncated));
+	assert_eq!(MphfRef::from_bytes(&shifted), Err(DecodeError::BadMagic));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_read_write() {
+	let mphf = Mphf::build(&["hello", "goodbye", "cat", "dog"], 2, 10000).unwrap();
+
+	let mut stream = Vec::new();
+	mphf.write_to(&mut stream).unwrap();
+	assert_eq!(stream, GOLDEN);
+	// Reading stops at the end of the table
+	stream.extend_from_slice(b"more data");
+	let mut cursor = io::Cursor::new(&stream);
+	assert_eq!(Mphf::read_from(&mut cursor).unwrap(), mphf);
+	assert_eq!(cursor.position(), GOLDEN.len() as u64);
+
+	// Truncated streams
+	for i in 0..GOLDEN.len() {
+		let mut cursor = io::Cursor::new(&GOLDEN[..i]);
+		assert!(matches!(Mphf::read_from(&mut cursor), Err(LoadError::Decode(DecodeError::Truncated))), "truncated to {} bytes", i);
+	}
+
+	// Header claiming a huge number of seeds is rejected before allocating
+	let mut bytes = GOLDEN;
+	bytes[12..16].copy_from_slice(&u32::MAX.to_le_bytes());
+	assert!(matches!(Mphf::read_from(&bytes[..]), Err(LoadError::Decode(DecodeError::TooLarge(0xffffffff)))));
+	assert!(matches!(Mphf::read_from_limited(&GOLDEN[..], 1), Err(LoadError::Decode(DecodeError::TooLarge(2)))));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_encode_into() {
+	let mphf = Mphf::build(&["hello", "goodbye", "cat", "dog"], 2, 10000).unwrap();
+	assert_eq!(mphf.encoded_len(), GOLDEN.len());
+
+	let mut buf = [0u8; GOLDEN.len()];
+	assert_eq!(mphf.encode_into(&mut buf), Ok(GOLDEN.len()));
+	assert_eq!(buf, GOLDEN);
+	assert_eq!(MphfRef::from_bytes(&buf).unwrap().to_owned(), mphf);
+
+	// Oversized buffers are written at the start
+	let mut buf = [0xaau8; 64];
+	assert_eq!(mphf.encode_into(&mut buf), Ok(GOLDEN.len()));
+	assert_eq!(buf[..GOLDEN.len()], GOLDEN);
+	assert!(buf[GOLDEN.len()..].iter().all(|&byte| byte == 0xaa));
+
+	// Undersized buffers are left untouched
+	let mut buf = [0xaau8; GOLDEN.len() - 1];
+	assert_eq!(mphf.encode_into(&mut buf), Err(EncodeError::BufferTooSmall { needed: GOLDEN.len() }));
+	assert!(buf.iter().all(|&byte| byte == 0xaa));
+	assert_eq!(mphf.encode_into(&mut []), Err(EncodeError::BufferTooSmall { needed: GOLDEN.len() }));
+}
+
+#[cfg(all(feature = "std", target_pointer_width = "64"))]
+#[test]
+fn test_encode_too_large() {
+	// More keys than the header can store are an error instead of being truncated
+	let len = u32::MAX as usize + 2;
+	let mut buf = [0u8; 64];
+	assert_eq!(encode_into(&[0], len, &mut buf), Err(EncodeError::TooLarge { count: len }));
+	assert_eq!(encode_into(&[0], len, &mut []), Err(EncodeError::TooLarge { count: len }));
+	assert_eq!(encoded_len(usize::MAX), usize::MAX);
+
+	let mphf = Mphf::from_seeds(Seeds::from(vec![0]), len).unwrap();
+	assert_eq!(mphf.encode_into(&mut buf), Err(EncodeError::TooLarge { count: len }));
+	let mut stream = Vec::new();
+	assert_eq!(mphf.write_to(&mut stream).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+	assert!(stream.is_empty());
+}
+
+#[cfg(all(feature = "std", target_pointer_width = "64"))]
+#[test]
+#[should_panic(expected = "mphf table too large for the binary format")]
+fn test_to_bytes_too_large() {
+	let mphf = Mphf::from_seeds(Seeds::from(vec![0]), u32::MAX as usize + 1).unwrap();
+	mphf.to_bytes();
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_limits() {
+	let limits = Limits { max_keys: 3, max_seeds: 2 };
+	assert_eq!(Mphf::from_bytes_with_limits(&GOLDEN, &limits), Err(DecodeError::TooLarge(4)));
+	assert!(matches!(Mphf::read_from_with_limits(&GOLDEN[..], &limits), Err(LoadError::Decode(DecodeError::TooLarge(4)))));
+	let limits = Limits { max_keys: 4, max_seeds: 1 };
+	assert_eq!(MphfRef::from_bytes_with_limits(&GOLDEN, &limits), Err(DecodeError::TooLarge(2)));
+	assert!(matches!(Mphf::read_from_with_limits(&GOLDEN[..], &limits), Err(LoadError::Decode(DecodeError::TooLarge(2)))));
+	let limits = Limits { max_keys: 4, max_seeds: 2 };
+	assert!(Mphf::from_bytes_with_limits(&GOLDEN, &limits).is_ok());
+	assert!(Mphf::read_from_with_limits(&GOLDEN[..], &limits).is_ok());
+
+	// Hostile headers with a valid checksum
+	for &(len, seeds_len) in &[(u32::MAX, u32::MAX), (0, u32::MAX), (u32::MAX, 1), (1 << 30, 1 << 30)] {
+		let mut bytes = GOLDEN;
+		bytes[8..12].copy_from_slice(&len.to_le_bytes());
+		bytes[12..16].copy_from_slice(&seeds_len.to_le_bytes());
+		bytes[16..20].copy_from_slice(&len.to_le_bytes());
+		fix_checksum(&mut bytes);
+		assert!(Mphf::from_bytes(&bytes).is_err());
+		assert!(Mphf::read_from(&bytes[..]).is_err());
+		assert!(Mphf::read_from_with_limits(&bytes[..], &Limits { max_keys: 1 << 20, max_seeds: 1 << 20 }).is_err());
+	}
+
+	let view = MphfRef::from_bytes(&GOLDEN).unwrap();
+	assert_eq!(view.seed(usize::MAX), None);
+	assert_eq!(view.seed(usize::MAX / 4), None);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_decode_random() {
+	// Random and mutated inputs never panic
+	let mut rng = 0x9E3779B97F4A7C15u64;
+	let mut next = move || {
+		rng ^= rng << 13;
+		rng ^= rng >> 7;
+		rng ^= rng << 17;
+		rng
+	};
+	for _ in 0..10000 {
+		let len = (next() % 64) as usize;
+		let mut bytes = (0..len).map(|_| next() as u8).collect::<Vec<u8>>();
+		// Most random inputs fail on the magic, give the header a chance
+		if next() % 2 == 0 && bytes.len() >= 8 {
+			bytes[..8].copy_from_slice(&GOLDEN[..8]);
+			if bytes.len() >= HEADER_LEN && next() % 2 == 0 {
+				fix_checksum(&mut bytes);
+			}
+		}
+		let _ = Mphf::from_bytes(&bytes);
+		let _ = Mphf::from_bytes_unchecked(&bytes);
+		let _ = Mphf::read_from(&bytes[..]);
+		let _ = Mphf::from_legacy_seeds(&bytes, next() as usize);
+		if let Ok(view) = MphfRef::from_bytes_unchecked(&bytes) {
+			let _ = view.index("key");
+			let _ = view.seed(next() as usize);
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_golden_lookups() {
+	// Hard-coded little-endian table, decoding must not depend on the host byte order
+	const BYTES: [u8; 40] = [
+		0x4D, 0x50, 0x48, 0x46, 0x02, 0x00, 0x00, 0x00,
+		0x09, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+		0x09, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+		0x80, 0xFD, 0xC0, 0xC4, 0x25, 0xF4, 0xE8, 0x82,
+		0xFE, 0x45, 0x45, 0xAD, 0x37, 0x02, 0x00, 0x00,
+	];
+	const EXPECTED: [(&str, usize); 9] = [
+		("red", 2), ("green", 3), ("blue", 0), ("cyan", 7), ("magenta", 6),
+		("yellow", 4), ("black", 1), ("white", 8), ("orange", 5),
+	];
+
+	let mphf = Mphf::from_bytes(&BYTES).unwrap();
+	assert_eq!(mphf.len(), 9);
+	assert_eq!(mphf.seeds().as_slice(), &[567]);
+	let view = MphfRef::from_bytes(&BYTES).unwrap();
+	let streamed = Mphf::read_from(&BYTES[..]).unwrap();
+	for &(key, index) in &EXPECTED {
+		assert_eq!(mphf.index(key), Some(index), "{}", key);
+		assert_eq!(view.index(key), Some(index), "{}", key);
+		assert_eq!(streamed.index(key), Some(index), "{}", key);
+	}
+	let keys = EXPECTED.iter().map(|&(key, _)| key).collect::<Vec<&str>>();
+	assert_eq!(mphf.to_bytes_with_keys(&keys), BYTES);
+	assert_eq!(view.key_digest(), Some(0x82E8F425C4C0FD80));
+}
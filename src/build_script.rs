@@ -0,0 +1,27 @@
+/*!
+Helpers for generating tables in build scripts.
+*/
+
+use std::path::Path;
+
+/// Tells Cargo to rerun the build script when the source of the keys changes.
+///
+/// Prints `cargo:rerun-if-changed={source_path}` to stdout, which is where Cargo reads the instructions of a build script from.
+/// Call it once for every file the keys and values are read from, outside of a build script the line is just printed.
+///
+/// ```no_run
+/// let path = std::path::Path::new("keywords.toml");
+/// mphf::build_script::emit_rerun_hints(path);
+/// ```
+pub fn emit_rerun_hints(source_path: &Path) {
+	println!("{}", rerun_hint(source_path));
+}
+
+pub(crate) fn rerun_hint(source_path: &Path) -> String {
+	format!("cargo:rerun-if-changed={}", source_path.display())
+}
+
+#[test]
+fn test_rerun_hint() {
+	assert_eq!(rerun_hint(Path::new("data/keywords.toml")), "cargo:rerun-if-changed=data/keywords.toml");
+}
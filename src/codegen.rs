@@ -2,28 +2,251 @@
 Code generation for mphf.
 */
 
+use std::{env, fmt, fs, io, process};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use super::{BuildError, Seeds};
+
+/// Type of the keys of the generated table.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum KeyKind {
+	/// The keys are `&str` from [`Options::keys`].
+	Str,
+	/// The keys are `&[u8]` from [`Options::byte_keys`], looked up with [`index_bytes`](crate::index_bytes).
+	Bytes,
+}
+
+/// Kind of the generated `SEEDS`, `KEYS` and `VALUES` items.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ItemKind {
+	/// The arrays are `static` items stored once in the binary.
+	Static,
+	/// The arrays are `const` items usable in const contexts.
+	///
+	/// Every use of a const is a fresh copy of the array which gives the optimizer the most freedom,
+	/// but large tables may end up duplicated in the binary and are better off as statics.
+	/// The value getters returning `&'static` references require the values to be promotable to static, eg. without `Drop`.
+	Const,
+}
+
+/// Language standard of the generated C++ code.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CppStandard {
+	Cpp17,
+	/// Uses `std::rotl` from `<bit>`.
+	Cpp20,
+}
+
+/// Rust edition of the crate including the generated code.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RustEdition {
+	/// Also suits crates of the 2018 edition.
+	Edition2021,
+	/// Writes the captures of the returned `impl Iterator` with `+ use<>`, which requires Rust 1.82.
+	///
+	/// The accessors take no references so nothing is captured either way, the bound makes that explicit under the 2024 capture rules.
+	Edition2024,
+}
+
+/// Indentation of the generated Rust code.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Indent {
+	/// A tab per level.
+	Tabs,
+	/// The number of spaces per level, eg. `4` to match rustfmt.
+	Spaces(u8),
+}
+impl Indent {
+	fn level(self, depth: usize) -> String {
+		match self {
+			Indent::Tabs => "\t".repeat(depth),
+			Indent::Spaces(width) => " ".repeat(width as usize * depth),
+		}
+	}
+}
+
+/// Layout of the generated Rust code, see [`Options::format`].
+///
+/// The default indents with tabs and writes every array on the line of its item.
+/// With elements on their own lines a changed key only touches the lines of its entries, the arrays stay in mphf order.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CodegenFormat {
+	/// Number of array elements per line, `0` writes the whole array on one line.
+	pub elems_per_line: usize,
+	/// Indentation of every level of the generated module.
+	pub indent: Indent,
+}
+impl Default for CodegenFormat {
+	fn default() -> CodegenFormat {
+		CodegenFormat { elems_per_line: 0, indent: Indent::Tabs }
+	}
+}
+
+/// Normalization of string keys before they are hashed, see [`Options::key_normalizer`].
+#[derive(Copy, Clone, Debug)]
+pub enum KeyNormalizer<'a> {
+	/// Lowercases the ASCII letters with `str::to_ascii_lowercase`.
+	AsciiLowercase,
+	/// Lowercases with `str::to_lowercase`.
+	Lowercase,
+	/// Strips the leading and trailing whitespace with `str::trim`, the only normalizer which does not allocate.
+	Trim,
+	/// Calls the function when generating and the function at the path in the generated code.
+	///
+	/// The function in the generated code takes `&str` and returns `String` or `&str`, it must normalize the same as `normalize`.
+	/// Paths starting with `self` or `super` are relative to the generated module.
+	Custom { normalize: fn(&str) -> String, path: &'a str },
+}
+impl<'a> KeyNormalizer<'a> {
+	/// Normalizes the key the same as the generated code.
+	pub fn normalize(&self, key: &str) -> String {
+		match *self {
+			KeyNormalizer::AsciiLowercase => key.to_ascii_lowercase(),
+			KeyNormalizer::Lowercase => key.to_lowercase(),
+			KeyNormalizer::Trim => String::from(key.trim()),
+			KeyNormalizer::Custom { normalize, .. } => normalize(key),
+		}
+	}
+}
+
+/// Primitive value of a generated table written as an unquoted literal.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Value<'a> {
+	Str(&'a str),
+	U32(u32),
+	I64(i64),
+	F64(f64),
+}
+
 pub struct Options<'a> {
 	pub name: &'a str,
+	/// Visibility of the generated module, one of `""`, `"pub"`, `"pub(crate)"`, `"pub(self)"` or `"pub(super)"`.
+	///
+	/// The items in the module are visible to the same code as the module itself.
+	pub visibility: &'a str,
+	/// Path to the mphf crate used by the generated code, defaults to `::mphf`.
+	///
+	/// Set it when the dependency is renamed or re-exported, eg. `mphf_rt` or `crate::deps::mphf`.
+	/// Paths starting with `self` or `super` are relative to the generated module.
+	pub crate_path: &'a str,
+	/// Inlines a private copy of the hash and lookup functions so the generated code does not depend on mphf, `crate_path` is ignored then.
+	pub standalone: bool,
+	/// Emits the seeds as `u8` or `u16` when they fit and looks them up with code generated next to them.
+	///
+	/// The max value of the narrow type marks empty buckets, the seeds stay `u32` when a seed does not fit below it.
+	pub narrow_seeds: bool,
+	pub item_kind: ItemKind,
+	pub key_kind: KeyKind,
 	pub keys: &'a [&'a str],
+	/// The keys when `key_kind` is [`KeyKind::Bytes`], `keys` is ignored then.
+	pub byte_keys: &'a [&'a [u8]],
+	/// Normalizes the keys before hashing them when generating and the lookup keys in the generated code, requires [`KeyKind::Str`].
+	///
+	/// Eg. [`KeyNormalizer::AsciiLowercase`] makes the lookups case insensitive, `KEYS` holds the normalized keys.
+	/// Keys which normalize the same are an error. The normalizers other than `Trim` allocate in the generated code, `#![no_std]` crates using them need `extern crate alloc`.
+	/// Only the Rust backend supports normalizers.
+	pub key_normalizer: Option<KeyNormalizer<'a>>,
 	pub values: &'a [&'a str],
+	/// The type of the values, defaults to `&str` when empty.
+	///
+	/// The values of any other type are raw Rust expressions emitted verbatim, `&str` values are string literals.
+	/// The generated module imports its parent module so the type and the values can refer to items defined next to it.
+	pub value_type: &'a str,
+	/// Primitive values replacing `values` and `value_type` when set.
+	///
+	/// The type of the first value is the type of the `VALUES` array, all values must have the same type.
+	pub typed_values: Option<&'a [Value<'a>]>,
 	pub seeds_len: usize,
 	pub max_seed: u32,
+	/// Emits `KEYS` with the `key` and `keys` accessors, and `iter` together with `has_values`.
 	pub has_keys: bool,
+	/// Emits `VALUES` with the `value` and `values` accessors, `values` may be empty without it.
 	pub has_values: bool,
+	/// Emits `index` which needs only the seeds.
 	pub has_index: bool,
+	/// Emits `unsafe fn value_unchecked` returning the value without the `Option`, `VALUES` is emitted even without `has_values`.
+	///
+	/// The key must be in the table, it is neither compared nor checked for an empty bucket.
+	pub has_unchecked: bool,
+	/// Emits `contains_key` which compares the key at its index, `KEYS` is emitted even without `has_keys`.
+	pub has_contains: bool,
+	/// Emits `entry` which returns the stored key and the value after comparing the key, `KEYS` and `VALUES` are emitted even without `has_keys` and `has_values`.
+	pub has_entry: bool,
+	/// The `key`, `value` and `index` functions compare the key stored at the index and return `None` for keys outside the table.
+	///
+	/// Without it any key maps to some entry of the table, `KEYS` is emitted even without `has_keys`.
+	pub verify_keys: bool,
+	/// Emits an enum with this name and a variant per key, requires [`KeyKind::Str`].
+	///
+	/// The variants are the keys split at every character other than an ASCII letter or digit with each part capitalized,
+	/// eg. `"good-bye"` becomes `GoodBye` and `"GET"` stays `GET`.
+	/// The discriminant of a variant is the index of its key, the enum implements `FromStr`, `as_str` and `From<Enum> for usize`.
+	pub emit_enum: Option<&'a str>,
+	/// Doc comment of the generated module, the lines become `///` comments.
+	///
+	/// The items in the module are always documented, the lookups note what they return for keys outside the table.
+	pub doc: Option<&'a str>,
+	/// Allows the `missing_docs` lint on the generated module, eg. for a public table without [`doc`](Options::doc).
+	pub allow_missing_docs: bool,
+	/// Hides the generated module from the documentation.
+	pub doc_hidden: bool,
+	/// Rust code appended verbatim after the generated module, eg. trait impls or re-exports of the items.
+	///
+	/// The other backends ignore it.
+	pub footer: Option<&'a str>,
+	/// Custom lines appended to the comment at the top of the generated code, eg. the command regenerating it.
+	///
+	/// The comment records the mphf version, the build parameters, the options differing from the defaults and a digest of the keys.
+	/// The digest is the [`key_digest`](crate::binary::key_digest) of the keys as given, it does not depend on their order and detects stale generated files.
+	pub header: Option<&'a str>,
+	/// Input files the keys and values were read from, [`buildrs`] tells Cargo to rerun the build script when they change.
+	pub watch_files: &'a [&'a Path],
+	/// Language standard of [`cpp`](Options::cpp), defaults to C++17.
+	pub cpp_standard: CppStandard,
+	/// Edition of the generated Rust code, defaults to the 2021 edition.
+	///
+	/// The code of every edition compiles in the later editions, see [`RustEdition`].
+	pub rust_edition: RustEdition,
+	/// Layout of the generated Rust code, the other backends ignore it.
+	pub format: CodegenFormat,
+	/// The value getters return copies of the values which requires the value type to be `Copy`, or `&'static` references otherwise.
 	pub copy_values: bool,
 }
 impl<'a> Default for Options<'a> {
 	fn default() -> Options<'a> {
 		Options {
 			name: "",
+			visibility: "pub",
+			crate_path: "::mphf",
+			standalone: false,
+			narrow_seeds: false,
+			item_kind: ItemKind::Static,
+			key_kind: KeyKind::Str,
 			keys: &[],
+			byte_keys: &[],
+			key_normalizer: None,
 			values: &[],
+			value_type: "",
+			typed_values: None,
 			seeds_len: 0,
 			max_seed: 0,
 			has_keys: true,
 			has_values: true,
 			has_index: true,
+			has_unchecked: false,
+			has_contains: false,
+			has_entry: false,
+			verify_keys: false,
+			emit_enum: None,
+			doc: None,
+			allow_missing_docs: false,
+			doc_hidden: false,
+			header: None,
+			footer: None,
+			watch_files: &[],
+			cpp_standard: CppStandard::Cpp17,
+			rust_edition: RustEdition::Edition2021,
+			format: CodegenFormat { elems_per_line: 0, indent: Indent::Tabs },
 			copy_values: true,
 		}
 	}
@@ -31,9 +254,1060 @@ impl<'a> Default for Options<'a> {
 
 impl<'a> Options<'a> {
 	/// Generates Rust source code.
-	pub fn rust(&self) -> String {
+	pub fn rust(&self) -> Result<String, CodegenError> {
 		self::rust::generate(self)
 	}
+	/// Generates Rust source code and writes it to the file, intended for build scripts writing to `OUT_DIR`.
+	///
+	/// The file is left untouched if it already has the same content, keeping its modification time so dependents are not rebuilt.
+	/// Otherwise the code is written to a temporary file next to it which is renamed over the file,
+	/// so an interrupted build never leaves a truncated file behind.
+	pub fn write_rust<P: AsRef<Path>>(&self, path: P) -> Result<(), CodegenError> {
+		let code = self.rust()?;
+		write_if_changed(path.as_ref(), code.as_bytes())
+	}
+	/// Generates Rust source code, panics with the error message on failure.
+	pub fn rust_or_panic(&self) -> String {
+		self.rust().unwrap_or_else(|err| panic!("{}", err))
+	}
+	/// Generates C99 source code.
+	///
+	/// The source declares and defines `int mphf_<name>_index(const char *key, size_t len)` returning `-1` for keys outside the table,
+	/// and `const char *mphf_<name>_value(const char *key, size_t len)` returning `NULL` with `has_values`.
+	/// The arrays and a copy of the hash function are `static`, include the source in a single translation unit.
+	///
+	/// Only string values are supported, `verify_keys` compares the key.
+	/// The options specific to Rust such as the visibility are ignored.
+	pub fn c(&self) -> Result<String, CodegenError> {
+		self::c::generate(self)
+	}
+	/// Generates a header-only C++ table.
+	///
+	/// The header defines a namespace with the `constexpr` arrays `SEEDS`, `KEYS` and `VALUES` in mphf order, the hash function,
+	/// `std::optional<std::size_t> index(std::string_view key)` and `value(key)` with `has_values`, all usable at compile time.
+	/// The keys are `std::string_view`, the values are `std::string_view` or the type of the typed values.
+	///
+	/// The options specific to Rust such as the visibility are ignored.
+	pub fn cpp(&self) -> Result<String, CodegenError> {
+		self::cpp::generate(self)
+	}
+	/// Generates a Go source file in the given package.
+	///
+	/// The file defines `func Index(key string) (int, bool)` and `func Value(key string) (string, bool)` with `has_values`,
+	/// with the same indices as the Rust lookups and `verify_keys` comparing the key.
+	/// The unexported `seeds`, `keys`, `values`, `hash` and `mix` are defined too, generate one table per package.
+	///
+	/// Only string values are supported, the options specific to Rust such as the visibility are ignored.
+	pub fn go(&self, package: &str) -> Result<String, CodegenError> {
+		self::go::generate(self, package)
+	}
+	/// Generates a TypeScript module.
+	///
+	/// The module exports `LEN`, `KEYS`, `hash`, `function index(key: string): number | undefined` and with `has_values` `VALUES` and `function value(key: string): string | undefined`.
+	/// The keys are hashed as UTF-8 with `TextEncoder` so the seeds and indices are the same as in Rust.
+	///
+	/// Only string keys and values are supported, the options specific to Rust such as the visibility are ignored.
+	pub fn typescript(&self) -> Result<String, CodegenError> {
+		self::typescript::generate(self)
+	}
+	/// Generates a Python module.
+	///
+	/// The module defines `SEEDS`, `KEYS`, `LEN`, `def index(key: str) -> Optional[int]` and with `has_values` `VALUES` and `def value(key: str) -> Optional[str]`.
+	/// String keys are hashed as UTF-8, byte string keys are looked up as `bytes`.
+	/// The module asserts a few hashes against the Rust ones when it is imported.
+	///
+	/// Only string values are supported, the options specific to Rust such as the visibility are ignored.
+	pub fn python(&self) -> Result<String, CodegenError> {
+		self::python::generate(self)
+	}
+	/// Generates a JSON artifact with the built table for other tools.
+	///
+	/// The schema is stable, fields are only added within a version:
+	///
+	/// ```text
+	/// {
+	///   "version": 1,
+	///   "name": "colors",
+	///   "hash": "murmur3_32",
+	///   "max_seed": 100000,
+	///   "values_len": 3,
+	///   "seeds": [1, 12],
+	///   "keys": ["green", "red", "blue"],
+	///   "values": ["#0f0", "#f00", "#00f"]
+	/// }
+	/// ```
+	///
+	/// * `version` is the version of the schema.
+	/// * `hash` is the name of the hash, the index of a key is `hash(key, seeds[hash(key, 0) % seeds.len()]) % values_len` over its UTF-8 bytes.
+	///   A seed of `4294967295` marks a bucket without keys.
+	/// * `keys` and `values` are in mphf order, the entry of a key is at its index.
+	///   The `values` are left out without `has_values`, typed values are written as JSON numbers and strings.
+	///
+	/// With values the artifact can be loaded by `MphfMap::from_json_str` which ignores `name` and `values_len`.
+	/// Byte string keys and value types other than strings are not supported.
+	pub fn json(&self) -> Result<String, CodegenError> {
+		self::json::generate(self)
+	}
+
+	fn keys_len(&self) -> usize {
+		match self.key_kind {
+			KeyKind::Str => self.keys.len(),
+			KeyKind::Bytes => self.byte_keys.len(),
+		}
+	}
+
+	// The comment lines after the first line of the generated code, starting with the comment syntax of the backend
+	fn provenance(&self, comment: &str) -> String {
+		let digest = match self.key_kind {
+			KeyKind::Str => crate::binary::key_digest(self.keys),
+			KeyKind::Bytes => crate::binary::key_digest_bytes(self.byte_keys),
+		};
+		let mut lines = vec![format!("{} keys with digest {:016x}, seeds_len {}, max_seed {}", self.keys_len(), digest, self.seeds_len, self.max_seed)];
+		let defaults = Options::default();
+		let flags = [
+			("standalone", self.standalone, defaults.standalone),
+			("narrow_seeds", self.narrow_seeds, defaults.narrow_seeds),
+			("has_keys", self.has_keys, defaults.has_keys),
+			("has_values", self.has_values, defaults.has_values),
+			("has_index", self.has_index, defaults.has_index),
+			("has_unchecked", self.has_unchecked, defaults.has_unchecked),
+			("has_contains", self.has_contains, defaults.has_contains),
+			("has_entry", self.has_entry, defaults.has_entry),
+			("verify_keys", self.verify_keys, defaults.verify_keys),
+			("copy_values", self.copy_values, defaults.copy_values),
+		];
+		let flags = flags.iter().filter(|flag| flag.1 != flag.2).map(|flag| format!("{} = {}", flag.0, flag.1)).collect::<Vec<String>>();
+		if !flags.is_empty() {
+			lines.push(format!("options: {}", flags.join(", ")));
+		}
+		lines.extend(self.header.iter().flat_map(|header| header.lines()).map(String::from));
+		lines.iter().map(|line| if line.is_empty() { format!("{}\n", comment) } else { format!("{} {}\n", comment, line) }).collect()
+	}
+
+	// Rejects the options the backend does not support and builds the seeds over the keys
+	// Returns the keys as bytes, the seeds and the key at every index
+	fn build_backend(&self, support: Support) -> Result<BackendTable<'a>, CodegenError> {
+		if !support.byte_keys && self.key_kind == KeyKind::Bytes {
+			return Err(CodegenError::Unsupported("byte string keys"));
+		}
+		if !support.typed_values && self.typed_values.is_some() {
+			return Err(CodegenError::Unsupported("typed values"));
+		}
+		if self.typed_values.is_none() && !(self.value_type.is_empty() || self.value_type == "&str") {
+			return Err(CodegenError::Unsupported(if support.typed_values { "value types other than strings and typed values" } else { "value types other than strings" }));
+		}
+		if !support.enums && self.emit_enum.is_some() {
+			return Err(CodegenError::Unsupported("enums"));
+		}
+		if self.key_normalizer.is_some() {
+			return Err(CodegenError::Unsupported("key normalizers"));
+		}
+		let keys = match self.key_kind {
+			KeyKind::Str => self.keys.iter().map(|key| key.as_bytes()).collect::<Vec<&[u8]>>(),
+			KeyKind::Bytes => self.byte_keys.to_vec(),
+		};
+		let values_len = self.typed_values.map_or(self.values.len(), |values| values.len());
+		if self.has_values && keys.len() != values_len {
+			return Err(CodegenError::LengthMismatch { keys: keys.len(), values: values_len });
+		}
+		// A string hashes the same as its bytes
+		let seeds = crate::build_bytes(&keys, self.seeds_len, self.max_seed).map_err(|err| self.build_error(err))?;
+		let mut perm = vec![0; keys.len()];
+		for (i, &key) in keys.iter().enumerate() {
+			// The seeds were built over the keys
+			perm[crate::index_bytes(key, &seeds, keys.len()).unwrap()] = i;
+		}
+		Ok((keys, seeds, perm))
+	}
+
+	fn build_error(&self, error: BuildError) -> CodegenError {
+		CodegenError::Build {
+			name: String::from(self.name),
+			keys_len: self.keys_len(),
+			seeds_len: self.seeds_len,
+			max_seed: self.max_seed,
+			error,
+		}
+	}
+}
+
+// The options a backend other than Rust supports besides the strings
+#[derive(Copy, Clone, Default)]
+struct Support {
+	byte_keys: bool,
+	typed_values: bool,
+	// Enums are ignored instead of rejected
+	enums: bool,
+}
+
+// The keys as bytes, the seeds and the key at every index
+type BackendTable<'a> = (Vec<&'a [u8]>, Seeds, Vec<usize>);
+
+fn write_if_changed(path: &Path, content: &[u8]) -> Result<(), CodegenError> {
+	let error = |err: io::Error| CodegenError::Io { path: path.to_path_buf(), kind: err.kind() };
+	if matches!(fs::read(path), Ok(old) if old == content) {
+		return Ok(());
+	}
+	let file_name = path.file_name().ok_or_else(|| error(io::ErrorKind::InvalidInput.into()))?;
+	let tmp = path.with_file_name(format!(".{}.{}.tmp", file_name.to_string_lossy(), process::id()));
+	let result = fs::File::create(&tmp)
+		.and_then(|mut file| file.write_all(content).and_then(|_| file.sync_all()))
+		.and_then(|_| fs::rename(&tmp, path));
+	if let Err(err) = result {
+		let _ = fs::remove_file(&tmp);
+		return Err(error(err));
+	}
+	Ok(())
+}
+
+/// Error generating code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CodegenError {
+	/// The keys and values have a different number of entries.
+	LengthMismatch { keys: usize, values: usize },
+	/// The typed value at `index` has a different type than the first value.
+	MixedValues { index: usize },
+	/// The visibility is not one of the allowed forms.
+	InvalidVisibility(String),
+	/// The crate path is not a valid Rust path.
+	InvalidCratePath(String),
+	/// The name is not a valid identifier.
+	InvalidName(String),
+	/// The options use a feature the backend does not support.
+	Unsupported(&'static str),
+	/// The enum name is not a valid Rust identifier.
+	InvalidEnumName(String),
+	/// The enum is requested for byte string keys.
+	EnumByteKeys,
+	/// The key does not become a valid variant name.
+	InvalidVariant { key: String },
+	/// The normalizer is requested for byte string keys.
+	NormalizerByteKeys,
+	/// The path of the custom normalizer is not a valid Rust path.
+	InvalidNormalizerPath(String),
+	/// Two keys normalize to the same key.
+	NormalizedDuplicate { normalized: String, keys: (String, String) },
+	/// Two keys become the same variant name.
+	DuplicateVariant { variant: String, keys: (String, String) },
+	/// Two tables of a batch have the same name.
+	DuplicateName(String),
+	/// Writing the generated code to the file failed.
+	Io { path: PathBuf, kind: io::ErrorKind },
+	/// The `OUT_DIR` environment variable is not set, the code is not generated by a build script.
+	MissingOutDir,
+	/// Writing the Cargo directives failed.
+	Directives(io::ErrorKind),
+	/// Building the mphf failed with the given options.
+	Build {
+		name: String,
+		keys_len: usize,
+		seeds_len: usize,
+		max_seed: u32,
+		error: BuildError,
+	},
+}
+
+impl fmt::Display for CodegenError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			CodegenError::LengthMismatch { keys, values } => write!(f, "{} keys but {} values", keys, values),
+			CodegenError::MixedValues { index } => write!(f, "value {} has a different type than the first value", index),
+			CodegenError::InvalidCratePath(path) => write!(f, "invalid crate path {:?}", path),
+			CodegenError::InvalidName(name) => write!(f, "invalid name {:?}", name),
+			CodegenError::Unsupported(feature) => write!(f, "{} are not supported by this backend", feature),
+			CodegenError::InvalidEnumName(name) => write!(f, "invalid enum name {:?}", name),
+			CodegenError::EnumByteKeys => f.write_str("an enum cannot be generated for byte string keys"),
+			CodegenError::InvalidVariant { key } => write!(f, "key {:?} does not become a valid variant name", key),
+			CodegenError::NormalizerByteKeys => f.write_str("a key normalizer cannot be used with byte string keys"),
+			CodegenError::InvalidNormalizerPath(path) => write!(f, "invalid normalizer path {:?}", path),
+			CodegenError::NormalizedDuplicate { normalized, keys: (first, second) } => write!(f, "keys {:?} and {:?} both normalize to {:?}", first, second, normalized),
+			CodegenError::DuplicateVariant { variant, keys: (first, second) } => write!(f, "keys {:?} and {:?} both become variant {}", first, second, variant),
+			CodegenError::DuplicateName(name) => write!(f, "another table is also named {:?}", name),
+			CodegenError::Io { path, kind } => write!(f, "error writing {}: {}", path.display(), kind),
+			CodegenError::MissingOutDir => f.write_str("OUT_DIR is not set, generate the code from a build script"),
+			CodegenError::Directives(kind) => write!(f, "error writing cargo directives: {}", kind),
+			CodegenError::InvalidVisibility(visibility) => write!(f, "invalid visibility {:?}, expected one of \"\", \"pub\", \"pub(crate)\", \"pub(self)\" or \"pub(super)\"", visibility),
+			CodegenError::Build { name, keys_len, seeds_len, max_seed, error } => {
+				write!(f, "failed to build mphf {:?} over {} keys with seeds_len {} and max_seed {}: {}", name, keys_len, seeds_len, max_seed, error)
+			},
+		}
+	}
+}
+
+impl std::error::Error for CodegenError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			CodegenError::Build { error, .. } => Some(error),
+			_ => None,
+		}
+	}
 }
 
+/// Error reading options from environment variables.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EnvError {
+	/// The variable is not set.
+	Missing(String),
+	/// The variable is not valid unicode.
+	NotUnicode(String),
+	/// The variable does not hold a valid number.
+	InvalidNumber(String),
+	/// The keys and values variables have a different number of entries.
+	LengthMismatch { keys: usize, values: usize },
+}
+
+impl fmt::Display for EnvError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			EnvError::Missing(var) => write!(f, "environment variable {} is not set", var),
+			EnvError::NotUnicode(var) => write!(f, "environment variable {} is not valid unicode", var),
+			EnvError::InvalidNumber(var) => write!(f, "environment variable {} is not a valid number", var),
+			EnvError::LengthMismatch { keys, values } => write!(f, "{} keys but {} values", keys, values),
+		}
+	}
+}
+
+impl std::error::Error for EnvError {}
+
+fn env_var(prefix: &str, name: &str) -> Result<Option<String>, EnvError> {
+	let var = format!("{}_{}", prefix, name);
+	match env::var(&var) {
+		Ok(value) => Ok(Some(value)),
+		Err(env::VarError::NotPresent) => Ok(None),
+		Err(env::VarError::NotUnicode(_)) => Err(EnvError::NotUnicode(var)),
+	}
+}
+fn env_required(prefix: &str, name: &str) -> Result<String, EnvError> {
+	env_var(prefix, name)?.ok_or_else(|| EnvError::Missing(format!("{}_{}", prefix, name)))
+}
+fn env_number<T: std::str::FromStr>(prefix: &str, name: &str) -> Result<T, EnvError> {
+	env_required(prefix, name)?.trim().parse().map_err(|_| EnvError::InvalidNumber(format!("{}_{}", prefix, name)))
+}
+fn env_list(list: String) -> &'static [&'static str] {
+	let list: &'static str = Box::leak(list.into_boxed_str());
+	let items = if list.is_empty() { Vec::new() } else { list.split(',').collect() };
+	Box::leak(items.into_boxed_slice())
+}
+
+impl Options<'static> {
+	/// Reads the options from environment variables, intended for build scripts.
+	///
+	/// * `{PREFIX}_KEYS` and `{PREFIX}_VALUES` are comma separated lists of equal length.
+	/// * `{PREFIX}_SEEDS_LEN` and `{PREFIX}_MAX_SEED` are the build parameters.
+	/// * `{PREFIX}_NAME` is the name of the generated module, defaults to the lowercase prefix.
+	///
+	/// The other options keep their defaults.
+	/// The strings are leaked to give them a static lifetime which is fine for a short lived build script.
+	///
+	/// ```
+	/// # std::env::set_var("COLORS_KEYS", "red,green,blue");
+	/// # std::env::set_var("COLORS_VALUES", "#f00,#0f0,#00f");
+	/// # std::env::set_var("COLORS_SEEDS_LEN", "1");
+	/// # std::env::set_var("COLORS_MAX_SEED", "100000");
+	/// let options = mphf::codegen::Options::from_env("COLORS").unwrap();
+	/// assert_eq!(options.name, "colors");
+	/// assert_eq!(options.keys, &["red", "green", "blue"]);
+	/// ```
+	pub fn from_env(prefix: &str) -> Result<Options<'static>, EnvError> {
+		let name = env_var(prefix, "NAME")?.unwrap_or_else(|| prefix.to_lowercase());
+		let keys = env_list(env_required(prefix, "KEYS")?);
+		let values = env_list(env_required(prefix, "VALUES")?);
+		if keys.len() != values.len() {
+			return Err(EnvError::LengthMismatch { keys: keys.len(), values: values.len() });
+		}
+		let seeds_len = env_number(prefix, "SEEDS_LEN")?;
+		let max_seed = env_number(prefix, "MAX_SEED")?;
+		Ok(Options {
+			name: Box::leak(name.into_boxed_str()),
+			keys,
+			values,
+			seeds_len,
+			max_seed,
+			..Options::default()
+		})
+	}
+}
+
+#[test]
+fn test_write_rust() {
+	let dir = env::temp_dir().join(format!("mphf-test-write-{}", process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	let path = dir.join("table.rs");
+	let options = Options {
+		name: "table",
+		keys: &["a", "b"],
+		values: &["1", "2"],
+		seeds_len: 1,
+		max_seed: 1000,
+		..Options::default()
+	};
+	options.write_rust(&path).unwrap();
+	assert_eq!(fs::read_to_string(&path).unwrap(), options.rust().unwrap());
+
+	// The same content keeps the modification time
+	let past = std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000_000);
+	fs::File::options().write(true).open(&path).unwrap().set_modified(past).unwrap();
+	options.write_rust(&path).unwrap();
+	assert_eq!(fs::metadata(&path).unwrap().modified().unwrap(), past);
+
+	let changed = Options { values: &["1", "3"], ..options };
+	changed.write_rust(&path).unwrap();
+	assert_eq!(fs::read_to_string(&path).unwrap(), changed.rust().unwrap());
+	assert_ne!(fs::metadata(&path).unwrap().modified().unwrap(), past);
+	assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+	fs::remove_dir_all(&dir).unwrap();
+
+	let error = options.write_rust(dir.join("table.rs")).unwrap_err();
+	assert_eq!(error, CodegenError::Io { path: dir.join("table.rs"), kind: io::ErrorKind::NotFound });
+	assert!(error.to_string().contains(&*dir.join("table.rs").to_string_lossy()));
+	assert!(matches!(Options { keys: &["a", "a"], ..options }.write_rust(dir.join("table.rs")), Err(CodegenError::Build { .. })));
+}
+
+#[test]
+fn test_key_normalizer() {
+	fn collapse(key: &str) -> String {
+		key.split_whitespace().collect::<Vec<&str>>().join(" ")
+	}
+	let options = Options {
+		name: "table",
+		keys: &[" a ", "b"],
+		values: &["1", "2"],
+		key_normalizer: Some(KeyNormalizer::Trim),
+		seeds_len: 1,
+		max_seed: 1000,
+		..Options::default()
+	};
+	let code = options.rust().unwrap();
+	assert!(code.contains("\"a\"") && !code.contains("\" a \""));
+	assert!(code.contains("fn value(key: &str) -> Option<&'static str> { let key = key.trim(); "));
+
+	let custom = Options { keys: &["x  y", "z"], key_normalizer: Some(KeyNormalizer::Custom { normalize: collapse, path: "super::collapse" }), ..options };
+	let code = custom.rust().unwrap();
+	assert!(code.contains("\"x y\"") && code.contains("let normalized = super::collapse(key); let key: &str = &normalized; "));
+
+	let bad_path = KeyNormalizer::Custom { normalize: collapse, path: "collapse()" };
+	assert_eq!(Options { key_normalizer: Some(bad_path), ..options }.rust(), Err(CodegenError::InvalidNormalizerPath(String::from("collapse()"))));
+	let bytes = Options { key_kind: KeyKind::Bytes, byte_keys: &[b"a", b"b"], ..options };
+	assert_eq!(bytes.rust(), Err(CodegenError::NormalizerByteKeys));
+	let duplicate = Options { keys: &["Key", "KEY"], key_normalizer: Some(KeyNormalizer::AsciiLowercase), ..options };
+	assert_eq!(duplicate.rust(), Err(CodegenError::NormalizedDuplicate { normalized: String::from("key"), keys: (String::from("Key"), String::from("KEY")) }));
+	assert_eq!(KeyNormalizer::Lowercase.normalize("ÄB"), "äb");
+
+	assert_eq!(options.c(), Err(CodegenError::Unsupported("key normalizers")));
+	assert_eq!(options.typescript(), Err(CodegenError::Unsupported("key normalizers")));
+	assert_eq!(options.json(), Err(CodegenError::Unsupported("key normalizers")));
+}
+
+#[test]
+fn test_from_env() {
+	env::set_var("MPHF_TEST_ENV_KEYS", "a,b,c");
+	env::set_var("MPHF_TEST_ENV_VALUES", "1,2");
+	env::set_var("MPHF_TEST_ENV_SEEDS_LEN", "x");
+	assert_eq!(Options::from_env("MPHF_TEST_ENV").err(), Some(EnvError::LengthMismatch { keys: 3, values: 2 }));
+	env::set_var("MPHF_TEST_ENV_VALUES", "1,2,3");
+	assert_eq!(Options::from_env("MPHF_TEST_ENV").err(), Some(EnvError::InvalidNumber("MPHF_TEST_ENV_SEEDS_LEN".into())));
+	env::set_var("MPHF_TEST_ENV_SEEDS_LEN", "2");
+	assert_eq!(Options::from_env("MPHF_TEST_ENV").err(), Some(EnvError::Missing("MPHF_TEST_ENV_MAX_SEED".into())));
+	env::set_var("MPHF_TEST_ENV_MAX_SEED", "1000");
+	env::set_var("MPHF_TEST_ENV_NAME", "table");
+
+	let options = Options::from_env("MPHF_TEST_ENV").unwrap();
+	assert_eq!(options.name, "table");
+	assert_eq!(options.keys, &["a", "b", "c"]);
+	assert_eq!(options.values, &["1", "2", "3"]);
+	assert_eq!(options.seeds_len, 2);
+	assert_eq!(options.max_seed, 1000);
+	assert!(options.rust().unwrap().contains("\npub mod table {\n"));
+}
+
+#[test]
+fn test_rust_errors() {
+	let options = Options {
+		name: "table",
+		keys: &["a", "a"],
+		values: &["1", "2"],
+		seeds_len: 1,
+		max_seed: 1000,
+		..Options::default()
+	};
+	let err = options.rust().unwrap_err();
+	assert_eq!(err, CodegenError::Build {
+		name: "table".into(),
+		keys_len: 2,
+		seeds_len: 1,
+		max_seed: 1000,
+		error: BuildError::SeedNotFound { bucket: 0, bucket_len: 2, max_seed: 1000 },
+	});
+	assert!(err.to_string().contains("try a larger seeds_len"));
+
+	let options = Options { seeds_len: 0, ..options };
+	assert!(matches!(options.rust(), Err(CodegenError::Build { error: BuildError::ZeroSeedsLen, .. })));
+	let options = Options { values: &["1"], ..options };
+	assert_eq!(options.rust(), Err(CodegenError::LengthMismatch { keys: 2, values: 1 }));
+}
+
+#[cfg(test)]
+fn parse_literals<'a>(file: &'a syn::File, name: &str) -> Vec<&'a syn::Lit> {
+	let syn::Item::Mod(module) = &file.items[0] else { panic!("expected a module") };
+	for item in &module.content.as_ref().unwrap().1 {
+		if let syn::Item::Static(item) = item {
+			if item.ident == name {
+				let syn::Expr::Array(array) = &*item.expr else { panic!("expected an array") };
+				return array.elems.iter().map(|elem| match elem {
+					syn::Expr::Lit(lit) => &lit.lit,
+					_ => panic!("expected a literal"),
+				}).collect();
+			}
+		}
+	}
+	panic!("{} not found", name)
+}
+#[cfg(test)]
+fn parse_strings(file: &syn::File, name: &str) -> Vec<String> {
+	parse_literals(file, name).into_iter().map(|lit| match lit {
+		syn::Lit::Str(lit) => lit.value(),
+		_ => panic!("expected a string literal"),
+	}).collect()
+}
+
+#[test]
+fn test_rust_escape() {
+	let keys = ["he\"llo", "back\\slash", "tab\there", "emoji\u{1F642}", "new\nline", "nul\0"];
+	let values = ["\"", "\\", "\r\n", "\u{7f}", "}", "{x}"];
+	let options = Options {
+		name: "escape",
+		keys: &keys,
+		values: &values,
+		seeds_len: 2,
+		max_seed: 100000,
+		..Options::default()
+	};
+	let code = options.rust().unwrap();
+	let file = syn::parse_file(&code).unwrap();
+
+	let parsed_keys = parse_strings(&file, "KEYS");
+	let parsed_values = parse_strings(&file, "VALUES");
+	assert_eq!(parsed_keys.len(), keys.len());
+	for (key, value) in keys.iter().zip(&values) {
+		let index = parsed_keys.iter().position(|parsed| parsed == key).unwrap();
+		assert_eq!(&parsed_values[index], value);
+	}
+}
+
+#[test]
+fn test_rust_bytes() {
+	let keys: [&[u8]; 6] = [b"\x00", b"\xff", b"a\x00b", b"\xff\xfe\x80", b"quote\"'\\", b"tag"];
+	let values = ["nul", "ff", "a0b", "fffe80", "quote", "tag"];
+	let options = Options {
+		name: "tags",
+		key_kind: KeyKind::Bytes,
+		byte_keys: &keys,
+		values: &values,
+		seeds_len: 2,
+		max_seed: 100000,
+		..Options::default()
+	};
+	let code = options.rust().unwrap();
+	assert!(code.contains("pub static KEYS: [&[u8]; 6]"));
+	assert!(code.contains("pub fn value(key: &[u8])"));
+	assert!(code.contains("::mphf::get_bytes(key, &SEEDS, &VALUES)"));
+	let file = syn::parse_file(&code).unwrap();
+
+	let seeds = parse_literals(&file, "SEEDS").into_iter().map(|lit| match lit {
+		syn::Lit::Int(lit) => lit.base10_parse::<u32>().unwrap(),
+		_ => panic!("expected an integer literal"),
+	}).collect::<Vec<u32>>();
+	let parsed_keys = parse_literals(&file, "KEYS").into_iter().map(|lit| match lit {
+		syn::Lit::ByteStr(lit) => lit.value(),
+		_ => panic!("expected a byte string literal"),
+	}).collect::<Vec<Vec<u8>>>();
+	let parsed_values = parse_strings(&file, "VALUES");
+	// The generated lookups map every key to its own entry
+	for (&key, value) in keys.iter().zip(&values) {
+		let index = crate::index_bytes(key, &seeds, parsed_keys.len()).unwrap();
+		assert_eq!(parsed_keys[index], key);
+		assert_eq!(&parsed_values[index], value);
+	}
+
+	let options = Options { values: &values[..5], ..options };
+	assert_eq!(options.rust(), Err(CodegenError::LengthMismatch { keys: 6, values: 5 }));
+}
+
+#[test]
+fn test_rust_typed_values() {
+	// The literals as written in the source, sorted back in the input order
+	fn parse_values(options: &Options) -> Vec<String> {
+		let code = options.rust().unwrap();
+		let keys = parse_strings(&syn::parse_file(&code).unwrap(), "KEYS");
+		let line = code.lines().find(|line| line.contains("pub static VALUES")).unwrap();
+		let values = line[line.find("= [").unwrap() + 3..line.rfind("];").unwrap()].split(',').filter(|value| !value.is_empty()).collect::<Vec<&str>>();
+		options.keys.iter().map(|key| values[keys.iter().position(|parsed| parsed == key).unwrap()].to_string()).collect()
+	}
+
+	let options = Options {
+		name: "numbers",
+		keys: &["a", "b", "c"],
+		typed_values: Some(&[Value::U32(3), Value::U32(17), Value::U32(42)]),
+		seeds_len: 1,
+		max_seed: 100000,
+		..Options::default()
+	};
+	let code = options.rust().unwrap();
+	assert!(code.contains("pub static VALUES: [u32; 3] = ["));
+	assert!(code.contains("pub fn value(key: &str) -> Option<u32>"));
+	assert!(!code.lines().find(|line| line.contains("VALUES:")).unwrap().contains('"'));
+	assert_eq!(parse_values(&options), ["3", "17", "42"]);
+
+	let options = Options { typed_values: Some(&[Value::I64(-1), Value::I64(i64::MIN), Value::I64(i64::MAX)]), copy_values: false, ..options };
+	assert!(options.rust().unwrap().contains("pub fn value(key: &str) -> Option<&'static i64>"));
+	assert_eq!(parse_values(&options), ["-1", "-9223372036854775808", "9223372036854775807"]);
+
+	// Floats always have a decimal point or exponent so they are not parsed as integers
+	let options = Options { typed_values: Some(&[Value::F64(3.0), Value::F64(-0.5), Value::F64(1e100)]), ..options };
+	assert!(options.rust().unwrap().contains("pub static VALUES: [f64; 3] = ["));
+	assert_eq!(parse_values(&options), ["3.0", "-0.5", "1e100"]);
+	let options = Options { typed_values: Some(&[Value::F64(f64::NAN), Value::F64(f64::INFINITY), Value::F64(f64::NEG_INFINITY)]), ..options };
+	let code = options.rust().unwrap();
+	assert!(code.contains("f64::NAN") && code.contains("f64::INFINITY") && code.contains("f64::NEG_INFINITY"));
+
+	let options = Options { typed_values: Some(&[Value::Str("x\"y"), Value::Str("b"), Value::Str("c")]), ..options };
+	assert!(options.rust().unwrap().contains("pub static VALUES: [&str; 3] = ["));
+	assert_eq!(parse_values(&options), ["\"x\\\"y\"", "\"b\"", "\"c\""]);
+
+	let options = Options { typed_values: Some(&[Value::U32(1), Value::U32(2), Value::Str("3")]), ..options };
+	assert_eq!(options.rust(), Err(CodegenError::MixedValues { index: 2 }));
+	let options = Options { typed_values: Some(&[Value::U32(1), Value::I64(2)]), ..options };
+	assert_eq!(options.rust(), Err(CodegenError::LengthMismatch { keys: 3, values: 2 }));
+}
+
+#[test]
+fn test_provenance() {
+	let options = Options {
+		name: "table",
+		keys: &["a", "b", "c"],
+		values: &["1", "2", "3"],
+		seeds_len: 1,
+		max_seed: 100000,
+		..Options::default()
+	};
+	let code = options.rust().unwrap();
+	let digest = crate::binary::key_digest(&["a", "b", "c"]);
+	assert!(code.starts_with(&format!("// @generated by mphf {}\n// 3 keys with digest {:016x}, seeds_len 1, max_seed 100000\npub mod table {{\n", env!("CARGO_PKG_VERSION"), digest)));
+
+	// The digest does not depend on the order of the keys
+	let reordered = Options { keys: &["c", "a", "b"], ..options };
+	assert_eq!(reordered.provenance("//"), options.provenance("//"));
+	assert_ne!(Options { keys: &["a", "b", "d"], ..options }.provenance("//"), options.provenance("//"));
+
+	let options = Options { verify_keys: true, has_index: false, header: Some("Regenerate with:\n\n  cargo run -p gen"), ..options };
+	assert_eq!(options.provenance("#"), format!("# 3 keys with digest {:016x}, seeds_len 1, max_seed 100000\n# options: has_index = false, verify_keys = true\n# Regenerate with:\n#\n#   cargo run -p gen\n", digest));
+	assert!(options.python().unwrap().starts_with(&format!("# @generated by mphf {}\n{}", env!("CARGO_PKG_VERSION"), options.provenance("#"))));
+	assert!(options.c().unwrap().lines().all(|line| line == line.trim_end()));
+}
+
+#[test]
+fn test_docs() {
+	let options = Options {
+		name: "table",
+		keys: &["a", "b"],
+		values: &["1", "2"],
+		seeds_len: 1,
+		max_seed: 100000,
+		doc: Some("First line.  \r\n\r\n```\nlet x = \"*/\";\n```\n"),
+		doc_hidden: true,
+		allow_missing_docs: true,
+		..Options::default()
+	};
+	let code = options.rust().unwrap();
+	assert!(code.contains("\n/// First line.\n///\n/// ```\n/// let x = \"*/\";\n/// ```\n#[allow(missing_docs)]\n#[doc(hidden)]\npub mod table {\n"));
+	syn::parse_file(&code).unwrap();
+
+	// The lookups tell whether keys outside the table are rejected
+	assert!(code.contains("/// Returns the value of the key, keys outside the table map to an arbitrary entry or `None`.\n"));
+	let code = Options { verify_keys: true, doc: None, ..options }.rust().unwrap();
+	assert!(code.contains("/// Returns the value of the key, returns `None` for keys outside the table.\n"));
+	assert!(code.contains("\n#[allow(missing_docs)]\n#[doc(hidden)]\npub mod table {\n"));
+}
+
+#[test]
+fn test_footer() {
+	let options = Options {
+		name: "table",
+		keys: &["a", "b"],
+		values: &["1", "2"],
+		seeds_len: 1,
+		max_seed: 100000,
+		footer: Some("pub use self::table::value as table_value;\npub fn table_len() -> usize { table::LEN }"),
+		..Options::default()
+	};
+	let code = options.rust().unwrap();
+	assert!(code.ends_with("\n}\npub use self::table::value as table_value;\npub fn table_len() -> usize { table::LEN }\n"));
+	syn::parse_file(&code).unwrap();
+	// A footer ending with a newline is not given another one
+	let code = Options { footer: Some("// end\n"), ..options }.rust().unwrap();
+	assert!(code.ends_with("\n}\n// end\n"));
+	assert_eq!(Options { footer: Some(""), ..options }.rust(), Options { footer: None, ..options }.rust());
+}
+
+#[test]
+fn test_indent() {
+	let options = Options {
+		name: "table",
+		visibility: "pub(crate)",
+		standalone: true,
+		narrow_seeds: true,
+		keys: &["a", "b", "c"],
+		values: &["1", "2", "3"],
+		seeds_len: 2,
+		max_seed: 100000,
+		has_unchecked: true,
+		has_contains: true,
+		has_entry: true,
+		emit_enum: Some("Key"),
+		..Options::default()
+	};
+	let tabs = options.rust().unwrap();
+	for &indent in &[Indent::Tabs, Indent::Spaces(2), Indent::Spaces(4)] {
+		for &elems_per_line in &[0, 1, 2] {
+			let code = Options { format: CodegenFormat { elems_per_line, indent }, ..options }.rust().unwrap();
+			syn::parse_file(&code).unwrap();
+			// Only the indentation differs from the default
+			if elems_per_line == 0 {
+				let (unit, width) = match indent {
+					Indent::Tabs => ("\t", 1),
+					Indent::Spaces(width) => (" ", width as usize),
+				};
+				for (line, tab_line) in code.lines().zip(tabs.lines()) {
+					let depth = tab_line.len() - tab_line.trim_start_matches('\t').len();
+					assert_eq!(line, format!("{}{}", unit.repeat(depth * width), tab_line.trim_start_matches('\t')));
+				}
+				assert_eq!(code.lines().count(), tabs.lines().count());
+			}
+		}
+	}
+	let code = Options { format: CodegenFormat { elems_per_line: 2, indent: Indent::Spaces(4) }, ..options }.rust().unwrap();
+	assert!(!code.contains('\t'));
+	assert!(code.contains("\n    pub(crate) static KEYS: [&str; 3] = [\n        "));
+	assert!(code.contains("\",\n    ];\n"));
+	assert!(code.contains("\n    mod mphf {\n        #![allow(dead_code)]\n"));
+}
+
+#[test]
+fn test_rust_visibility() {
+	let options = Options {
+		name: "table",
+		visibility: "pub(crate)",
+		keys: &["a", "b", "c"],
+		values: &["1", "2", "3"],
+		seeds_len: 1,
+		max_seed: 100000,
+		..Options::default()
+	};
+	let code = options.rust().unwrap();
+	assert!(code.contains("\npub(crate) mod table {\n"));
+	assert!(code.contains("\n\t#![allow(dead_code)]\n"));
+	for line in code.lines().skip_while(|line| line.starts_with("//")).skip(1).filter(|&line| line != "}" && !line.starts_with("\t#!") && !line.starts_with("\t///")) {
+		assert!(line.trim_start_matches('\t').trim_start_matches("#[inline] ").starts_with("pub(crate) "), "{}", line);
+	}
+
+	// Relative visibilities are relative to the parent of the module
+	let code = Options { visibility: "", ..options }.rust().unwrap();
+	assert!(code.contains("\nmod table {\n"));
+	assert!(code.contains("\tpub(super) static SEEDS") && code.contains("#[inline] pub(super) fn value"));
+	let code = Options { visibility: "pub(super)", ..options }.rust().unwrap();
+	assert!(code.contains("\npub(super) mod table {\n"));
+	assert!(code.contains("\tpub(in super::super) static SEEDS") && code.contains("#[inline] pub(in super::super) fn value"));
+	syn::parse_file(&code).unwrap();
+
+	assert_eq!(Options { visibility: "public", ..options }.rust(), Err(CodegenError::InvalidVisibility("public".into())));
+	assert_eq!(Options { name: "my-table", ..options }.rust(), Err(CodegenError::InvalidName("my-table".into())));
+}
+
+#[test]
+fn test_rust_crate_path() {
+	let options = Options {
+		name: "table",
+		crate_path: "mphf_rt",
+		keys: &["a", "b", "c"],
+		values: &["1", "2", "3"],
+		seeds_len: 1,
+		max_seed: 100000,
+		..Options::default()
+	};
+	let code = options.rust().unwrap();
+	assert!(!code.contains("::mphf"));
+	assert!(code.contains("mphf_rt::get(key, &SEEDS, &VALUES)") && code.contains("mphf_rt::index(key, &SEEDS, 3)"));
+
+	for &path in &["::mphf", "mphf", "crate::deps::mphf", "super::mphf", "self::r#mphf", "_mphf2"] {
+		assert!(Options { crate_path: path, ..options }.rust().is_ok(), "{}", path);
+	}
+	for &path in &["", "::", "crate::", "mphf::", "deps:mphf", "deps::::mphf", "2mphf", "mphf rt", "mphf;", "crate::mphf::{get}"] {
+		assert_eq!(Options { crate_path: path, ..options }.rust(), Err(CodegenError::InvalidCratePath(path.into())), "{}", path);
+	}
+}
+
+#[test]
+fn test_rust_items() {
+	let keys = ["one", "two", "three", "four", "five"];
+	let values = ["1", "2", "3", "4", "5"];
+	let items = |options: &Options| {
+		let file = syn::parse_file(&options.rust().unwrap()).unwrap();
+		let module = file.items.iter().find_map(|item| match item { syn::Item::Mod(module) => Some(module), _ => None }).unwrap();
+		module.content.as_ref().unwrap().1.iter().filter_map(|item| match item {
+			syn::Item::Static(item) => Some(item.ident.to_string()),
+			syn::Item::Fn(item) => Some(item.sig.ident.to_string()),
+			_ => None,
+		}).collect::<Vec<String>>().join(" ")
+	};
+	let options = Options {
+		name: "items",
+		keys: &keys,
+		values: &values,
+		seeds_len: 2,
+		max_seed: 100000,
+		..Options::default()
+	};
+	assert_eq!(items(&options), "SEEDS KEYS VALUES key keys value values index len is_empty iter");
+	assert_eq!(items(&Options { has_keys: false, ..options }), "SEEDS VALUES value values index len is_empty");
+	assert_eq!(items(&Options { has_values: false, ..options }), "SEEDS KEYS key keys index len is_empty");
+	assert_eq!(items(&Options { has_index: false, ..options }), "SEEDS KEYS VALUES key keys value values len is_empty iter");
+	assert_eq!(items(&Options { has_keys: false, has_values: false, ..options }), "SEEDS index len is_empty");
+	assert_eq!(items(&Options { has_keys: false, has_values: false, has_index: false, narrow_seeds: true, ..options }), "SEEDS len is_empty");
+	assert_eq!(items(&Options { has_keys: false, has_values: false, has_index: false, has_contains: true, ..options }), "SEEDS KEYS contains_key len is_empty");
+	assert_eq!(items(&Options { has_keys: false, has_contains: true, ..options }), "SEEDS KEYS VALUES value values index contains_key len is_empty");
+	assert_eq!(items(&Options { has_keys: false, has_values: false, narrow_seeds: true, ..options }), "SEEDS lookup index len is_empty");
+	assert_eq!(items(&Options { has_keys: false, has_values: false, has_index: false, has_entry: true, ..options }), "SEEDS KEYS VALUES len is_empty entry");
+	assert_eq!(items(&Options { has_keys: false, verify_keys: true, ..options }), "SEEDS KEYS VALUES value values index len is_empty");
+
+	// Values are not needed when they are not emitted
+	assert_eq!(items(&Options { values: &[], has_values: false, ..options }), "SEEDS KEYS key keys index len is_empty");
+	assert!(Options { values: &[], ..options }.rust().is_err());
+}
+
+#[test]
+fn test_rust_narrow_seeds() {
+	let keys = ["k0", "k1", "k2", "k3", "k4", "k5", "k6", "k7", "k8", "k9"];
+	let values = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+	let seeds_line = |options: &Options| String::from(options.rust().unwrap().lines().find(|line| line.contains(" SEEDS: ")).unwrap());
+
+	// Many buckets with small seeds, a few are empty
+	let options = Options {
+		name: "table",
+		keys: &keys,
+		values: &values,
+		seeds_len: 8,
+		max_seed: 100000,
+		narrow_seeds: true,
+		..Options::default()
+	};
+	let seeds = crate::build(&keys, 8, 100000).unwrap();
+	assert!(seeds.contains(&u32::MAX) && seeds.iter().all(|&seed| seed == u32::MAX || seed < 255));
+	let line = seeds_line(&options);
+	assert!(line.contains("SEEDS: [u8; 8] = ["), "{}", line);
+	assert!(line.contains(",255,") || line.contains("[255,"), "{}", line);
+	let code = options.rust().unwrap();
+	assert!(code.contains("fn lookup(key: &[u8])") && code.contains("if seed == u8::MAX"));
+	assert!(code.contains("fn value(key: &str) -> Option<&'static str> { lookup(key.as_bytes()).map(|i| VALUES[i]) }"));
+	syn::parse_file(&code).unwrap();
+
+	// A single bucket for 7 keys needs a seed above 255
+	let options = Options { keys: &keys[..7], values: &values[..7], seeds_len: 1, ..options };
+	assert!(seeds_line(&options).contains("SEEDS: [u16; 1] = [479,]"));
+	assert!(seeds_line(&Options { narrow_seeds: false, ..options }).contains("SEEDS: [u32; 1] = [479,]"));
+	assert!(Options { narrow_seeds: false, ..options }.rust().unwrap().contains("::mphf::get(key, &SEEDS, &VALUES)"));
+
+	assert_eq!(rust::seed_type(&[0, 254, u32::MAX]), "u8");
+	assert_eq!(rust::seed_type(&[255]), "u16");
+	assert_eq!(rust::seed_type(&[65534, u32::MAX]), "u16");
+	assert_eq!(rust::seed_type(&[65535]), "u32");
+	assert_eq!(rust::seed_type(&[u32::MAX - 1]), "u32");
+	assert_eq!(rust::seed_type(&[u32::MAX]), "u8");
+}
+
+#[test]
+fn test_rust_enum() {
+	let options = Options {
+		name: "commands",
+		keys: &["hello", "good-bye", "list_all", "GET", "v2"],
+		seeds_len: 2,
+		max_seed: 100000,
+		has_values: false,
+		emit_enum: Some("Command"),
+		..Options::default()
+	};
+	let code = options.rust().unwrap();
+	syn::parse_file(&code).unwrap();
+	for variant in &["Hello", "GoodBye", "ListAll", "GET", "V2"] {
+		assert!(code.contains(&format!("\t\t{} = ", variant)), "{}", variant);
+	}
+
+	assert_eq!(Options { emit_enum: Some("my enum"), ..options }.rust(), Err(CodegenError::InvalidEnumName("my enum".into())));
+	assert_eq!(Options { emit_enum: Some("a::Command"), ..options }.rust(), Err(CodegenError::InvalidEnumName("a::Command".into())));
+	assert_eq!(Options { key_kind: KeyKind::Bytes, byte_keys: &[b"hello"], ..options }.rust(), Err(CodegenError::EnumByteKeys));
+	for &key in &["", "2fa", "--", "self", "\u{e9}"] {
+		let keys = ["hello", key];
+		assert_eq!(Options { keys: &keys, ..options }.rust(), Err(CodegenError::InvalidVariant { key: key.into() }), "{:?}", key);
+	}
+	let keys = ["good-bye", "good_bye"];
+	match (Options { keys: &keys, ..options }).rust() {
+		Err(CodegenError::DuplicateVariant { variant, keys: (a, b) }) => {
+			assert_eq!(variant, "GoodBye");
+			assert!(a != b && keys.contains(&&*a) && keys.contains(&&*b));
+		},
+		result => panic!("{:?}", result),
+	}
+}
+
+#[test]
+fn test_c_literal() {
+	assert_eq!(c::c_literal(b"hello"), "\"hello\"");
+	assert_eq!(c::c_literal(b""), "\"\"");
+	assert_eq!(c::c_literal(b"say \"hi\"\\"), "\"say \\\"hi\\\"\\\\\"");
+	// Trigraphs such as ??= must not form
+	assert_eq!(c::c_literal(b"what??="), "\"what\\?\\?=\"");
+	// Octal escapes take exactly three digits so the digits after them are not part of the escape
+	assert_eq!(c::c_literal(b"\x001"), "\"\\0001\"");
+	assert_eq!(c::c_literal(b"tab\tnew\nline"), "\"tab\\011new\\012line\"");
+	assert_eq!(c::c_literal("\u{e9}".as_bytes()), "\"\\303\\251\"");
+	assert_eq!(c::c_literal(b"\x7f\xff"), "\"\\177\\377\"");
+}
+
+// The small table the tests of the other backends start from
+#[cfg(test)]
+fn backend_options() -> Options<'static> {
+	Options {
+		name: "table",
+		keys: &["a", "b"],
+		values: &["1", "2"],
+		seeds_len: 1,
+		max_seed: 100000,
+		..Options::default()
+	}
+}
+
+#[test]
+fn test_c_errors() {
+	let options = backend_options();
+	assert!(options.c().unwrap().contains("int mphf_table_index(const char *key, size_t len) {"));
+	assert_eq!(Options { name: "my-table", ..options }.c(), Err(CodegenError::InvalidName("my-table".into())));
+	assert_eq!(Options { typed_values: Some(&[Value::U32(1), Value::U32(2)]), ..options }.c(), Err(CodegenError::Unsupported("typed values")));
+	assert_eq!(Options { value_type: "u32", ..options }.c(), Err(CodegenError::Unsupported("value types other than strings")));
+	assert_eq!(Options { values: &["1"], ..options }.c(), Err(CodegenError::LengthMismatch { keys: 2, values: 1 }));
+	assert!(Options { values: &[], has_values: false, ..options }.c().is_ok());
+}
+
+#[test]
+fn test_cpp() {
+	let options = Options {
+		name: "table",
+		keys: &["a", "nul\0", "b"],
+		typed_values: Some(&[Value::I64(i64::MIN), Value::I64(-1), Value::I64(7)]),
+		seeds_len: 1,
+		max_seed: 100000,
+		..Options::default()
+	};
+	let code = options.cpp().unwrap();
+	assert!(code.contains("constexpr std::array<std::int64_t, LEN> VALUES = {"));
+	assert!(code.contains("INT64_MIN,") && code.contains("INT64_C(-1),") && code.contains("INT64_C(7),"));
+	assert!(code.contains("std::string_view(\"nul\\000\", 4),"));
+	assert!(code.contains("(x << 15 | x >> 17)") && !code.contains("<bit>"));
+	let code = Options { cpp_standard: CppStandard::Cpp20, ..options }.cpp().unwrap();
+	assert!(code.contains("std::rotl(x, 15)") && code.contains("#include <bit>"));
+
+	assert_eq!(Options { name: "a::b", ..options }.cpp(), Err(CodegenError::InvalidName("a::b".into())));
+	assert_eq!(Options { typed_values: None, value_type: "Route", ..options }.cpp(), Err(CodegenError::Unsupported("value types other than strings and typed values")));
+	assert_eq!(Options { typed_values: Some(&[Value::U32(1), Value::I64(2), Value::U32(3)]), ..options }.cpp(), Err(CodegenError::MixedValues { index: 1 }));
+	assert_eq!(Options { typed_values: Some(&[Value::U32(1)]), ..options }.cpp(), Err(CodegenError::LengthMismatch { keys: 3, values: 1 }));
+}
+
+#[test]
+fn test_go() {
+	assert_eq!(go::go_literal(b"plain text"), "\"plain text\"");
+	assert_eq!(go::go_literal(b"q\"b\\"), "\"q\\\"b\\\\\"");
+	// Hex escapes are exactly two digits in Go
+	assert_eq!(go::go_literal(b"\x001\tx\x7f"), "\"\\x001\\x09x\\x7f\"");
+	assert_eq!(go::go_literal("\u{e9}".as_bytes()), "\"\\xc3\\xa9\"");
+	assert_eq!(go::go_literal(b"??="), "\"??=\"");
+
+	let options = backend_options();
+	let code = options.go("tables").unwrap();
+	assert!(code.contains("package tables\n") && code.contains("func Value(key string) (string, bool) {"));
+	assert!(!code.contains("if keys[i] != key"));
+	assert!(Options { verify_keys: true, ..options }.go("tables").unwrap().contains("if keys[i] != key"));
+	assert_eq!(options.go("my-package"), Err(CodegenError::InvalidName("my-package".into())));
+	assert_eq!(Options { typed_values: Some(&[Value::U32(1), Value::U32(2)]), ..options }.go("tables"), Err(CodegenError::Unsupported("typed values")));
+}
+
+#[test]
+fn test_typescript() {
+	assert_eq!(typescript::ts_literal("plain text"), "\"plain text\"");
+	assert_eq!(typescript::ts_literal("q\"b\\"), "\"q\\\"b\\\\\"");
+	assert_eq!(typescript::ts_literal("\0\n\u{7f}"), "\"\\u{0}\\u{a}\\u{7f}\"");
+	// Outside the BMP a single code point escape instead of a surrogate pair
+	assert_eq!(typescript::ts_literal("caf\u{e9} \u{1f600}"), "\"caf\\u{e9} \\u{1f600}\"");
+
+	let options = backend_options();
+	let code = options.typescript().unwrap();
+	assert!(code.contains("export function value(key: string): string | undefined {"));
+	assert!(!code.contains("KEYS[i] !== key"));
+	assert!(Options { verify_keys: true, ..options }.typescript().unwrap().contains("if (KEYS[i] !== key) return undefined;"));
+	assert!(Options { keys: &[], values: &[], ..options }.typescript().unwrap().contains("export function index(key: string): number | undefined {\n\treturn undefined;\n}"));
+	assert_eq!(Options { key_kind: KeyKind::Bytes, byte_keys: &[b"a", b"b"], ..options }.typescript(), Err(CodegenError::Unsupported("byte string keys")));
+	assert_eq!(Options { typed_values: Some(&[Value::U32(1), Value::U32(2)]), ..options }.typescript(), Err(CodegenError::Unsupported("typed values")));
+}
+
+#[test]
+fn test_python() {
+	assert_eq!(python::str_literal("plain text"), "\"plain text\"");
+	assert_eq!(python::str_literal("q\"b\\"), "\"q\\\"b\\\\\"");
+	assert_eq!(python::str_literal("\0\u{e9}\u{65e5}\u{1f600}"), "\"\\x00\\xe9\\u65e5\\U0001f600\"");
+	assert_eq!(python::bytes_literal(b"a\xff\""), "b\"a\\xff\\\"\"");
+
+	let options = backend_options();
+	let code = options.python().unwrap();
+	assert!(code.contains("def index(key: str) -> Optional[int]:\n    data = key.encode(\"utf-8\")\n"));
+	assert!(code.contains(&format!("assert _hash(b\"abc\", 0) == {:#010x}\n", crate::hash(b"abc", 0))));
+	assert!(!code.contains("if KEYS[i] != key:"));
+	assert!(Options { verify_keys: true, ..options }.python().unwrap().contains("if KEYS[i] != key:"));
+	let code = Options { key_kind: KeyKind::Bytes, byte_keys: &[b"a", b"\xff"], ..options }.python().unwrap();
+	assert!(code.contains("b\"\\xff\"") && code.contains("def index(key: bytes) -> Optional[int]:\n    data = key\n"));
+	assert_eq!(Options { typed_values: Some(&[Value::U32(1), Value::U32(2)]), ..options }.python(), Err(CodegenError::Unsupported("typed values")));
+}
+
+#[test]
+fn test_json() {
+	assert_eq!(json::json_string("plain \u{e9}\u{1f600}"), "\"plain \u{e9}\u{1f600}\"");
+	assert_eq!(json::json_string("q\"b\\\n\t\0\u{7f}"), "\"q\\\"b\\\\\\n\\t\\u0000\\u007f\"");
+
+	let options = backend_options();
+	let code = options.json().unwrap();
+	assert!(code.starts_with("{\n  \"version\": 1,\n  \"name\": \"table\",\n  \"hash\": \"murmur3_32\",\n"));
+	assert!(code.contains("\"values\": [\"") && code.ends_with("]\n}\n"));
+	assert!(!Options { has_values: false, ..options }.json().unwrap().contains("\"values\""));
+	let code = Options { typed_values: Some(&[Value::I64(-1), Value::I64(-1)]), ..options }.json().unwrap();
+	assert!(code.contains("\"values\": [-1, -1]"));
+	assert_eq!(Options { typed_values: Some(&[Value::F64(1.0), Value::F64(f64::NAN)]), ..options }.json(), Err(CodegenError::Unsupported("non-finite float values")));
+	assert_eq!(Options { typed_values: Some(&[Value::U32(1), Value::I64(2)]), ..options }.json(), Err(CodegenError::MixedValues { index: 1 }));
+	assert_eq!(Options { key_kind: KeyKind::Bytes, byte_keys: &[b"a", b"b"], ..options }.json(), Err(CodegenError::Unsupported("byte string keys")));
+}
+
+pub mod buildrs;
+
+mod batch;
+pub use self::batch::{Batch, BatchError};
+
+mod csv;
+pub use self::csv::{load_csv, CsvError, CsvOptions, OwnedEntries};
+
+#[cfg(feature = "toml")]
+mod manifest;
+#[cfg(feature = "toml")]
+pub use self::manifest::{Manifest, ManifestError, ManifestRefs};
+
+mod c;
+mod cpp;
+mod go;
+mod json;
+mod python;
+mod typescript;
 mod rust;
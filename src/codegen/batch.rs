@@ -0,0 +1,190 @@
+use std::{fmt, path::Path};
+use std::collections::HashSet;
+use super::{rust, write_if_changed, CodegenError, Options};
+
+/// Generates many tables at once, intended for build scripts.
+///
+/// Every table is generated on its own, the errors of all tables are collected instead of stopping at the first.
+/// The tables do not share scratch buffers between their builds, the allocations of a build are small next to its seed search.
+///
+/// # Examples
+///
+/// ```
+/// let batch = mphf::codegen::Batch {
+/// 	tables: vec![
+/// 		mphf::codegen::Options { name: "colors", keys: &["red", "green"], values: &["#f00", "#0f0"], seeds_len: 1, max_seed: 100000, ..Default::default() },
+/// 		mphf::codegen::Options { name: "sizes", keys: &["small", "large"], values: &["s", "l"], seeds_len: 1, max_seed: 100000, ..Default::default() },
+/// 	],
+/// 	parent_module: Some("tables"),
+/// };
+/// let code = batch.rust().unwrap();
+/// assert!(code.contains("pub mod colors {") && code.contains("pub mod sizes {"));
+/// assert!(code.contains("pub mod tables {\n\tpub use super::{colors, sizes};\n}\n"));
+/// ```
+#[derive(Default)]
+pub struct Batch<'a> {
+	pub tables: Vec<Options<'a>>,
+	/// Emits a module with this name re-exporting every table.
+	///
+	/// The tables themselves stay side by side so their values can still refer to the items next to them.
+	/// Each table is re-exported as visible as the table itself.
+	pub parent_module: Option<&'a str>,
+}
+
+impl<'a> Batch<'a> {
+	/// Generates the Rust source code of every table, the modules are written side by side.
+	pub fn rust(&self) -> Result<String, BatchError> {
+		let (code, err) = self.generate();
+		if !err.errors.is_empty() {
+			return Err(err);
+		}
+		let mut out = code.iter().map(|(_, code)| &code[..]).collect::<String>();
+		if let Some(parent) = self.parent_module {
+			out.push_str(&self.reexports(parent));
+		}
+		Ok(out)
+	}
+
+	/// Writes every table to `{name}.rs` in the directory, the files are written the same as [`Options::write_rust`].
+	///
+	/// With a parent module `{parent}.rs` includes the other files and re-exports the tables, so a single `include!` brings in all of them.
+	/// The tables which generate fine are written even when others fail, the parent file only when none fail.
+	pub fn write_rust_dir<P: AsRef<Path>>(&self, dir: P) -> Result<(), BatchError> {
+		let dir = dir.as_ref();
+		let (generated, BatchError { mut errors }) = self.generate();
+		for (name, code) in &generated {
+			if let Err(err) = write_if_changed(&dir.join(format!("{}.rs", name)), code.as_bytes()) {
+				errors.push((String::from(*name), err));
+			}
+		}
+		if !errors.is_empty() {
+			return Err(BatchError { errors });
+		}
+		if let Some(parent) = self.parent_module {
+			let mut code = self.tables.iter().map(|options| format!("include!({:?});\n", format!("{}.rs", options.name))).collect::<String>();
+			code.push_str(&self.reexports(parent));
+			write_if_changed(&dir.join(format!("{}.rs", parent)), code.as_bytes()).map_err(|err| BatchError { errors: vec![(String::from(parent), err)] })?;
+		}
+		Ok(())
+	}
+
+	// The names and code of the tables which generate, and the errors of the tables which fail
+	// A table with the name of an earlier table fails without being generated
+	fn generate(&self) -> (Vec<(&'a str, String)>, BatchError) {
+		let mut generated = Vec::new();
+		let mut errors = Vec::new();
+		let mut names = HashSet::new();
+		for options in &self.tables {
+			if !names.insert(options.name) {
+				errors.push((String::from(options.name), CodegenError::DuplicateName(String::from(options.name))));
+				continue;
+			}
+			match options.rust() {
+				Ok(code) => generated.push((options.name, code)),
+				Err(err) => errors.push((String::from(options.name), err)),
+			}
+		}
+		(generated, BatchError { errors })
+	}
+
+	// Grouped by visibility in the order of the tables, the tables all generated so their visibility is valid
+	fn reexports(&self, parent: &str) -> String {
+		let mut groups: Vec<(&str, Vec<&str>)> = Vec::new();
+		for options in &self.tables {
+			let vis = rust::nested_visibility(options.visibility).map_or("pub", |(_, vis)| vis);
+			match groups.iter_mut().find(|(group, _)| *group == vis) {
+				Some((_, names)) => names.push(options.name),
+				None => groups.push((vis, vec![options.name])),
+			}
+		}
+		let mut code = format!("pub mod {} {{\n", parent);
+		for (vis, names) in &groups {
+			code.push_str(&format!("\t{} use super::{{{}}};\n", vis, names.join(", ")));
+		}
+		code.push_str("}\n");
+		code
+	}
+}
+
+/// Errors of the tables of a [`Batch`] which failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchError {
+	/// The name of the table and its error, in the order of the tables.
+	pub errors: Vec<(String, CodegenError)>,
+}
+
+impl fmt::Display for BatchError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} tables failed to generate", self.errors.len())?;
+		for (name, err) in &self.errors {
+			write!(f, "\n  {}: {}", name, err)?;
+		}
+		Ok(())
+	}
+}
+
+impl std::error::Error for BatchError {}
+
+#[test]
+fn test_batch() {
+	let table = |name: &'static str, keys: &'static [&'static str], seeds_len: usize| Options {
+		name,
+		keys,
+		values: keys,
+		seeds_len,
+		max_seed: 100000,
+		..Options::default()
+	};
+	let tables = vec![table("colors", &["red", "green", "blue"], 1), table("sizes", &["s", "m", "l", "xl"], 2), table("words", &["a", "b", "c", "d", "e"], 2)];
+	let batch = Batch { tables, parent_module: Some("tables") };
+	let code = batch.rust().unwrap();
+	// Each table is the same as generated alone
+	let alone = batch.tables.iter().map(|options| options.rust().unwrap()).collect::<String>();
+	assert_eq!(code, format!("{}pub mod tables {{\n\tpub use super::{{colors, sizes, words}};\n}}\n", alone));
+
+	// The failures of every table are collected
+	let failing = Batch {
+		tables: vec![table("ok", &["x"], 1), table("zero", &["a"], 0), table("dup", &["a", "a"], 1), table("ok", &["y"], 1)],
+		parent_module: None,
+	};
+	let err = failing.rust().unwrap_err();
+	let names = err.errors.iter().map(|(name, _)| &name[..]).collect::<Vec<&str>>();
+	assert_eq!(names, ["zero", "dup", "ok"]);
+	assert!(matches!(err.errors[0].1, CodegenError::Build { error: crate::BuildError::ZeroSeedsLen, .. }));
+	assert_eq!(err.errors[2].1, CodegenError::DuplicateName(String::from("ok")));
+	assert!(err.to_string().starts_with("3 tables failed to generate\n  zero: "));
+
+	let dir = std::env::temp_dir().join(format!("mphf-test-batch-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).unwrap();
+	batch.write_rust_dir(&dir).unwrap();
+	assert_eq!(std::fs::read_to_string(dir.join("sizes.rs")).unwrap(), batch.tables[1].rust().unwrap());
+	let parent = std::fs::read_to_string(dir.join("tables.rs")).unwrap();
+	assert!(parent.starts_with("include!(\"colors.rs\");\ninclude!(\"sizes.rs\");\ninclude!(\"words.rs\");\npub mod tables {"));
+
+	// The tables which generate are still written
+	let partial = Batch { tables: vec![table("good", &["x"], 1), table("bad", &["a", "a"], 1)], parent_module: Some("partial") };
+	assert_eq!(partial.write_rust_dir(&dir).unwrap_err().errors.len(), 1);
+	assert!(dir.join("good.rs").exists() && !dir.join("bad.rs").exists() && !dir.join("partial.rs").exists());
+
+	// A later table with the same name does not overwrite the earlier one
+	let duplicate = Batch { tables: vec![table("twice", &["first"], 1), table("twice", &["second"], 1)], parent_module: None };
+	assert_eq!(duplicate.write_rust_dir(&dir).unwrap_err().errors, [(String::from("twice"), CodegenError::DuplicateName(String::from("twice")))]);
+	assert_eq!(std::fs::read_to_string(dir.join("twice.rs")).unwrap(), duplicate.tables[0].rust().unwrap());
+	std::fs::remove_dir_all(&dir).unwrap();
+
+	// Names which are not identifiers
+	let invalid = Batch { tables: vec![table("my-table", &["a"], 1), table("../escape", &["b"], 1)], parent_module: None };
+	let err = invalid.rust().unwrap_err();
+	assert_eq!(err.errors[0].1, CodegenError::InvalidName(String::from("my-table")));
+	assert_eq!(err.errors[1].1, CodegenError::InvalidName(String::from("../escape")));
+
+	// Private tables are re-exported as visible as themselves
+	let tables = vec![
+		Options { visibility: "", ..table("private", &["a"], 1) },
+		table("public", &["b"], 1),
+		Options { visibility: "pub(crate)", ..table("internal", &["c"], 1) },
+		Options { visibility: "pub(self)", ..table("also_private", &["d"], 1) },
+	];
+	let code = Batch { tables, parent_module: Some("all") }.rust().unwrap();
+	assert!(code.ends_with("pub mod all {\n\tpub(super) use super::{private, also_private};\n\tpub use super::{public};\n\tpub(crate) use super::{internal};\n}\n"));
+}
@@ -0,0 +1,47 @@
+/*!
+Helpers for build scripts generating tables into `OUT_DIR`.
+
+```no_run
+// build.rs
+let options = mphf::codegen::Options {
+	name: "keywords",
+	keys: &["fn", "let", "match"],
+	values: &["Fn", "Let", "Match"],
+	watch_files: &[std::path::Path::new("keywords.csv")],
+	seeds_len: 1,
+	max_seed: 100000,
+	..Default::default()
+};
+mphf::codegen::buildrs::generate_into_out_dir(&options, "keywords.rs").unwrap();
+```
+
+The crate includes the module with `include!(concat!(env!("OUT_DIR"), "/keywords.rs"));`.
+*/
+
+use std::{env, io};
+use std::path::{Path, PathBuf};
+use super::{CodegenError, Options};
+
+/// Generates the Rust module into the `OUT_DIR` of the build script and prints the Cargo directives to stdout.
+///
+/// Returns the path of the written file, see [`generate_into`].
+pub fn generate_into_out_dir(options: &Options, file_name: &str) -> Result<PathBuf, CodegenError> {
+	let out_dir = env::var_os("OUT_DIR").ok_or(CodegenError::MissingOutDir)?;
+	generate_into(options, Path::new(&out_dir), file_name, &mut io::stdout())
+}
+
+/// Generates the Rust module into the directory and writes the Cargo directives to `directives`.
+///
+/// A `cargo:rerun-if-changed` line is written for every file in [`Options::watch_files`] before generating,
+/// so a build failing on a bad input reruns once the input is fixed.
+/// Cargo stops rerunning the build script on every change of the package once any such line is printed.
+///
+/// The file is written with [`Options::write_rust`] which leaves it untouched if the content did not change.
+pub fn generate_into(options: &Options, dir: &Path, file_name: &str, directives: &mut dyn io::Write) -> Result<PathBuf, CodegenError> {
+	for path in options.watch_files {
+		writeln!(directives, "{}", crate::build_script::rerun_hint(path)).map_err(|err| CodegenError::Directives(err.kind()))?;
+	}
+	let path = dir.join(file_name);
+	options.write_rust(&path)?;
+	Ok(path)
+}
@@ -0,0 +1,124 @@
+use super::{CodegenError, Options, Support};
+
+pub fn generate(input: &Options) -> Result<String, CodegenError> {
+	let name = input.name;
+	if !is_ident(name) {
+		return Err(CodegenError::InvalidName(String::from(name)));
+	}
+	let (keys, seeds, perm) = input.build_backend(Support::default())?;
+	let literals = perm.iter().map(|&i| c_literal(keys[i])).collect::<Vec<String>>();
+	let values = if input.has_values { perm.iter().map(|&i| c_literal(input.values[i].as_bytes())).collect() } else { Vec::new() };
+	// C has no empty arrays, an empty table is left without them
+	let empty = keys.is_empty();
+
+	Ok(format_xml::template!(
+		"// @generated by mphf "{env!("CARGO_PKG_VERSION")}"\n"
+		{input.provenance("//")}
+		"#include <stddef.h>\n"
+		"#include <stdint.h>\n"
+		"#include <string.h>\n"
+		"\n"
+		"int mphf_"{name}"_index(const char *key, size_t len);\n"
+		if (input.has_values) {
+			"const char *mphf_"{name}"_value(const char *key, size_t len);\n"
+		}
+		"\n"
+		if (!empty) {
+			"static const uint32_t mphf_"{name}"_seeds["{seeds.len()}"] = {" for seed in (seeds.iter()) { {seed}"u," } "};\n"
+			if (input.verify_keys) {
+				"static const char *const mphf_"{name}"_keys["{literals.len()}"] = {" for literal in (&literals) { {literal}"," } "};\n"
+				"static const size_t mphf_"{name}"_key_lens["{literals.len()}"] = {" for &i in (&perm) { {keys[i].len()}"," } "};\n"
+			}
+			if (input.has_values) {
+				"static const char *const mphf_"{name}"_values["{values.len()}"] = {" for value in (&values) { {value}"," } "};\n"
+			}
+			"\n"
+			// Same as mphf::hash
+			"static uint32_t mphf_"{name}"_hash(const char *key, size_t len, uint32_t seed) {\n"
+			"\tconst unsigned char *s = (const unsigned char *)key;\n"
+			"\tuint32_t h = seed;\n"
+			"\tuint32_t k;\n"
+			"\tsize_t i = 0;\n"
+			"\tfor (; i + 4 <= len; i += 4) {\n"
+			"\t\tk = (uint32_t)s[i] | (uint32_t)s[i + 1] << 8 | (uint32_t)s[i + 2] << 16 | (uint32_t)s[i + 3] << 24;\n"
+			"\t\tk *= 0xcc9e2d51u;\n"
+			"\t\tk = k << 15 | k >> 17;\n"
+			"\t\tk *= 0x1b873593u;\n"
+			"\t\th ^= k;\n"
+			"\t\th = h << 13 | h >> 19;\n"
+			"\t\th = h * 5u + 0xe6546b64u;\n"
+			"\t}\n"
+			"\tif ((len & 3) != 0) {\n"
+			"\t\tk = 0;\n"
+			"\t\tif ((len & 3) == 3) k |= (uint32_t)s[i + 2] << 16;\n"
+			"\t\tif ((len & 3) >= 2) k |= (uint32_t)s[i + 1] << 8;\n"
+			"\t\tk |= (uint32_t)s[i];\n"
+			"\t\tk *= 0xcc9e2d51u;\n"
+			"\t\tk = k << 15 | k >> 17;\n"
+			"\t\tk *= 0x1b873593u;\n"
+			"\t\th ^= k;\n"
+			"\t}\n"
+			"\th ^= (uint32_t)len;\n"
+			"\th ^= h >> 16;\n"
+			"\th *= 0x85ebca6bu;\n"
+			"\th ^= h >> 13;\n"
+			"\th *= 0xc2b2ae35u;\n"
+			"\th ^= h >> 16;\n"
+			"\treturn h;\n"
+			"}\n"
+			"\n"
+			"int mphf_"{name}"_index(const char *key, size_t len) {\n"
+			"\tuint32_t seed = mphf_"{name}"_seeds[mphf_"{name}"_hash(key, len, 0) % "{seeds.len()}"u];\n"
+			"\tsize_t i;\n"
+			"\tif (seed == 0xffffffffu) return -1;\n"
+			"\ti = mphf_"{name}"_hash(key, len, seed) % "{literals.len()}"u;\n"
+			if (input.verify_keys) {
+				"\tif (mphf_"{name}"_key_lens[i] != len || memcmp(mphf_"{name}"_keys[i], key, len) != 0) return -1;\n"
+			}
+			"\treturn (int)i;\n"
+			"}\n"
+			if (input.has_values) {
+				"const char *mphf_"{name}"_value(const char *key, size_t len) {\n"
+				"\tint i = mphf_"{name}"_index(key, len);\n"
+				"\treturn i < 0 ? NULL : mphf_"{name}"_values[i];\n"
+				"}\n"
+			}
+		}
+		else {
+			"int mphf_"{name}"_index(const char *key, size_t len) {\n"
+			"\t(void)key;\n"
+			"\t(void)len;\n"
+			"\treturn -1;\n"
+			"}\n"
+			if (input.has_values) {
+				"const char *mphf_"{name}"_value(const char *key, size_t len) {\n"
+				"\t(void)key;\n"
+				"\t(void)len;\n"
+				"\treturn NULL;\n"
+				"}\n"
+			}
+		}
+	).to_string())
+}
+
+pub(super) fn is_ident(name: &str) -> bool {
+	let mut chars = name.chars();
+	matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_') && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// Printable ASCII is written as is, other bytes are written as three digit octal escapes which cannot run into the next character
+// Question marks are escaped so no trigraphs are formed
+pub(super) fn c_literal(bytes: &[u8]) -> String {
+	let mut literal = String::from("\"");
+	for &byte in bytes {
+		match byte {
+			b'"' => literal.push_str("\\\""),
+			b'\\' => literal.push_str("\\\\"),
+			b'?' => literal.push_str("\\?"),
+			0x20..=0x7e => literal.push(byte as char),
+			_ => literal.push_str(&format!("\\{:03o}", byte)),
+		}
+	}
+	literal.push('"');
+	literal
+}
@@ -0,0 +1,138 @@
+use super::{CodegenError, CppStandard, Options, Support, Value};
+use super::c::{c_literal, is_ident};
+
+pub fn generate(input: &Options) -> Result<String, CodegenError> {
+	let name = input.name;
+	if !is_ident(name) {
+		return Err(CodegenError::InvalidName(String::from(name)));
+	}
+	let (keys, seeds, perm) = input.build_backend(Support { typed_values: true, ..Support::default() })?;
+
+	// Plain literals with an explicit length so embedded nul bytes are kept, the escapes are the same as for C
+	let literals = perm.iter().map(|&i| string_view(keys[i])).collect::<Vec<String>>();
+	let (values, value_ty) = match input.typed_values {
+		_ if !input.has_values => (Vec::new(), "std::string_view"),
+		Some(values) => {
+			let value_ty = match values.first() {
+				Some(Value::Str(_)) | None => "std::string_view",
+				Some(Value::U32(_)) => "std::uint32_t",
+				Some(Value::I64(_)) => "std::int64_t",
+				Some(Value::F64(_)) => "double",
+			};
+			if let Some(index) = values.iter().position(|value| std::mem::discriminant(value) != std::mem::discriminant(&values[0])) {
+				return Err(CodegenError::MixedValues { index });
+			}
+			(perm.iter().map(|&i| value_literal(&values[i])).collect(), value_ty)
+		},
+		None => (perm.iter().map(|&i| string_view(input.values[i].as_bytes())).collect(), "std::string_view"),
+	};
+	let rotl = |n: u32| match input.cpp_standard {
+		CppStandard::Cpp17 => format!("(x << {} | x >> {})", n, 32 - n),
+		CppStandard::Cpp20 => format!("std::rotl(x, {})", n),
+	};
+	let (rotl15, rotl13) = (rotl(15), rotl(13));
+	// An empty table has nothing to look up and no modulo by zero
+	let empty = keys.is_empty();
+
+	Ok(format_xml::template!(
+		"// @generated by mphf "{env!("CARGO_PKG_VERSION")}"\n"
+		{input.provenance("//")}
+		"#pragma once\n"
+		"#include <array>\n"
+		if (input.cpp_standard == CppStandard::Cpp20) {
+			"#include <bit>\n"
+		}
+		"#include <cstddef>\n"
+		"#include <cstdint>\n"
+		if (value_ty == "double") {
+			"#include <limits>\n"
+		}
+		"#include <optional>\n"
+		"#include <string_view>\n"
+		"\n"
+		"namespace "{name}" {\n"
+		"\tconstexpr std::size_t LEN = "{literals.len()}";\n"
+		"\tconstexpr std::array<std::uint32_t, "{seeds.len()}"> SEEDS = {" for seed in (seeds.iter()) { {seed}"u," } "};\n"
+		"\tconstexpr std::array<std::string_view, LEN> KEYS = {" for literal in (&literals) { {literal}"," } "};\n"
+		if (input.has_values) {
+			"\tconstexpr std::array<"{value_ty}", LEN> VALUES = {" for value in (&values) { {value}"," } "};\n"
+		}
+		"\n"
+		"\tnamespace detail {\n"
+		"\t\tconstexpr std::uint32_t byte(std::string_view key, std::size_t i) noexcept {\n"
+		"\t\t\treturn static_cast<unsigned char>(key[i]);\n"
+		"\t\t}\n"
+		"\t\tconstexpr std::uint32_t mix(std::uint32_t x) noexcept {\n"
+		"\t\t\tx *= 0xcc9e2d51u;\n"
+		"\t\t\tx = "{rotl15}";\n"
+		"\t\t\treturn x * 0x1b873593u;\n"
+		"\t\t}\n"
+		"\t}\n"
+		"\n"
+		"\t// Same as mphf::hash\n"
+		"\tconstexpr std::uint32_t hash(std::string_view key, std::uint32_t seed) noexcept {\n"
+		"\t\tstd::uint32_t x = seed;\n"
+		"\t\tstd::size_t i = 0;\n"
+		"\t\tfor (; i + 4 <= key.size(); i += 4) {\n"
+		"\t\t\tx ^= detail::mix(detail::byte(key, i) | detail::byte(key, i + 1) << 8 | detail::byte(key, i + 2) << 16 | detail::byte(key, i + 3) << 24);\n"
+		"\t\t\tx = "{rotl13}";\n"
+		"\t\t\tx = x * 5u + 0xe6546b64u;\n"
+		"\t\t}\n"
+		"\t\tif ((key.size() & 3) != 0) {\n"
+		"\t\t\tstd::uint32_t k = detail::byte(key, i);\n"
+		"\t\t\tif ((key.size() & 3) >= 2) k |= detail::byte(key, i + 1) << 8;\n"
+		"\t\t\tif ((key.size() & 3) == 3) k |= detail::byte(key, i + 2) << 16;\n"
+		"\t\t\tx ^= detail::mix(k);\n"
+		"\t\t}\n"
+		"\t\tx ^= static_cast<std::uint32_t>(key.size());\n"
+		"\t\tx ^= x >> 16;\n"
+		"\t\tx *= 0x85ebca6bu;\n"
+		"\t\tx ^= x >> 13;\n"
+		"\t\tx *= 0xc2b2ae35u;\n"
+		"\t\tx ^= x >> 16;\n"
+		"\t\treturn x;\n"
+		"\t}\n"
+		"\n"
+		"\tconstexpr std::optional<std::size_t> index(std::string_view key) noexcept {\n"
+		if (empty) {
+			"\t\tstatic_cast<void>(key);\n"
+			"\t\treturn std::nullopt;\n"
+		}
+		else {
+			"\t\tstd::uint32_t seed = SEEDS[hash(key, 0) % SEEDS.size()];\n"
+			"\t\tif (seed == 0xffffffffu) return std::nullopt;\n"
+			"\t\tstd::size_t i = hash(key, seed) % LEN;\n"
+			if (input.verify_keys) {
+				"\t\tif (KEYS[i] != key) return std::nullopt;\n"
+			}
+			"\t\treturn i;\n"
+		}
+		"\t}\n"
+		if (input.has_values) {
+			"\tconstexpr std::optional<"{value_ty}"> value(std::string_view key) noexcept {\n"
+			"\t\tstd::optional<std::size_t> i = index(key);\n"
+			"\t\tif (!i) return std::nullopt;\n"
+			"\t\treturn VALUES[*i];\n"
+			"\t}\n"
+		}
+		"}\n"
+	).to_string())
+}
+
+fn string_view(bytes: &[u8]) -> String {
+	format!("std::string_view({}, {})", c_literal(bytes), bytes.len())
+}
+
+fn value_literal(value: &Value) -> String {
+	match *value {
+		Value::Str(value) => string_view(value.as_bytes()),
+		Value::U32(value) => format!("{}u", value),
+		// The minimum cannot be written as a negated literal
+		Value::I64(i64::MIN) => String::from("INT64_MIN"),
+		Value::I64(value) => format!("INT64_C({})", value),
+		Value::F64(value) if value.is_nan() => String::from("std::numeric_limits<double>::quiet_NaN()"),
+		Value::F64(value) if value == f64::INFINITY => String::from("std::numeric_limits<double>::infinity()"),
+		Value::F64(value) if value == f64::NEG_INFINITY => String::from("-std::numeric_limits<double>::infinity()"),
+		Value::F64(value) => format!("{:?}", value),
+	}
+}
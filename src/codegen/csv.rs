@@ -0,0 +1,245 @@
+use std::{fmt, io};
+use std::collections::HashMap;
+
+/// Options of [`load_csv`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CsvOptions {
+	/// Separator of the fields, `b','` for CSV or `b'\t'` for TSV.
+	pub delimiter: u8,
+	/// Skips the first row which is not blank.
+	pub has_header: bool,
+	/// Zero based column of the keys.
+	pub key_column: usize,
+	/// Zero based column of the values.
+	pub value_column: usize,
+}
+impl Default for CsvOptions {
+	fn default() -> CsvOptions {
+		CsvOptions {
+			delimiter: b',',
+			has_header: false,
+			key_column: 0,
+			value_column: 1,
+		}
+	}
+}
+
+/// Keys and values read by [`load_csv`] in the order of the rows.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OwnedEntries {
+	pub keys: Vec<String>,
+	pub values: Vec<String>,
+}
+impl OwnedEntries {
+	/// Borrows the keys and values as the slices [`Options`](super::Options) takes.
+	///
+	/// ```
+	/// # let entries = mphf::codegen::OwnedEntries::default();
+	/// let (keys, values) = entries.refs();
+	/// let options = mphf::codegen::Options { keys: &keys, values: &values, ..Default::default() };
+	/// ```
+	pub fn refs(&self) -> (Vec<&str>, Vec<&str>) {
+		(self.keys.iter().map(String::as_str).collect(), self.values.iter().map(String::as_str).collect())
+	}
+}
+
+/// Error reading a CSV file.
+///
+/// Rows and columns are one based, the rows count the header and blank rows the same as a spreadsheet.
+#[derive(Debug)]
+pub enum CsvError {
+	/// Reading the input failed.
+	Io(io::Error),
+	/// The quoted field starting in the row is not closed.
+	UnterminatedQuote { row: usize, column: usize },
+	/// The field has a quote in the middle or text after its closing quote.
+	InvalidQuote { row: usize, column: usize },
+	/// The row does not have the key or value column.
+	MissingColumn { row: usize, column: usize },
+	/// The field is not valid UTF-8.
+	NotUtf8 { row: usize, column: usize },
+	/// The key appears in two rows.
+	DuplicateKey { key: String, rows: (usize, usize) },
+}
+
+impl From<io::Error> for CsvError {
+	fn from(err: io::Error) -> CsvError {
+		CsvError::Io(err)
+	}
+}
+
+impl fmt::Display for CsvError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			CsvError::Io(err) => write!(f, "error reading csv: {}", err),
+			CsvError::UnterminatedQuote { row, column } => write!(f, "row {} column {}: quoted field is not closed", row, column),
+			CsvError::InvalidQuote { row, column } => write!(f, "row {} column {}: unexpected quote", row, column),
+			CsvError::MissingColumn { row, column } => write!(f, "row {}: missing column {}", row, column),
+			CsvError::NotUtf8 { row, column } => write!(f, "row {} column {}: field is not valid utf-8", row, column),
+			CsvError::DuplicateKey { key, rows: (first, second) } => write!(f, "key {:?} appears in rows {} and {}", key, first, second),
+		}
+	}
+}
+
+impl std::error::Error for CsvError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			CsvError::Io(err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+/// Reads the keys and values from CSV or TSV, intended for build scripts.
+///
+/// Fields in double quotes may contain the delimiter, newlines and quotes written twice as `""`.
+/// Rows end with `\n` or `\r\n`, blank rows are skipped and the columns other than the key and value columns are ignored.
+/// The fields are not trimmed.
+///
+/// # Examples
+///
+/// ```
+/// let csv = "key,value\nred,#f00\n\"green, light\",#9f9\n";
+/// let options = mphf::codegen::CsvOptions { has_header: true, ..Default::default() };
+/// let entries = mphf::codegen::load_csv(csv.as_bytes(), options).unwrap();
+/// assert_eq!(entries.keys, ["red", "green, light"]);
+/// assert_eq!(entries.values, ["#f00", "#9f9"]);
+/// ```
+pub fn load_csv<R: io::Read>(mut reader: R, options: CsvOptions) -> Result<OwnedEntries, CsvError> {
+	let mut data = Vec::new();
+	reader.read_to_end(&mut data)?;
+
+	let mut entries = OwnedEntries::default();
+	let mut rows = HashMap::new();
+	let records = parse(&data, options.delimiter)?;
+	for (row, fields) in records.into_iter().skip(options.has_header as usize) {
+		let field = |column: usize| match fields.get(column) {
+			Some(field) => String::from_utf8(field.clone()).map_err(|_| CsvError::NotUtf8 { row, column: column + 1 }),
+			None => Err(CsvError::MissingColumn { row, column: column + 1 }),
+		};
+		let key = field(options.key_column)?;
+		let value = field(options.value_column)?;
+		if let Some(&first) = rows.get(&key) {
+			return Err(CsvError::DuplicateKey { key, rows: (first, row) });
+		}
+		rows.insert(key.clone(), row);
+		entries.keys.push(key);
+		entries.values.push(value);
+	}
+	Ok(entries)
+}
+
+// Row number and its fields
+type Record = (usize, Vec<Vec<u8>>);
+
+// Splits the data in rows of fields, blank rows are left out
+fn parse(data: &[u8], delimiter: u8) -> Result<Vec<Record>, CsvError> {
+	let mut records = Vec::new();
+	let mut i = 0;
+	let mut row = 0;
+	while i < data.len() {
+		row += 1;
+		let mut fields = Vec::new();
+		loop {
+			let column = fields.len() + 1;
+			let mut field = Vec::new();
+			if data.get(i) == Some(&b'"') {
+				i += 1;
+				loop {
+					match data.get(i) {
+						None => return Err(CsvError::UnterminatedQuote { row, column }),
+						Some(b'"') if data.get(i + 1) == Some(&b'"') => {
+							field.push(b'"');
+							i += 2;
+						},
+						Some(b'"') => {
+							i += 1;
+							break;
+						},
+						Some(&byte) => {
+							field.push(byte);
+							i += 1;
+						},
+					}
+				}
+				match data.get(i) {
+					None | Some(b'\n') | Some(b'\r') => (),
+					Some(&byte) if byte == delimiter => (),
+					Some(_) => return Err(CsvError::InvalidQuote { row, column }),
+				}
+			}
+			else {
+				while let Some(&byte) = data.get(i) {
+					if byte == delimiter || byte == b'\n' || byte == b'\r' {
+						break;
+					}
+					if byte == b'"' {
+						return Err(CsvError::InvalidQuote { row, column });
+					}
+					field.push(byte);
+					i += 1;
+				}
+			}
+			fields.push(field);
+			match data.get(i) {
+				Some(&byte) if byte == delimiter => i += 1,
+				Some(b'\r') if data.get(i + 1) == Some(&b'\n') => {
+					i += 2;
+					break;
+				},
+				Some(_) => {
+					i += 1;
+					break;
+				},
+				None => break,
+			}
+		}
+		if !(fields.len() == 1 && fields[0].is_empty()) {
+			records.push((row, fields));
+		}
+	}
+	Ok(records)
+}
+
+#[test]
+fn test_load_csv() {
+	let load = |csv: &str| load_csv(csv.as_bytes(), CsvOptions::default());
+	let entries = load("a,1\n\"b,c\",\"2,3\"\r\n\"multi\nline\",\"say \"\"hi\"\"\"\n\nd,,extra\n").unwrap();
+	assert_eq!(entries.keys, ["a", "b,c", "multi\nline", "d"]);
+	assert_eq!(entries.values, ["1", "2,3", "say \"hi\"", ""]);
+	assert_eq!(load("").unwrap(), OwnedEntries::default());
+
+	let options = CsvOptions { delimiter: b'\t', has_header: true, key_column: 1, value_column: 0 };
+	let entries = load_csv("value\tkey\n1\ta,b\n".as_bytes(), options).unwrap();
+	assert_eq!((entries.keys, entries.values), (vec![String::from("a,b")], vec![String::from("1")]));
+	// The header is the first record after the blank rows
+	let entries = load_csv("\n\r\nkey\tvalue\na\t1\n".as_bytes(), CsvOptions { delimiter: b'\t', has_header: true, ..CsvOptions::default() }).unwrap();
+	assert_eq!((entries.keys, entries.values), (vec![String::from("a")], vec![String::from("1")]));
+
+	// The rows count the blank row and the second line of the quoted field belongs to its row
+	assert!(matches!(load("a,1\n\n\"b\nb\",2\na,3\n"), Err(CsvError::DuplicateKey { key, rows: (1, 4) }) if key == "a"));
+	assert!(matches!(load("a,1\nb,\"2\n"), Err(CsvError::UnterminatedQuote { row: 2, column: 2 })));
+	assert!(matches!(load("a,1\nb,\"2\"x\n"), Err(CsvError::InvalidQuote { row: 2, column: 2 })));
+	assert!(matches!(load("a,1\nb\"c,2\n"), Err(CsvError::InvalidQuote { row: 2, column: 1 })));
+	assert!(matches!(load("a,1\nb\n"), Err(CsvError::MissingColumn { row: 2, column: 2 })));
+	assert!(matches!(load_csv(&b"a,\xff\n"[..], CsvOptions::default()), Err(CsvError::NotUtf8 { row: 1, column: 2 })));
+	assert_eq!(load("a,1\na,2\n").unwrap_err().to_string(), "key \"a\" appears in rows 1 and 2");
+}
+
+#[test]
+fn test_load_csv_codegen() {
+	let csv = "keyword,token\nfn,Fn\n\"let\",Let\n\"match\",\"Match\"\n";
+	let entries = load_csv(csv.as_bytes(), CsvOptions { has_header: true, ..CsvOptions::default() }).unwrap();
+	let (keys, values) = entries.refs();
+	let options = super::Options {
+		name: "keywords",
+		keys: &keys,
+		values: &values,
+		seeds_len: 1,
+		max_seed: 100000,
+		..super::Options::default()
+	};
+	let code = options.rust().unwrap();
+	assert!(code.contains("\npub mod keywords {\n"));
+	assert!(code.contains("\"match\"") && code.contains("\"Match\""));
+}
@@ -0,0 +1,104 @@
+use super::{CodegenError, Options, Support};
+use super::c::is_ident;
+
+pub fn generate(input: &Options, package: &str) -> Result<String, CodegenError> {
+	if !is_ident(package) {
+		return Err(CodegenError::InvalidName(String::from(package)));
+	}
+	let (keys, seeds, perm) = input.build_backend(Support::default())?;
+	let literals = perm.iter().map(|&i| go_literal(keys[i])).collect::<Vec<String>>();
+	let values = if input.has_values { perm.iter().map(|&i| go_literal(input.values[i].as_bytes())).collect() } else { Vec::new() };
+
+	// Formatted the way gofmt formats it
+	Ok(format_xml::template!(
+		"// Code generated by mphf "{env!("CARGO_PKG_VERSION")}". DO NOT EDIT.\n"
+		{input.provenance("//")}
+		"\n"
+		"package "{package}"\n"
+		"\n"
+		"var seeds = []uint32{" for (i, seed) in (seeds.iter().enumerate()) { if (i != 0) { ", " } {seed} } "}\n"
+		"var keys = []string{" for (i, literal) in (literals.iter().enumerate()) { if (i != 0) { ", " } {literal} } "}\n"
+		if (input.has_values) {
+			"var values = []string{" for (i, value) in (values.iter().enumerate()) { if (i != 0) { ", " } {value} } "}\n"
+		}
+		"\n"
+		"func mix(k uint32) uint32 {\n"
+		"\tk *= 0xcc9e2d51\n"
+		"\tk = k<<15 | k>>17\n"
+		"\treturn k * 0x1b873593\n"
+		"}\n"
+		"\n"
+		"// Same as mphf::hash\n"
+		"func hash(key string, seed uint32) uint32 {\n"
+		"\th := seed\n"
+		"\tn := len(key)\n"
+		"\ti := 0\n"
+		"\tfor ; i+4 <= n; i += 4 {\n"
+		"\t\th ^= mix(uint32(key[i]) | uint32(key[i+1])<<8 | uint32(key[i+2])<<16 | uint32(key[i+3])<<24)\n"
+		"\t\th = h<<13 | h>>19\n"
+		"\t\th = h*5 + 0xe6546b64\n"
+		"\t}\n"
+		"\tif n&3 != 0 {\n"
+		"\t\tk := uint32(key[i])\n"
+		"\t\tif n&3 >= 2 {\n"
+		"\t\t\tk |= uint32(key[i+1]) << 8\n"
+		"\t\t}\n"
+		"\t\tif n&3 == 3 {\n"
+		"\t\t\tk |= uint32(key[i+2]) << 16\n"
+		"\t\t}\n"
+		"\t\th ^= mix(k)\n"
+		"\t}\n"
+		"\th ^= uint32(n)\n"
+		"\th ^= h >> 16\n"
+		"\th *= 0x85ebca6b\n"
+		"\th ^= h >> 13\n"
+		"\th *= 0xc2b2ae35\n"
+		"\th ^= h >> 16\n"
+		"\treturn h\n"
+		"}\n"
+		"\n"
+		"// Index returns the index of the key in keys and values.\n"
+		if (!input.verify_keys) {
+			"// Keys outside the table map to some index.\n"
+		}
+		"func Index(key string) (int, bool) {\n"
+		"\tseed := seeds[hash(key, 0)%uint32(len(seeds))]\n"
+		"\tif seed == 0xffffffff {\n"
+		"\t\treturn 0, false\n"
+		"\t}\n"
+		"\ti := int(hash(key, seed) % uint32(len(keys)))\n"
+		if (input.verify_keys) {
+			"\tif keys[i] != key {\n"
+			"\t\treturn 0, false\n"
+			"\t}\n"
+		}
+		"\treturn i, true\n"
+		"}\n"
+		if (input.has_values) {
+			"\n"
+			"// Value returns the value of the key.\n"
+			"func Value(key string) (string, bool) {\n"
+			"\ti, ok := Index(key)\n"
+			"\tif !ok {\n"
+			"\t\treturn \"\", false\n"
+			"\t}\n"
+			"\treturn values[i], true\n"
+			"}\n"
+		}
+	).to_string())
+}
+
+// Printable ASCII is written as is, other bytes are written as two digit hex escapes
+pub(super) fn go_literal(bytes: &[u8]) -> String {
+	let mut literal = String::from("\"");
+	for &byte in bytes {
+		match byte {
+			b'"' => literal.push_str("\\\""),
+			b'\\' => literal.push_str("\\\\"),
+			0x20..=0x7e => literal.push(byte as char),
+			_ => literal.push_str(&format!("\\x{:02x}", byte)),
+		}
+	}
+	literal.push('"');
+	literal
+}
@@ -0,0 +1,64 @@
+use super::{CodegenError, Options, Support, Value};
+
+// Same version as the JSON of MphfMap, the artifact is a superset of it
+const VERSION: u32 = 1;
+
+pub fn generate(input: &Options) -> Result<String, CodegenError> {
+	let (_, seeds, perm) = input.build_backend(Support { typed_values: true, enums: true, ..Support::default() })?;
+	let literals = perm.iter().map(|&i| json_string(input.keys[i])).collect::<Vec<String>>();
+	let values = match input.typed_values {
+		_ if !input.has_values => Vec::new(),
+		Some(values) => {
+			if let Some(index) = values.iter().position(|value| std::mem::discriminant(value) != std::mem::discriminant(&values[0])) {
+				return Err(CodegenError::MixedValues { index });
+			}
+			perm.iter().map(|&i| json_value(&values[i])).collect::<Result<Vec<String>, CodegenError>>()?
+		},
+		None => perm.iter().map(|&i| json_string(input.values[i])).collect(),
+	};
+
+	Ok(format_xml::template!(
+		"{\n"
+		"  \"version\": "{VERSION}",\n"
+		"  \"name\": "{json_string(input.name)}",\n"
+		"  \"hash\": \"murmur3_32\",\n"
+		"  \"max_seed\": "{input.max_seed}",\n"
+		"  \"values_len\": "{literals.len()}",\n"
+		"  \"seeds\": [" for (i, seed) in (seeds.iter().enumerate()) { if (i != 0) { ", " } {seed} } "],\n"
+		"  \"keys\": [" for (i, literal) in (literals.iter().enumerate()) { if (i != 0) { ", " } {literal} } "]"
+		if (input.has_values) {
+			",\n"
+			"  \"values\": [" for (i, value) in (values.iter().enumerate()) { if (i != 0) { ", " } {value} } "]"
+		}
+		"\n"
+		"}\n"
+	).to_string())
+}
+
+fn json_value(value: &Value) -> Result<String, CodegenError> {
+	match *value {
+		Value::Str(value) => Ok(json_string(value)),
+		Value::U32(value) => Ok(value.to_string()),
+		Value::I64(value) => Ok(value.to_string()),
+		// JSON has no literals for these
+		Value::F64(value) if !value.is_finite() => Err(CodegenError::Unsupported("non-finite float values")),
+		Value::F64(value) => Ok(format!("{:?}", value)),
+	}
+}
+
+// Control characters are escaped, everything else is written as is
+pub(super) fn json_string(s: &str) -> String {
+	let mut literal = String::from("\"");
+	for chr in s.chars() {
+		match chr {
+			'"' => literal.push_str("\\\""),
+			'\\' => literal.push_str("\\\\"),
+			'\n' => literal.push_str("\\n"),
+			'\t' => literal.push_str("\\t"),
+			'\0'..='\u{1f}' | '\u{7f}' => literal.push_str(&format!("\\u{:04x}", chr as u32)),
+			_ => literal.push(chr),
+		}
+	}
+	literal.push('"');
+	literal
+}
@@ -0,0 +1,367 @@
+use std::{fmt, slice};
+use std::path::{Path, PathBuf};
+use std::convert::TryFrom;
+use crate::TomlError;
+use super::{ItemKind, Options};
+
+// Fields of a table which can also be given in the defaults
+const SHARED: &[&str] = &[
+	"key_field", "value_field", "visibility", "crate_path", "value_type", "seeds_len", "max_seed", "item_kind", "standalone", "narrow_seeds",
+	"has_keys", "has_values", "has_index", "has_contains", "has_entry", "verify_keys", "copy_values",
+];
+// Fields only a table can have
+const OWN: &[&str] = &["name", "source", "keys", "values", "emit_enum"];
+
+/// Tables described by a manifest file, requires the `codegen` and `toml` features.
+///
+/// Owns the strings of the tables, borrow them as [`Options`] with [`refs`](Manifest::refs).
+pub struct Manifest {
+	tables: Vec<Table>,
+}
+
+// The strings of a table, the other fields are in its options
+struct Table {
+	options: Options<'static>,
+	name: String,
+	keys: Vec<String>,
+	values: Vec<String>,
+	visibility: Option<String>,
+	crate_path: Option<String>,
+	value_type: Option<String>,
+	emit_enum: Option<String>,
+	source: Option<PathBuf>,
+}
+
+/// The tables of a [`Manifest`] borrowed as the slices [`Options`] takes.
+pub struct ManifestRefs<'a> {
+	tables: &'a [Table],
+	keys: Vec<Vec<&'a str>>,
+	values: Vec<Vec<&'a str>>,
+	sources: Vec<Option<&'a Path>>,
+}
+
+/// Error reading a manifest.
+#[derive(Debug)]
+pub enum ManifestError {
+	/// The manifest is not valid TOML.
+	Parse(toml::de::Error),
+	/// The table has a field the manifest does not know.
+	///
+	/// The table is the name of a table, `defaults` or `manifest` for the top level.
+	UnknownField { table: String, field: String },
+	/// The table is missing a required field.
+	///
+	/// Tables without a name are called by their position, eg. `table[2]`.
+	MissingField { table: String, field: String },
+	/// The field of the table has the wrong type or an invalid value.
+	InvalidField { table: String, field: String },
+	/// Reading the keys and values from the source of the table failed.
+	Source { table: String, error: TomlError },
+}
+
+impl From<toml::de::Error> for ManifestError {
+	fn from(err: toml::de::Error) -> ManifestError {
+		ManifestError::Parse(err)
+	}
+}
+
+impl fmt::Display for ManifestError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			ManifestError::Parse(err) => write!(f, "error parsing manifest: {}", err),
+			ManifestError::UnknownField { table, field } => write!(f, "{}: unknown field {:?}", table, field),
+			ManifestError::MissingField { table, field } => write!(f, "{}: missing field {:?}", table, field),
+			ManifestError::InvalidField { table, field } => write!(f, "{}: invalid value for field {:?}", table, field),
+			ManifestError::Source { table, error } => write!(f, "{}: {}", table, error),
+		}
+	}
+}
+
+impl std::error::Error for ManifestError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			ManifestError::Parse(err) => Some(err),
+			ManifestError::Source { error, .. } => Some(error),
+			_ => None,
+		}
+	}
+}
+
+impl Manifest {
+	/// Reads the tables from a manifest, intended for build scripts.
+	///
+	/// The manifest has an optional `[defaults]` table and a `[[table]]` for every generated table:
+	///
+	/// ```toml
+	/// [defaults]
+	/// max_seed = 100000
+	/// verify_keys = true
+	///
+	/// [[table]]
+	/// name = "colors"
+	/// keys = ["red", "green", "blue"]
+	/// values = ["#f00", "#0f0", "#00f"]
+	/// seeds_len = 1
+	///
+	/// [[table]]
+	/// name = "commands"
+	/// source = "data/commands.toml"
+	/// key_field = "name"
+	/// value_field = "handler"
+	/// seeds_len = 4
+	/// max_seed = 1000000
+	/// ```
+	///
+	/// * `name` and `seeds_len` and `max_seed` are required, the latter two in the table or the defaults.
+	/// * The keys and values are either listed in `keys` and `values`, or read from the `source` file with [`build_from_toml`](crate::build_from_toml)
+	///   from the `key_field` and `value_field` which default to `key` and `value`. The paths are relative to the current directory.
+	/// * The other fields are the [`Options`] of the same name, `item_kind` is `"static"` or `"const"`.
+	///   The values in a table override the defaults, the defaults override the defaults of [`Options`].
+	///
+	/// The source files are read while reading the manifest and set as the `watch_files` of their table.
+	/// A table with a `source` cannot also list `keys` or `values`.
+	pub fn from_toml_str(s: &str) -> Result<Manifest, ManifestError> {
+		let document: toml::Table = s.parse()?;
+		let mut defaults = &toml::Table::new();
+		let mut tables: &[toml::Value] = &[];
+		for (field, value) in &document {
+			match (&**field, value) {
+				("defaults", toml::Value::Table(table)) => defaults = table,
+				("table", toml::Value::Array(array)) => tables = array,
+				("defaults", _) | ("table", _) => return Err(invalid("manifest", field)),
+				_ => return Err(ManifestError::UnknownField { table: String::from("manifest"), field: field.clone() }),
+			}
+		}
+		if let Some(field) = defaults.keys().find(|field| !SHARED.contains(&&***field)) {
+			return Err(ManifestError::UnknownField { table: String::from("defaults"), field: field.clone() });
+		}
+
+		let mut manifest = Manifest { tables: Vec::with_capacity(tables.len()) };
+		for (index, table) in tables.iter().enumerate() {
+			let table = match table {
+				toml::Value::Table(table) => table,
+				_ => return Err(invalid("manifest", "table")),
+			};
+			manifest.tables.push(read_table(table, defaults, index)?);
+		}
+		Ok(manifest)
+	}
+
+	/// Returns the number of tables.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.tables.len()
+	}
+	/// Returns true if the manifest has no tables.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.tables.is_empty()
+	}
+
+	/// Borrows the tables to generate them.
+	///
+	/// ```
+	/// let manifest = mphf::codegen::Manifest::from_toml_str("[[table]]\nname = 'colors'\nkeys = ['red']\nseeds_len = 1\nmax_seed = 1000\n").unwrap();
+	/// let refs = manifest.refs();
+	/// for options in refs.options() {
+	/// 	assert!(options.rust().unwrap().contains("mod colors {"));
+	/// }
+	/// ```
+	pub fn refs(&self) -> ManifestRefs<'_> {
+		fn strs(list: &[String]) -> Vec<&str> {
+			list.iter().map(String::as_str).collect()
+		}
+		ManifestRefs {
+			tables: &self.tables,
+			keys: self.tables.iter().map(|table| strs(&table.keys)).collect(),
+			values: self.tables.iter().map(|table| strs(&table.values)).collect(),
+			sources: self.tables.iter().map(|table| table.source.as_deref()).collect(),
+		}
+	}
+}
+
+impl<'a> ManifestRefs<'a> {
+	/// Returns the options of every table in the order of the manifest.
+	pub fn options(&self) -> impl Iterator<Item = Options<'_>> + '_ {
+		let borrowed = self.keys.iter().zip(&self.values).zip(&self.sources);
+		self.tables.iter().zip(borrowed).map(|(table, ((keys, values), source))| Options {
+			name: &table.name,
+			keys,
+			values,
+			visibility: table.visibility.as_deref().unwrap_or(table.options.visibility),
+			crate_path: table.crate_path.as_deref().unwrap_or(table.options.crate_path),
+			value_type: table.value_type.as_deref().unwrap_or(table.options.value_type),
+			emit_enum: table.emit_enum.as_deref(),
+			watch_files: match source {
+				Some(source) => slice::from_ref(source),
+				None => &[],
+			},
+			..table.options
+		})
+	}
+}
+
+fn read_table(table: &toml::Table, defaults: &toml::Table, index: usize) -> Result<Table, ManifestError> {
+	let name = match table.get("name") {
+		Some(toml::Value::String(name)) => name.clone(),
+		Some(_) => return Err(invalid(&format!("table[{}]", index), "name")),
+		None => return Err(ManifestError::MissingField { table: format!("table[{}]", index), field: String::from("name") }),
+	};
+	if let Some(field) = table.keys().find(|field| !SHARED.contains(&&***field) && !OWN.contains(&&***field)) {
+		return Err(ManifestError::UnknownField { table: name, field: field.clone() });
+	}
+	let get = |field: &str| table.get(field).or_else(|| defaults.get(field));
+	let string = |field: &str| match get(field) {
+		Some(toml::Value::String(s)) => Ok(Some(s.clone())),
+		Some(_) => Err(invalid(&name, field)),
+		None => Ok(None),
+	};
+	let flag = |field: &str, default: bool| match get(field) {
+		Some(&toml::Value::Boolean(flag)) => Ok(flag),
+		Some(_) => Err(invalid(&name, field)),
+		None => Ok(default),
+	};
+	let number = |field: &str| match get(field) {
+		Some(&toml::Value::Integer(number)) if number >= 0 => Ok(number),
+		Some(_) => Err(invalid(&name, field)),
+		None => Err(ManifestError::MissingField { table: name.clone(), field: String::from(field) }),
+	};
+
+	let mut source_path = None;
+	let (keys, values) = match (table.get("source"), table.get("keys")) {
+		(Some(_), _) if table.contains_key("values") => return Err(invalid(&name, "values")),
+		(Some(toml::Value::String(source)), None) => {
+			let key_field = string("key_field")?;
+			let value_field = string("value_field")?;
+			let entries = crate::build_from_toml(Path::new(source), key_field.as_deref().unwrap_or("key"), value_field.as_deref().unwrap_or("value"));
+			source_path = Some(PathBuf::from(source));
+			entries.map_err(|error| ManifestError::Source { table: name.clone(), error })?
+		},
+		(Some(_), None) => return Err(invalid(&name, "source")),
+		(None, Some(keys)) => {
+			let keys = list(keys).ok_or_else(|| invalid(&name, "keys"))?;
+			let values = match table.get("values") {
+				Some(values) => list(values).ok_or_else(|| invalid(&name, "values"))?,
+				None => Vec::new(),
+			};
+			(keys, values)
+		},
+		(Some(_), Some(_)) => return Err(invalid(&name, "keys")),
+		(None, None) => return Err(ManifestError::MissingField { table: name.clone(), field: String::from("keys") }),
+	};
+	let base = Options::default();
+	let item_kind = match string("item_kind")?.as_deref() {
+		Some("static") => ItemKind::Static,
+		Some("const") => ItemKind::Const,
+		Some(_) => return Err(invalid(&name, "item_kind")),
+		None => base.item_kind,
+	};
+	let options = Options {
+		standalone: flag("standalone", base.standalone)?,
+		narrow_seeds: flag("narrow_seeds", base.narrow_seeds)?,
+		item_kind,
+		has_values: flag("has_values", !values.is_empty())?,
+		seeds_len: number("seeds_len")? as usize,
+		max_seed: u32::try_from(number("max_seed")?).map_err(|_| invalid(&name, "max_seed"))?,
+		has_keys: flag("has_keys", base.has_keys)?,
+		has_index: flag("has_index", base.has_index)?,
+		has_contains: flag("has_contains", base.has_contains)?,
+		has_entry: flag("has_entry", base.has_entry)?,
+		verify_keys: flag("verify_keys", base.verify_keys)?,
+		copy_values: flag("copy_values", base.copy_values)?,
+		..base
+	};
+	Ok(Table {
+		options,
+		visibility: string("visibility")?,
+		crate_path: string("crate_path")?,
+		value_type: string("value_type")?,
+		emit_enum: string("emit_enum")?,
+		source: source_path,
+		name,
+		keys,
+		values,
+	})
+}
+
+fn invalid(table: &str, field: &str) -> ManifestError {
+	ManifestError::InvalidField { table: String::from(table), field: String::from(field) }
+}
+
+// Strings are taken as is, numbers and booleans are written as in TOML
+fn list(value: &toml::Value) -> Option<Vec<String>> {
+	value.as_array()?.iter().map(|item| match item {
+		toml::Value::String(s) => Some(s.clone()),
+		toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::Boolean(_) => Some(item.to_string()),
+		_ => None,
+	}).collect()
+}
+
+#[test]
+fn test_manifest() {
+	let source = std::env::temp_dir().join(format!("mphf-test-manifest-{}.toml", std::process::id()));
+	std::fs::write(&source, "[[command]]\nname = \"help\"\nhandler = \"show_help\"\n\n[[command]]\nname = \"list\"\nhandler = \"list_items\"\n").unwrap();
+	let manifest = format!(r##"
+		[defaults]
+		max_seed = 100000
+		verify_keys = true
+		visibility = "pub(crate)"
+
+		[[table]]
+		name = "colors"
+		keys = ["red", "green", "blue"]
+		values = ["#f00", "#0f0", "#00f"]
+		seeds_len = 1
+
+		[[table]]
+		name = "commands"
+		source = {:?}
+		key_field = "name"
+		value_field = "handler"
+		seeds_len = 2
+		max_seed = 1000000
+		visibility = "pub"
+
+		[[table]]
+		name = "ports"
+		keys = ["ftp", "ssh"]
+		values = [21, 22]
+		value_type = "u16"
+		item_kind = "const"
+		seeds_len = 1
+		verify_keys = false
+	"##, source.display().to_string());
+	let manifest = Manifest::from_toml_str(&manifest).unwrap();
+	std::fs::remove_file(&source).unwrap();
+
+	assert_eq!(manifest.len(), 3);
+	let refs = manifest.refs();
+	let tables = refs.options().collect::<Vec<Options>>();
+	assert_eq!((tables[0].name, tables[0].keys, tables[0].seeds_len, tables[0].max_seed), ("colors", &["red", "green", "blue"][..], 1, 100000));
+	assert_eq!((tables[1].name, tables[1].values, tables[1].max_seed, tables[1].visibility), ("commands", &["show_help", "list_items"][..], 1000000, "pub"));
+	assert_eq!((tables[0].watch_files, tables[1].watch_files), (&[][..], &[source.as_path()][..]));
+	assert_eq!((tables[2].values, tables[2].value_type, tables[2].item_kind), (&["21", "22"][..], "u16", ItemKind::Const));
+	assert!(tables[0].verify_keys && tables[1].verify_keys && !tables[2].verify_keys);
+	let code = tables.iter().map(|options| options.rust().unwrap()).collect::<Vec<String>>();
+	assert!(code[0].contains("\npub(crate) mod colors {\n"));
+	assert!(code[1].contains("\npub mod commands {\n"));
+	assert!(code[2].contains("const VALUES: [u16; 2]"));
+
+	let error = |manifest: &str| Manifest::from_toml_str(manifest).err().unwrap();
+	let table = "[[table]]\nname = 'a'\nkeys = ['x']\nseeds_len = 1\nmax_seed = 10\n";
+	assert!(Manifest::from_toml_str(table).is_ok());
+	assert!(matches!(error(&format!("{}colour = 'red'\n", table)), ManifestError::UnknownField { table, field } if table == "a" && field == "colour"));
+	assert!(matches!(error(&format!("[defaults]\nname = 'b'\n{}", table)), ManifestError::UnknownField { table, field } if table == "defaults" && field == "name"));
+	assert!(matches!(error(&format!("tables = []\n{}", table)), ManifestError::UnknownField { table, .. } if table == "manifest"));
+	assert!(matches!(error("[[table]]\nkeys = []\n"), ManifestError::MissingField { table, field } if table == "table[0]" && field == "name"));
+	assert!(matches!(error("[[table]]\nname = 'a'\nkeys = []\nseeds_len = 1\n"), ManifestError::MissingField { table, field } if table == "a" && field == "max_seed"));
+	assert!(matches!(error(&format!("{}[[table]]\nname = 'b'\nseeds_len = 1\nmax_seed = 10\n", table)), ManifestError::MissingField { table, field } if table == "b" && field == "keys"));
+	assert!(matches!(error(&table.replace("max_seed = 10", "max_seed = -1")), ManifestError::InvalidField { field, .. } if field == "max_seed"));
+	assert!(matches!(error(&table.replace("max_seed = 10", "max_seed = 5000000000")), ManifestError::InvalidField { field, .. } if field == "max_seed"));
+	assert!(matches!(error(&format!("{}verify_keys = 'yes'\n", table)), ManifestError::InvalidField { field, .. } if field == "verify_keys"));
+	assert!(matches!(error(&format!("{}item_kind = 'mut'\n", table)), ManifestError::InvalidField { field, .. } if field == "item_kind"));
+	assert!(matches!(error(&table.replace("keys = ['x']", "keys = [['x']]")), ManifestError::InvalidField { field, .. } if field == "keys"));
+	assert!(matches!(error(&table.replace("keys = ['x']", "source = 'missing.toml'")), ManifestError::Source { table, error: TomlError::Io(_) } if table == "a"));
+	assert!(matches!(error(&table.replace("keys = ['x']", "source = 'missing.toml'\nvalues = ['y']")), ManifestError::InvalidField { table, field } if table == "a" && field == "values"));
+	assert!(matches!(error("[[table]\n"), ManifestError::Parse(_)));
+}
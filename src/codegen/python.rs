@@ -0,0 +1,126 @@
+use super::{CodegenError, KeyKind, Options, Support};
+
+// Hashes checked when the module is imported, a port with a missing mask fails right away
+const GOLDEN: [(&[u8], u32); 4] = [(b"", 0), (b"abc", 0), (b"hello world", 0x5082edee), (b"\xff\xff\xff\xff\xff", 0xffffffff)];
+
+pub fn generate(input: &Options) -> Result<String, CodegenError> {
+	let (keys, seeds, perm) = input.build_backend(Support { byte_keys: true, ..Support::default() })?;
+	let key_ty = match input.key_kind {
+		KeyKind::Str => "str",
+		KeyKind::Bytes => "bytes",
+	};
+	let literals = match input.key_kind {
+		KeyKind::Str => perm.iter().map(|&i| str_literal(input.keys[i])).collect::<Vec<String>>(),
+		KeyKind::Bytes => perm.iter().map(|&i| bytes_literal(keys[i])).collect(),
+	};
+	let values = if input.has_values { perm.iter().map(|&i| str_literal(input.values[i])).collect() } else { Vec::new() };
+	// An empty table has nothing to look up and no modulo by zero
+	let empty = keys.is_empty();
+
+	// Python integers do not wrap, every multiplication and left shift is masked back to 32 bits
+	Ok(format_xml::template!(
+		"# @generated by mphf "{env!("CARGO_PKG_VERSION")}"\n"
+		{input.provenance("#")}
+		"from typing import Optional\n"
+		"\n"
+		"SEEDS = [" for (i, seed) in (seeds.iter().enumerate()) { if (i != 0) { ", " } {seed} } "]\n"
+		"KEYS = [" for (i, literal) in (literals.iter().enumerate()) { if (i != 0) { ", " } {literal} } "]\n"
+		if (input.has_values) {
+			"VALUES = [" for (i, value) in (values.iter().enumerate()) { if (i != 0) { ", " } {value} } "]\n"
+		}
+		"LEN = "{literals.len()}"\n"
+		"\n"
+		"\n"
+		"def _mix(k: int) -> int:\n"
+		"    k = (k * 0xcc9e2d51) & 0xffffffff\n"
+		"    k = ((k << 15) | (k >> 17)) & 0xffffffff\n"
+		"    return (k * 0x1b873593) & 0xffffffff\n"
+		"\n"
+		"\n"
+		"def _hash(key: bytes, seed: int) -> int:\n"
+		"    \"\"\"Same as mphf::hash.\"\"\"\n"
+		"    h = seed\n"
+		"    n = len(key)\n"
+		"    i = 0\n"
+		"    while i + 4 <= n:\n"
+		"        h ^= _mix(int.from_bytes(key[i:i + 4], \"little\"))\n"
+		"        h = ((h << 13) | (h >> 19)) & 0xffffffff\n"
+		"        h = (h * 5 + 0xe6546b64) & 0xffffffff\n"
+		"        i += 4\n"
+		"    if n & 3:\n"
+		"        h ^= _mix(int.from_bytes(key[i:], \"little\"))\n"
+		"    h ^= n & 0xffffffff\n"
+		"    h ^= h >> 16\n"
+		"    h = (h * 0x85ebca6b) & 0xffffffff\n"
+		"    h ^= h >> 13\n"
+		"    h = (h * 0xc2b2ae35) & 0xffffffff\n"
+		"    h ^= h >> 16\n"
+		"    return h\n"
+		"\n"
+		"\n"
+		for &(key, seed) in (GOLDEN.iter()) {
+			"assert _hash("{bytes_literal(key)}", "{seed}") == "{format_args!("{:#010x}", crate::hash(key, seed))}"\n"
+		}
+		"\n"
+		"\n"
+		"def index(key: "{key_ty}") -> Optional[int]:\n"
+		if (empty) {
+			"    return None\n"
+		}
+		else {
+			if (input.key_kind == KeyKind::Str) {
+				"    data = key.encode(\"utf-8\")\n"
+			}
+			else {
+				"    data = key\n"
+			}
+			"    seed = SEEDS[_hash(data, 0) % len(SEEDS)]\n"
+			"    if seed == 0xffffffff:\n"
+			"        return None\n"
+			"    i = _hash(data, seed) % LEN\n"
+			if (input.verify_keys) {
+				"    if KEYS[i] != key:\n"
+				"        return None\n"
+			}
+			"    return i\n"
+		}
+		if (input.has_values) {
+			"\n"
+			"\n"
+			"def value(key: "{key_ty}") -> Optional[str]:\n"
+			"    i = index(key)\n"
+			"    return None if i is None else VALUES[i]\n"
+		}
+	).to_string())
+}
+
+// Printable ASCII is written as is, everything else as escapes so the module is plain ASCII
+pub(super) fn str_literal(s: &str) -> String {
+	let mut literal = String::from("\"");
+	for chr in s.chars() {
+		match chr {
+			'"' => literal.push_str("\\\""),
+			'\\' => literal.push_str("\\\\"),
+			' '..='~' => literal.push(chr),
+			'\0'..='\u{ff}' => literal.push_str(&format!("\\x{:02x}", chr as u32)),
+			'\u{100}'..='\u{ffff}' => literal.push_str(&format!("\\u{:04x}", chr as u32)),
+			_ => literal.push_str(&format!("\\U{:08x}", chr as u32)),
+		}
+	}
+	literal.push('"');
+	literal
+}
+
+pub(super) fn bytes_literal(bytes: &[u8]) -> String {
+	let mut literal = String::from("b\"");
+	for &byte in bytes {
+		match byte {
+			b'"' => literal.push_str("\\\""),
+			b'\\' => literal.push_str("\\\\"),
+			0x20..=0x7e => literal.push(byte as char),
+			_ => literal.push_str(&format!("\\x{:02x}", byte)),
+		}
+	}
+	literal.push('"');
+	literal
+}
@@ -1,36 +1,457 @@
-use super::Options;
-
-pub fn generate(input: &Options) -> String {
-	let seeds = crate::build(input.keys, input.seeds_len, input.max_seed).unwrap();
-	let mut keys = input.keys.to_vec();
-	let mut values = input.values.to_vec();
-	crate::reorder(&mut keys, &seeds, Some(&mut values)).unwrap();
-
-	format_xml::template!(
-		"pub mod "{input.name}" {\n"
-		"\tpub static SEEDS: [u32; "{seeds.len()}"] = [" for &seed in (&seeds) { {seed}"," } "];\n"
-		"\tpub static KEYS: [&str; "{keys.len()}"] = [" for &key in (&keys) { "\""{key}"\"," } "];\n"
-		"\tpub static VALUES: [&str; "{values.len()}"] = [" for &value in (&values) { "\""{value}"\"," } "];\n"
+use std::{ascii, mem, collections::HashMap};
+use super::{CodegenError, CodegenFormat, Indent, ItemKind, KeyKind, KeyNormalizer, Options, RustEdition, Value};
+
+// The visibility of the module and of the items inside it, which must reach one level further out
+pub(super) fn nested_visibility(visibility: &str) -> Option<(&'static str, &'static str)> {
+	match visibility {
+		"" | "pub(self)" => Some(("", "pub(super)")),
+		"pub" => Some(("pub ", "pub")),
+		"pub(crate)" => Some(("pub(crate) ", "pub(crate)")),
+		"pub(super)" => Some(("pub(super) ", "pub(in super::super)")),
+		_ => None,
+	}
+}
+
+pub fn generate(input: &Options) -> Result<String, CodegenError> {
+	let normalized = normalize_keys(input)?;
+	let str_key = |i: usize| normalized.as_ref().map_or(input.keys[i], |normalized| &normalized[i]);
+	let keys = match input.key_kind {
+		KeyKind::Str => (0..input.keys.len()).map(|i| str_key(i).as_bytes()).collect::<Vec<&[u8]>>(),
+		KeyKind::Bytes => input.byte_keys.to_vec(),
+	};
+	if !super::c::is_ident(input.name) {
+		return Err(CodegenError::InvalidName(String::from(input.name)));
+	}
+	let (mod_vis, vis) = nested_visibility(input.visibility).ok_or_else(|| CodegenError::InvalidVisibility(String::from(input.visibility)))?;
+	if !is_path(input.crate_path) {
+		return Err(CodegenError::InvalidCratePath(String::from(input.crate_path)));
+	}
+	let krate = if input.standalone { "self::mphf" } else { input.crate_path };
+	// Only the accessors decide which arrays are emitted, the index and len need neither
+	// The keys are needed to tell the keys apart from other inputs mapping to the same index
+	let emit_keys = input.has_keys || input.has_contains || input.has_entry || input.verify_keys || input.emit_enum.is_some();
+	let emit_values = input.has_values || input.has_entry || input.has_unchecked;
+	let values_len = input.typed_values.map_or(input.values.len(), |values| values.len());
+	if emit_values && keys.len() != values_len {
+		return Err(CodegenError::LengthMismatch { keys: keys.len(), values: values_len });
+	}
+	if let (true, Some(values)) = (emit_values, input.typed_values) {
+		if let Some(index) = values.iter().position(|value| mem::discriminant(value) != mem::discriminant(&values[0])) {
+			return Err(CodegenError::MixedValues { index });
+		}
+	}
+	// A string hashes the same as its bytes
+	let seeds = crate::build_bytes(&keys, input.seeds_len, input.max_seed).map_err(|err| input.build_error(err))?;
+	let mut perm = vec![0; keys.len()];
+	for (i, &key) in keys.iter().enumerate() {
+		// The seeds were built over the keys
+		perm[crate::index_bytes(key, &seeds, keys.len()).unwrap()] = i;
+	}
+
+	// String keys and values are written with Debug formatting which escapes them as Rust string literals, other values are written verbatim
+	let literals = perm.iter().map(|&i| match input.key_kind {
+		KeyKind::Str => format!("{:?}", str_key(i)),
+		KeyKind::Bytes => byte_literal(input.byte_keys[i]),
+	}).collect::<Vec<String>>();
+	let (values, value_ty, static_value_ty, raw_values) = match input.typed_values {
+		_ if !emit_values => (Vec::new(), "&str", "&'static str", false),
+		Some(values) => {
+			let literals = perm.iter().map(|&i| value_literal(&values[i])).collect::<Vec<String>>();
+			let (value_ty, static_value_ty) = match values.first() {
+				Some(Value::Str(_)) | None => ("&str", "&'static str"),
+				Some(Value::U32(_)) => ("u32", "u32"),
+				Some(Value::I64(_)) => ("i64", "i64"),
+				Some(Value::F64(_)) => ("f64", "f64"),
+			};
+			(literals, value_ty, static_value_ty, false)
+		},
+		None if input.value_type.is_empty() || input.value_type == "&str" => {
+			(perm.iter().map(|&i| format!("{:?}", input.values[i])).collect(), "&str", "&'static str", false)
+		},
+		None => (perm.iter().map(|&i| String::from(input.values[i])).collect(), input.value_type, input.value_type, true),
+	};
+	let string_values = value_ty == "&str";
+	let (key_ty, static_key_ty, get, index) = match input.key_kind {
+		KeyKind::Str => ("&str", "&'static str", "get", "index"),
+		KeyKind::Bytes => ("&[u8]", "&'static [u8]", "get_bytes", "index_bytes"),
+	};
+	let captures = if input.rust_edition == RustEdition::Edition2024 { " + use<>" } else { "" };
+	// Narrow seeds reserve the max value of their type for empty buckets and are looked up by the generated code
+	let seed_ty = if input.narrow_seeds && !literals.is_empty() { seed_type(&seeds) } else { "u32" };
+	let seed_literals = seeds.iter().map(|&seed| match (seed, seed_ty) {
+		(u32::MAX, "u8") => u8::MAX.to_string(),
+		(u32::MAX, "u16") => u16::MAX.to_string(),
+		(seed, _) => seed.to_string(),
+	}).collect::<Vec<String>>();
+	let lookup = if seed_ty == "u32" || !(input.has_keys || input.has_values || input.has_index || input.has_contains || input.has_entry || input.has_unchecked || input.emit_enum.is_some()) {
+		None
+	}
+	else {
+		Some(if input.key_kind == KeyKind::Str { "lookup(key.as_bytes())" } else { "lookup(key)" })
+	};
+	let raw_index = match lookup {
+		None => format!("{}::{}(key, &SEEDS, {})", krate, index, literals.len()),
+		Some(lookup) => String::from(lookup),
+	};
+	// The lookup key is normalized before anything else, shadowing the argument
+	let normalize = match input.key_normalizer {
+		None => String::new(),
+		Some(KeyNormalizer::Trim) => String::from("let key = key.trim(); "),
+		Some(KeyNormalizer::AsciiLowercase) => String::from("let normalized = key.to_ascii_lowercase(); let key: &str = &normalized; "),
+		Some(KeyNormalizer::Lowercase) => String::from("let normalized = key.to_lowercase(); let key: &str = &normalized; "),
+		Some(KeyNormalizer::Custom { path, .. }) => format!("let normalized = {}(key); let key: &str = &normalized; ", path),
+	};
+	// Every key maps to some index, only comparing the key stored there rejects other keys
+	let verified = |item: &str| format!("match {} {{ Some(i) if KEYS[i] == key => Some({}), _ => None }}", raw_index, item);
+	let key_body = match (input.verify_keys, lookup) {
+		(true, _) => verified("KEYS[i]"),
+		(false, None) => format!("{}::{}(key, &SEEDS, &KEYS).copied()", krate, get),
+		(false, Some(lookup)) => format!("{}.map(|i| KEYS[i])", lookup),
+	};
+	let value_body = match (input.verify_keys, lookup, input.copy_values) {
+		(true, _, true) => verified("VALUES[i]"),
+		(true, _, false) => verified("&VALUES[i]"),
+		(false, None, true) => format!("{}::{}(key, &SEEDS, &VALUES).copied()", krate, get),
+		(false, None, false) => format!("{}::{}(key, &SEEDS, &VALUES)", krate, get),
+		(false, Some(lookup), true) => format!("{}.map(|i| VALUES[i])", lookup),
+		(false, Some(lookup), false) => format!("{}.map(|i| &VALUES[i])", lookup),
+	};
+	// Keys in the table never land in an empty bucket, skipping that check is the whole point
+	// Copied references are returned through auto-deref, an explicit deref trips clippy in the user's crate
+	let deref = input.copy_values && !static_value_ty.starts_with('&');
+	let unchecked_body = format!("unsafe {{ let i = {}.unwrap_unchecked(); {}VALUES.get_unchecked(i) }}", raw_index, if deref { "*" } else { "" });
+	let index_body = if input.verify_keys { verified("i") } else { raw_index.clone() };
+	let contains_body = format!("matches!({}, Some(i) if KEYS[i] == key)", raw_index);
+	let getter_ty = if input.copy_values { String::from(static_value_ty) } else { format!("&'static {}", static_value_ty) };
+	// The entries pair the keys with their values, string values are always copied
+	let (entry_ty, entry_value) = if input.copy_values || string_values { (String::from(static_value_ty), "VALUES[i]") } else { (getter_ty.clone(), "&VALUES[i]") };
+	let entry_body = verified(&format!("(KEYS[i], {})", entry_value));
+	let [key_body, value_body, index_body, contains_body, unchecked_body, entry_body] = [key_body, value_body, index_body, contains_body, unchecked_body, entry_body]
+		.map(|body| format!("{}{}", normalize, body));
+	let variants = match input.emit_enum {
+		Some(name) => Some(variants(input, name, &perm)?),
+		None => None,
+	};
+	let variant_literals = variants.iter().flat_map(|(name, variants)| variants.iter().map(move |variant| format!("{}::{}", name, variant))).collect::<Vec<String>>();
+	let array = |elems: &[String]| array(&input.format, elems);
+	// The template is written with a tab per level
+	let (i1, i2) = (input.format.indent.level(1), input.format.indent.level(2));
+	// Without verification every key maps to some entry, the docs of the lookups say so
+	let outside = if input.verify_keys { "returns `None` for keys outside the table" } else { "keys outside the table map to an arbitrary entry or `None`" };
+	// Rendered on its own, the template of the whole module exceeds the recursion limit
+	let enum_items = match &variants {
+		None => String::new(),
+		Some((name, variants)) => format_xml::template!(
+			{i1}"/// A variant per key, the discriminant is the index of the key.\n"
+			{i1}"#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]\n"
+			{i1}{vis}" enum "{name}" {\n"
+			for (i, variant) in (variants.iter().enumerate()) {
+				{i2}"/// The key "{literals[i]}".\n"
+				{i2}{variant}" = "{i}",\n"
+			}
+			{i1}"}\n"
+			{i1}"const VARIANTS: ["{name}"; "{variants.len()}"] = "{array(&variant_literals)}";\n"
+			{i1}"impl "{name}" {\n"
+			{i2}"/// Returns the key of the variant.\n"
+			{i2}"#[inline] "{vis}" fn as_str(&self) -> &'static str { KEYS[*self as usize] }\n"
+			{i1}"}\n"
+			{i1}"impl core::str::FromStr for "{name}" {\n"
+			{i2}"type Err = ();\n"
+			{i2}"#[inline] fn from_str(key: &str) -> Result<"{name}", ()> { "{normalize}"match "{raw_index}" { Some(i) if KEYS[i] == key => Ok(VARIANTS[i]), _ => Err(()) } }\n"
+			{i1}"}\n"
+			{i1}"impl From<"{name}"> for usize {\n"
+			{i2}"#[inline] fn from(value: "{name}") -> usize { value as usize }\n"
+			{i1}"}\n"
+		).to_string(),
+	};
+	let mut module_attrs = input.doc.iter().flat_map(|doc| doc.lines()).map(str::trim_end)
+		.map(|line| if line.is_empty() { String::from("///\n") } else { format!("/// {}\n", line) })
+		.collect::<String>();
+	if input.allow_missing_docs {
+		module_attrs.push_str("#[allow(missing_docs)]\n");
+	}
+	if input.doc_hidden {
+		module_attrs.push_str("#[doc(hidden)]\n");
+	}
+	let footer = match input.footer {
+		Some(footer) if !footer.is_empty() && !footer.ends_with('\n') => format!("{}\n", footer),
+		footer => String::from(footer.unwrap_or("")),
+	};
+	let item = match input.item_kind {
+		ItemKind::Static => "static",
+		ItemKind::Const => "const",
+	};
+
+	Ok(format_xml::template!(
+		"// @generated by mphf "{env!("CARGO_PKG_VERSION")}"\n"
+		{input.provenance("//")}
+		{module_attrs}
+		{mod_vis}"mod "{input.name}" {\n"
+		// Unused parts of a private table are not worth a warning
+		if (input.visibility != "pub") {
+			{i1}"#![allow(dead_code)]\n"
+		}
+		if (raw_values) {
+			{i1}"#[allow(unused_imports)]\n"
+			{i1}"use super::*;\n"
+		}
+		if (input.standalone) {
+			{i1}"// Lookup functions inlined from mphf "{env!("CARGO_PKG_VERSION")}", equivalent to mphf::index and mphf::get\n"
+			{reindent(STANDALONE, input.format.indent)}
+		}
+		{i1}"/// The seeds of the buckets, empty buckets have the max value.\n"
+		{i1}{vis}" "{item}" SEEDS: ["{seed_ty}"; "{seeds.len()}"] = "{array(&seed_literals)}";\n"
+		if (emit_keys) {
+			{i1}"/// The keys in mphf order.\n"
+			{i1}{vis}" "{item}" KEYS: ["{key_ty}"; "{literals.len()}"] = "{array(&literals)}";\n"
+		}
+		if (emit_values) {
+			{i1}"/// The values in the order of their keys.\n"
+			{i1}{vis}" "{item}" VALUES: ["{value_ty}"; "{values.len()}"] = "{array(&values)}";\n"
+		}
+		{i1}"/// The number of keys.\n"
+		{i1}{vis}" const LEN: usize = "{literals.len()}";\n"
+		if (lookup.is_some()) {
+			{i1}"#[inline] fn lookup(key: &[u8]) -> Option<usize> {\n"
+			{i2}"let seed = SEEDS["{krate}"::hash(key, 0) as usize % "{seeds.len()}"];\n"
+			{i2}"if seed == "{seed_ty}"::MAX { return None; }\n"
+			{i2}"Some("{krate}"::hash(key, seed as u32) as usize % "{literals.len()}")\n"
+			{i1}"}\n"
+		}
 		if (input.has_keys) {
-			"\t#[inline] pub fn key(key: &str) -> Option<&'static str> { ::mphf::get(key, &SEEDS, &VALUES).copied() }\n"
-			"\t#[inline] pub fn keys() -> impl Iterator<Item = &'static str> { KEYS.iter().copied() }\n"
+			{i1}"/// Returns the key stored at the index of the key, "{outside}".\n"
+			{i1}"#[inline] "{vis}" fn key(key: "{key_ty}") -> Option<"{static_key_ty}"> { "{key_body}" }\n"
+			{i1}"/// Iterates over the keys in mphf order.\n"
+			{i1}"#[inline] "{vis}" fn keys() -> impl Iterator<Item = "{static_key_ty}">"{captures}" { KEYS.iter().copied() }\n"
 		}
 		if (input.has_values) {
 			if (input.copy_values) {
-				"\t#[inline] pub fn value(key: &str) -> Option<&'static str> { ::mphf::get(key, &SEEDS, &VALUES).copied() }\n"
-				"\t#[inline] pub fn values() -> impl Iterator<Item = &'static str> { VALUES.iter().copied() }\n"
+				{i1}"/// Returns the value of the key, "{outside}".\n"
+				{i1}"#[inline] "{vis}" fn value(key: "{key_ty}") -> Option<"{getter_ty}"> { "{value_body}" }\n"
+				{i1}"/// Iterates over the values in mphf order.\n"
+				{i1}"#[inline] "{vis}" fn values() -> impl Iterator<Item = "{getter_ty}">"{captures}" { VALUES.iter().copied() }\n"
 			}
 			else {
-				"\t#[inline] pub fn value(key: &str) -> Option<&'static &'static str> { ::mphf::get(key, &SEEDS, &VALUES) }\n"
-				"\t#[inline] pub fn values() -> impl Iterator<Item = &'static &'static str> { VALUES.iter() }\n"
+				{i1}"/// Returns the value of the key, "{outside}".\n"
+				{i1}"#[inline] "{vis}" fn value(key: "{key_ty}") -> Option<"{getter_ty}"> { "{value_body}" }\n"
+				{i1}"/// Iterates over the values in mphf order.\n"
+				{i1}"#[inline] "{vis}" fn values() -> impl Iterator<Item = "{getter_ty}">"{captures}" { VALUES.iter() }\n"
 			}
 		}
+		if (input.has_unchecked) {
+			{i1}"/// Returns the value of the key without comparing the key or checking for an empty bucket.\n"
+			{i1}"///\n"
+			{i1}"/// # Safety\n"
+			{i1}"///\n"
+			{i1}"/// The key must be in the table.\n"
+			{i1}"#[inline] "{vis}" unsafe fn value_unchecked(key: "{key_ty}") -> "{getter_ty}" { "{unchecked_body}" }\n"
+		}
 		if (input.has_index) {
-			"\t#[inline] pub fn index(key: &str) -> Option<usize> { ::mphf::index(key, &SEEDS, VALUES.len()) }\n"
+			{i1}"/// Returns the index of the key in the arrays, "{outside}".\n"
+			{i1}"#[inline] "{vis}" fn index(key: "{key_ty}") -> Option<usize> { "{index_body}" }\n"
+		}
+		if (input.has_contains) {
+			{i1}"/// Returns true if the key is in the table.\n"
+			{i1}"#[inline] "{vis}" fn contains_key(key: "{key_ty}") -> bool { "{contains_body}" }\n"
 		}
+		{i1}"/// Returns the number of keys.\n"
+		{i1}"#[inline] "{vis}" const fn len() -> usize { LEN }\n"
+		{i1}"/// Returns true if the table has no keys.\n"
+		{i1}"#[inline] "{vis}" const fn is_empty() -> bool { LEN == 0 }\n"
+		if (input.has_entry) {
+			{i1}"/// Returns the stored key and the value of the key, returns `None` for keys outside the table.\n"
+			{i1}"#[inline] "{vis}" fn entry(key: "{key_ty}") -> Option<("{static_key_ty}", "{entry_ty}")> { "{entry_body}" }\n"
+		}
+		{enum_items}
 		if (input.has_keys && input.has_values) {
-			"\t#[inline] pub fn iter() -> impl Iterator<Item = (&'static str, &'static str)> { (0.."{keys.len()}").map(|i| (KEYS[i], VALUES[i])) }\n"
+			{i1}"/// Iterates over the keys and their values in mphf order.\n"
+			{i1}"#[inline] "{vis}" fn iter() -> impl Iterator<Item = ("{static_key_ty}", "{entry_ty}")>"{captures}" { (0.."{literals.len()}").map(|i| (KEYS[i], "{entry_value}")) }\n"
 		}
 		"}\n"
-	).to_string()
+		{footer}
+	).to_string())
+}
+
+// Copy of the murmur3 hash and the lookup functions, the tests check it is equivalent to the library
+const STANDALONE: &str = "	mod mphf {
+		#![allow(dead_code)]
+		const fn fmix32(mut h: u32) -> u32 {
+			h ^= h >> 16;
+			h = h.wrapping_mul(0x85ebca6b);
+			h ^= h >> 13;
+			h = h.wrapping_mul(0xc2b2ae35);
+			h ^= h >> 16;
+			h
+		}
+		pub const fn hash(s: &[u8], seed: u32) -> u32 {
+			const C1: u32 = 0xcc9e2d51;
+			const C2: u32 = 0x1b873593;
+			let mut h = seed;
+			let mut i = 0;
+			while i < s.len() & !3 {
+				let k = u32::from_le_bytes([s[i], s[i + 1], s[i + 2], s[i + 3]]);
+				h ^= k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+				h = h.rotate_left(13).wrapping_mul(5).wrapping_add(0xe6546b64);
+				i += 4;
+			}
+			if s.len() & 3 != 0 {
+				let k = match s.len() & 3 {
+					3 => u32::from_le_bytes([s[i], s[i + 1], s[i + 2], 0]),
+					2 => u32::from_le_bytes([s[i], s[i + 1], 0, 0]),
+					_ => u32::from_le_bytes([s[i], 0, 0, 0]),
+				};
+				h ^= k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+			}
+			fmix32(h ^ s.len() as u32)
+		}
+		#[inline]
+		pub fn index_bytes(key: &[u8], seeds: &[u32], values_len: usize) -> Option<usize> {
+			if seeds.is_empty() || values_len == 0 {
+				return None;
+			}
+			let seed = seeds[hash(key, 0) as usize % seeds.len()];
+			if seed == u32::MAX {
+				return None;
+			}
+			Some(hash(key, seed) as usize % values_len)
+		}
+		#[inline]
+		pub fn index(key: &str, seeds: &[u32], values_len: usize) -> Option<usize> {
+			index_bytes(key.as_bytes(), seeds, values_len)
+		}
+		#[inline]
+		pub fn get_bytes<'a, T>(key: &[u8], seeds: &[u32], values: &'a [T]) -> Option<&'a T> {
+			values.get(index_bytes(key, seeds, values.len())?)
+		}
+		#[inline]
+		pub fn get<'a, T>(key: &str, seeds: &[u32], values: &'a [T]) -> Option<&'a T> {
+			get_bytes(key.as_bytes(), seeds, values)
+		}
+	}
+";
+
+// Narrowest type which fits the seeds with its max value free to mark empty buckets
+pub(super) fn seed_type(seeds: &[u32]) -> &'static str {
+	let max = seeds.iter().copied().filter(|&seed| seed != u32::MAX).max().unwrap_or(0);
+	if max < u8::MAX as u32 { "u8" } else if max < u16::MAX as u32 { "u16" } else { "u32" }
+}
+
+// The enum name and the variant names of the keys in mphf order
+fn variants<'a>(input: &Options, name: &'a str, perm: &[usize]) -> Result<(&'a str, Vec<String>), CodegenError> {
+	if !is_path(name) || name.contains("::") {
+		return Err(CodegenError::InvalidEnumName(String::from(name)));
+	}
+	if input.key_kind != KeyKind::Str {
+		return Err(CodegenError::EnumByteKeys);
+	}
+	let mut variants = Vec::with_capacity(perm.len());
+	let mut seen = HashMap::new();
+	for &i in perm {
+		let key = input.keys[i];
+		let variant = variant_name(key).ok_or_else(|| CodegenError::InvalidVariant { key: String::from(key) })?;
+		if let Some(&other) = seen.get(&variant) {
+			return Err(CodegenError::DuplicateVariant { variant, keys: (String::from(other), String::from(key)) });
+		}
+		seen.insert(variant.clone(), key);
+		variants.push(variant);
+	}
+	Ok((name, variants))
+}
+
+// The keys as hashed and stored in the table, the generated lookups normalize their key the same way
+fn normalize_keys(input: &Options) -> Result<Option<Vec<String>>, CodegenError> {
+	let normalizer = match input.key_normalizer {
+		Some(normalizer) => normalizer,
+		None => return Ok(None),
+	};
+	if input.key_kind != KeyKind::Str {
+		return Err(CodegenError::NormalizerByteKeys);
+	}
+	if let KeyNormalizer::Custom { path, .. } = normalizer {
+		if !is_path(path) {
+			return Err(CodegenError::InvalidNormalizerPath(String::from(path)));
+		}
+	}
+	let mut normalized = Vec::with_capacity(input.keys.len());
+	let mut seen = HashMap::new();
+	for &key in input.keys {
+		let normal = normalizer.normalize(key);
+		if let Some(&other) = seen.get(&normal) {
+			return Err(CodegenError::NormalizedDuplicate { normalized: normal, keys: (String::from(other), String::from(key)) });
+		}
+		seen.insert(normal.clone(), key);
+		normalized.push(normal);
+	}
+	Ok(Some(normalized))
+}
+
+// Capitalizes the ASCII alphanumeric parts of the key
+fn variant_name(key: &str) -> Option<String> {
+	let mut variant = String::new();
+	for part in key.split(|c: char| !c.is_ascii_alphanumeric()).filter(|part| !part.is_empty()) {
+		variant.push(part.as_bytes()[0].to_ascii_uppercase() as char);
+		variant.push_str(&part[1..]);
+	}
+	match variant.as_bytes().first() {
+		Some(c) if c.is_ascii_alphabetic() && variant != "Self" => Some(variant),
+		_ => None,
+	}
+}
+
+// Identifiers separated by `::`, optionally starting with `::`
+fn is_path(path: &str) -> bool {
+	let path = path.strip_prefix("::").unwrap_or(path);
+	path.split("::").all(|segment| {
+		let ident = segment.strip_prefix("r#").unwrap_or(segment);
+		let mut chars = ident.chars();
+		matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_') && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+	})
+}
+
+// Written without a suffix, the type of the array gives the literals their type
+fn value_literal(value: &Value) -> String {
+	match *value {
+		Value::Str(value) => format!("{:?}", value),
+		Value::U32(value) => value.to_string(),
+		Value::I64(value) => value.to_string(),
+		Value::F64(value) if value.is_nan() => String::from("f64::NAN"),
+		Value::F64(value) if value == f64::INFINITY => String::from("f64::INFINITY"),
+		Value::F64(value) if value == f64::NEG_INFINITY => String::from("f64::NEG_INFINITY"),
+		// Debug formatting always includes a decimal point or an exponent
+		Value::F64(value) => format!("{:?}", value),
+	}
+}
+
+// Printable ASCII is written as is, other bytes are hex escaped
+fn byte_literal(bytes: &[u8]) -> String {
+	let mut literal = String::from("b\"");
+	for &byte in bytes {
+		literal.extend(ascii::escape_default(byte).map(char::from));
+	}
+	literal.push('"');
+	literal
+}
+
+// Replaces the leading tabs of every line by the indentation of as many levels
+fn reindent(code: &str, indent: Indent) -> String {
+	code.lines().map(|line| {
+		let code = line.trim_start_matches('\t');
+		format!("{}{}\n", indent.level(line.len() - code.len()), code)
+	}).collect()
+}
+
+// Every element is followed by a comma, the lines of the elements have no trailing whitespace
+fn array(format: &CodegenFormat, elems: &[String]) -> String {
+	if format.elems_per_line == 0 || elems.is_empty() {
+		return format!("[{}]", elems.iter().map(|elem| format!("{},", elem)).collect::<String>());
+	}
+	let mut array = String::from("[\n");
+	for line in elems.chunks(format.elems_per_line) {
+		array.push_str(&format.indent.level(2));
+		array.push_str(&line.join(", "));
+		array.push_str(",\n");
+	}
+	array.push_str(&format.indent.level(1));
+	array.push(']');
+	array
 }
@@ -0,0 +1,93 @@
+use super::{CodegenError, Options, Support};
+
+pub fn generate(input: &Options) -> Result<String, CodegenError> {
+	let (keys, seeds, perm) = input.build_backend(Support::default())?;
+	let literals = perm.iter().map(|&i| ts_literal(input.keys[i])).collect::<Vec<String>>();
+	let values = if input.has_values { perm.iter().map(|&i| ts_literal(input.values[i])).collect() } else { Vec::new() };
+	// An empty table has nothing to look up and no modulo by zero
+	let empty = keys.is_empty();
+
+	// The hash works on the int32 values of the bitwise operators, Math.imul multiplies them with wrapping and >>> 0 makes them unsigned
+	Ok(format_xml::template!(
+		"// @generated by mphf "{env!("CARGO_PKG_VERSION")}"\n"
+		{input.provenance("//")}
+		"\n"
+		"const SEEDS = new Uint32Array([" for (i, seed) in (seeds.iter().enumerate()) { if (i != 0) { ", " } {seed} } "]);\n"
+		"export const LEN = "{literals.len()}";\n"
+		"export const KEYS: readonly string[] = [" for (i, literal) in (literals.iter().enumerate()) { if (i != 0) { ", " } {literal} } "];\n"
+		if (input.has_values) {
+			"export const VALUES: readonly string[] = [" for (i, value) in (values.iter().enumerate()) { if (i != 0) { ", " } {value} } "];\n"
+		}
+		"\n"
+		"const encoder = new TextEncoder();\n"
+		"\n"
+		"function mix(k: number): number {\n"
+		"\tk = Math.imul(k, 0xcc9e2d51);\n"
+		"\tk = k << 15 | k >>> 17;\n"
+		"\treturn Math.imul(k, 0x1b873593);\n"
+		"}\n"
+		"\n"
+		"// Same as mphf::hash, the keys are hashed as UTF-8\n"
+		"export function hash(bytes: Uint8Array, seed: number): number {\n"
+		"\tlet h = seed | 0;\n"
+		"\tconst n = bytes.length;\n"
+		"\tlet i = 0;\n"
+		"\tfor (; i + 4 <= n; i += 4) {\n"
+		"\t\th ^= mix(bytes[i] | bytes[i + 1] << 8 | bytes[i + 2] << 16 | bytes[i + 3] << 24);\n"
+		"\t\th = h << 13 | h >>> 19;\n"
+		"\t\th = (Math.imul(h, 5) + 0xe6546b64) | 0;\n"
+		"\t}\n"
+		"\tif ((n & 3) !== 0) {\n"
+		"\t\tlet k = bytes[i];\n"
+		"\t\tif ((n & 3) >= 2) k |= bytes[i + 1] << 8;\n"
+		"\t\tif ((n & 3) === 3) k |= bytes[i + 2] << 16;\n"
+		"\t\th ^= mix(k);\n"
+		"\t}\n"
+		"\th ^= n;\n"
+		"\th ^= h >>> 16;\n"
+		"\th = Math.imul(h, 0x85ebca6b);\n"
+		"\th ^= h >>> 13;\n"
+		"\th = Math.imul(h, 0xc2b2ae35);\n"
+		"\th ^= h >>> 16;\n"
+		"\treturn h >>> 0;\n"
+		"}\n"
+		"\n"
+		"export function index(key: string): number | undefined {\n"
+		if (empty) {
+			"\treturn undefined;\n"
+		}
+		else {
+			"\tconst bytes = encoder.encode(key);\n"
+			"\tconst seed = SEEDS[hash(bytes, 0) % SEEDS.length];\n"
+			"\tif (seed === 0xffffffff) return undefined;\n"
+			"\tconst i = hash(bytes, seed) % LEN;\n"
+			if (input.verify_keys) {
+				"\tif (KEYS[i] !== key) return undefined;\n"
+			}
+			"\treturn i;\n"
+		}
+		"}\n"
+		if (input.has_values) {
+			"\n"
+			"export function value(key: string): string | undefined {\n"
+			"\tconst i = index(key);\n"
+			"\treturn i === undefined ? undefined : VALUES[i];\n"
+			"}\n"
+		}
+	).to_string())
+}
+
+// Printable ASCII is written as is, everything else as code point escapes so the module is plain ASCII
+pub(super) fn ts_literal(s: &str) -> String {
+	let mut literal = String::from("\"");
+	for chr in s.chars() {
+		match chr {
+			'"' => literal.push_str("\\\""),
+			'\\' => literal.push_str("\\\\"),
+			' '..='~' => literal.push(chr),
+			_ => literal.push_str(&format!("\\u{{{:x}}}", chr as u32)),
+		}
+	}
+	literal.push('"');
+	literal
+}
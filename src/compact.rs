@@ -0,0 +1,170 @@
+/*!
+Single allocation tables, requires the `bytemuck` feature.
+*/
+
+use std::{fmt, marker::PhantomData, mem};
+use super::{BuildError, Mphf};
+
+/// Minimally Perfect Hash table storing its seeds and values in a single flat allocation.
+///
+/// The bytes hold the seeds as little-endian words followed by the values in mphf order, padded to the alignment of `V`.
+/// Like [`Mphf`] the keys are not stored, keys outside the set return some value (or `None`).
+pub struct CompactMphfTable<V> {
+	bytes: Vec<u8>,
+	seeds_len: usize,
+	len: usize,
+	_values: PhantomData<V>,
+}
+
+impl<V: bytemuck::Pod> CompactMphfTable<V> {
+	/// Builds the table over the input keys and their values.
+	///
+	/// See [`build`](crate::build) for the meaning of the arguments.
+	///
+	/// # Panics
+	///
+	/// Panics if the number of keys and values differ.
+	pub fn build<S: AsRef<str>>(keys: &[S], values: &[V], seeds_len: usize, max_seed: u32) -> Result<CompactMphfTable<V>, BuildError> {
+		assert_eq!(keys.len(), values.len(), "keys and values must have the same length");
+		let mphf = Mphf::build(keys, seeds_len, max_seed)?;
+		// Built over the keys so every key has a unique index
+		let perm = crate::reorder_indices(keys, mphf.seeds()).unwrap();
+		let values = perm.iter().map(|&i| values[i]).collect::<Vec<V>>();
+		Ok(CompactMphfTable::from_mphf(&mphf, &values).unwrap())
+	}
+
+	/// Copies the seeds and the values in mphf order into a single allocation.
+	///
+	/// Returns `None` if the number of values does not match the mphf.
+	pub fn from_mphf(mphf: &Mphf, values: &[V]) -> Option<CompactMphfTable<V>> {
+		if values.len() != mphf.len() {
+			return None;
+		}
+		CompactMphfTable::from_parts(mphf.seeds(), values)
+	}
+
+	fn from_parts(seeds: &[u32], values: &[V]) -> Option<CompactMphfTable<V>> {
+		let seeds_len = seeds.len();
+		let values_size = mem::size_of_val(values);
+		// Reserve room to align the values, the allocation never moves afterwards
+		let mut bytes = Vec::with_capacity(seeds_len * 4 + mem::align_of::<V>() - 1 + values_size);
+		for &seed in seeds {
+			bytes.extend_from_slice(&seed.to_le_bytes());
+		}
+		let padding = bytes.as_ptr().wrapping_add(bytes.len()).align_offset(mem::align_of::<V>());
+		if padding >= mem::align_of::<V>() {
+			return None;
+		}
+		bytes.resize(bytes.len() + padding, 0);
+		bytes.extend_from_slice(bytemuck::cast_slice(values));
+		Some(CompactMphfTable { bytes, seeds_len, len: values.len(), _values: PhantomData })
+	}
+
+	/// Returns the number of keys.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.len
+	}
+	/// Returns true if built over an empty set of keys.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+	/// Returns the number of seeds.
+	#[inline]
+	pub fn seeds_len(&self) -> usize {
+		self.seeds_len
+	}
+	/// Returns the seed at the given index.
+	#[inline]
+	pub fn seed(&self, index: usize) -> Option<u32> {
+		if index >= self.seeds_len {
+			return None;
+		}
+		let i = index * 4;
+		Some(u32::from_le_bytes([self.bytes[i + 0], self.bytes[i + 1], self.bytes[i + 2], self.bytes[i + 3]]))
+	}
+	/// Returns the values in mphf order.
+	#[inline]
+	pub fn values(&self) -> &[V] {
+		let start = self.bytes.len() - self.len * mem::size_of::<V>();
+		bytemuck::cast_slice(&self.bytes[start..])
+	}
+
+	/// Returns the index of the given key.
+	#[inline]
+	pub fn index(&self, key: &str) -> Option<usize> {
+		if self.len == 0 || self.seeds_len == 0 {
+			return None;
+		}
+		let key = key.as_bytes();
+		let h0 = crate::hash(key, 0) as usize % self.seeds_len;
+		let seed = self.seed(h0)?;
+		if seed == u32::MAX {
+			return None;
+		}
+		return Some(crate::hash(key, seed) as usize % self.len);
+	}
+	/// Gets the value of the given key.
+	#[inline]
+	pub fn get(&self, key: &str) -> Option<&V> {
+		self.values().get(self.index(key)?)
+	}
+
+	/// Copies the seeds into an owned mphf.
+	pub fn to_mphf(&self) -> Mphf {
+		let seeds = (0..self.seeds_len).map(|i| self.seed(i).unwrap()).collect::<Vec<u32>>();
+		// Built from a valid mphf
+		Mphf::from_seeds(seeds.into(), self.len).unwrap()
+	}
+}
+
+// The padding depends on the address of the allocation, clones lay out their own bytes.
+impl<V: bytemuck::Pod> Clone for CompactMphfTable<V> {
+	fn clone(&self) -> CompactMphfTable<V> {
+		let seeds = (0..self.seeds_len).map(|i| self.seed(i).unwrap()).collect::<Vec<u32>>();
+		CompactMphfTable::from_parts(&seeds, self.values()).unwrap()
+	}
+}
+impl<V: bytemuck::Pod + PartialEq> PartialEq for CompactMphfTable<V> {
+	fn eq(&self, other: &CompactMphfTable<V>) -> bool {
+		self.seeds_len == other.seeds_len && self.bytes[..self.seeds_len * 4] == other.bytes[..other.seeds_len * 4] && self.values() == other.values()
+	}
+}
+impl<V: bytemuck::Pod + fmt::Debug> fmt::Debug for CompactMphfTable<V> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("CompactMphfTable")
+			.field("seeds_len", &self.seeds_len)
+			.field("values", &self.values())
+			.finish()
+	}
+}
+
+#[test]
+fn test_compact() {
+	let keys = (0..100).map(|i| format!("key{}", i)).collect::<Vec<String>>();
+	let values = (0..100u64).map(|i| i * 1000).collect::<Vec<u64>>();
+	let table = CompactMphfTable::build(&keys, &values, 25, 100000).unwrap();
+	assert_eq!(table.len(), 100);
+	assert_eq!(table.seeds_len(), 25);
+	for (key, value) in keys.iter().zip(&values) {
+		assert_eq!(table.get(key), Some(value));
+	}
+	assert_eq!(table.values().as_ptr() as usize % mem::align_of::<u64>(), 0);
+
+	let mphf = table.to_mphf();
+	assert_eq!(mphf, Mphf::build(&keys, 25, 100000).unwrap());
+	for key in &keys {
+		assert_eq!(table.index(key), mphf.index(key));
+	}
+
+	let clone = table.clone();
+	assert_eq!(clone, table);
+	assert_eq!(clone.values().as_ptr() as usize % mem::align_of::<u64>(), 0);
+
+	// Values without alignment requirements are not padded
+	let table = CompactMphfTable::build(&["a", "b", "c"], &[[1u8, 2, 3], [4, 5, 6], [7, 8, 9]], 1, 100000).unwrap();
+	assert_eq!(table.bytes.len(), 4 + 9);
+	assert_eq!(table.get("b"), Some(&[4, 5, 6]));
+	assert_eq!(CompactMphfTable::from_mphf(&table.to_mphf(), &[[0u8; 3]; 2]), None);
+}
@@ -0,0 +1,49 @@
+const TABLE: [u32; 256] = table();
+
+const fn table() -> [u32; 256] {
+	let mut table = [0u32; 256];
+	let mut i = 0;
+	while i < 256 {
+		let mut crc = i as u32;
+		let mut k = 0;
+		while k < 8 {
+			crc = if crc & 1 != 0 { (crc >> 1) ^ 0xedb88320 } else { crc >> 1 };
+			k += 1;
+		}
+		table[i] = crc;
+		i += 1;
+	}
+	return table;
+}
+
+/// Incremental CRC-32 (IEEE) checksum.
+#[derive(Copy, Clone, Debug)]
+pub struct Crc32(u32);
+
+impl Crc32 {
+	#[inline]
+	pub const fn new() -> Crc32 {
+		Crc32(!0)
+	}
+	#[inline]
+	pub fn update(&mut self, bytes: &[u8]) {
+		let mut crc = self.0;
+		for &byte in bytes {
+			crc = TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8);
+		}
+		self.0 = crc;
+	}
+	#[inline]
+	pub const fn finish(self) -> u32 {
+		!self.0
+	}
+}
+
+#[test]
+fn test_crc32_vectors() {
+	let mut crc = Crc32::new();
+	crc.update(b"1234");
+	crc.update(b"56789");
+	assert_eq!(crc.finish(), 0xCBF43926);
+	assert_eq!(Crc32::new().finish(), 0);
+}
@@ -2,19 +2,232 @@
 Minimally Perfect Hash Functions
 ================================
 
-
+Without the default `std` feature the crate is `#![no_std]` and only provides the lookup functions `hash`, `index`, `index_bytes`, `get`, `get_copied` and `get_bytes`, and the [`binary`] decoder [`MphfRef`].
+This is all the code generated by the `codegen` module needs, the generated tables work on `#![no_std]` targets with `default-features = false`.
+The exception are the key normalizers other than `Trim`, their generated lookups allocate and need `extern crate alloc`.
 */
 
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::needless_return, clippy::identity_op, clippy::tabs_in_doc_comments, clippy::manual_is_multiple_of)]
+
 #[cfg(feature = "codegen")]
 pub mod codegen;
 
 mod murmur3;
-pub use self::murmur3::hash;
+pub use self::murmur3::{hash, hash_u32};
+
+mod lookup_table;
+#[doc(hidden)]
+pub mod __lookup_table {
+	pub use super::lookup_table::{build, reorder, seeds_len};
+}
+
+#[cfg(feature = "macros")]
+mod static_map;
+#[cfg(feature = "macros")]
+pub use self::static_map::{Map, Set};
+/// Builds a [`Map`] with string keys at compile time.
+///
+/// Takes `"key" => value` pairs, the keys must be string literals and the values constant expressions of a `Copy` type.
+/// Expands to a constant expression for the initializer of a `static` or `const`.
+/// The seeds are bruteforced in `const` context the same as [`lookup_table!`], duplicate keys are reported at the literal.
+///
+/// # Examples
+///
+/// ```
+/// static KEYWORDS: mphf::Map<&str, u32> = mphf::map! { "if" => 0, "else" => 1, "while" => 2 };
+///
+/// assert_eq!(KEYWORDS.get("while"), Some(&2));
+/// assert_eq!(KEYWORDS.get("for"), None);
+/// assert!(KEYWORDS.entries().all(|(key, value)| KEYWORDS.get(key) == Some(value)));
+/// ```
+#[cfg(feature = "macros")]
+pub use mphf_macros::map;
+/// Builds a [`Set`] of strings at compile time.
+///
+/// Takes string literals separated by commas, expands to a constant expression for the initializer of a `static` or `const`.
+/// Duplicate keys are reported at the second literal, the same as [`map!`].
+///
+/// # Examples
+///
+/// ```
+/// static KEYWORDS: mphf::Set<&str> = mphf::set! { "if", "else", "while" };
+///
+/// assert!(KEYWORDS.contains("else"));
+/// assert_eq!(KEYWORDS.index_of("for"), None);
+/// ```
+#[cfg(feature = "macros")]
+pub use mphf_macros::set;
+/// Derives `FromStr` and `name(&self) -> &'static str` for an enum of unit variants.
+///
+/// `FromStr` looks up the name with an mphf built over the names at compile time, unknown names are `Err(())`.
+/// The names are the variant names, `#[mphf(rename_all = "...")]` on the enum converts their case with the same rules as serde:
+/// `"lowercase"`, `"UPPERCASE"`, `"PascalCase"`, `"camelCase"`, `"snake_case"`, `"SCREAMING_SNAKE_CASE"`, `"kebab-case"` or `"SCREAMING-KEBAB-CASE"`.
+/// `#[mphf(rename = "...")]` on a variant sets its name.
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, PartialEq, mphf::FromName)]
+/// #[mphf(rename_all = "kebab-case")]
+/// enum Header {
+/// 	ContentType,
+/// 	ContentLength,
+/// 	#[mphf(rename = "x-request-id")]
+/// 	RequestId,
+/// }
+///
+/// assert_eq!("content-type".parse(), Ok(Header::ContentType));
+/// assert_eq!("request-id".parse::<Header>(), Err(()));
+/// assert_eq!(Header::RequestId.name(), "x-request-id");
+/// ```
+#[cfg(feature = "macros")]
+pub use mphf_macros::FromName;
+
+mod crc32;
+
+#[cfg(feature = "std")]
+mod seeds;
+#[cfg(feature = "std")]
+pub use self::seeds::Seeds;
+#[cfg(feature = "rkyv")]
+pub use self::seeds::ArchivedSeeds;
+
+#[cfg(feature = "std")]
+mod mphf;
+#[cfg(feature = "std")]
+pub use self::mphf::Mphf;
+#[cfg(feature = "rkyv")]
+pub use self::mphf::ArchivedMphf;
+
+pub mod binary;
+pub use self::binary::MphfRef;
+
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "mmap")]
+pub use self::mmap::MmapMphf;
+
+#[cfg(feature = "std")]
+mod map;
+#[cfg(feature = "std")]
+pub use self::map::{MphfMap, SubsetError};
+
+#[cfg(feature = "bytemuck")]
+pub mod compact;
+
+#[cfg(feature = "std")]
+pub mod build_script;
+
+#[cfg(feature = "toml")]
+mod toml_source;
+#[cfg(feature = "toml")]
+pub use self::toml_source::{build_from_toml, TomlError};
+
+// Estimates the number of seeds the bruteforce search is expected to try.
+// Buckets are resolved largest first, each bucket needs all its keys to land in slots still free after the buckets before it.
+// For a bucket of `b` keys with `u` slots already used the chance a random seed works is `prod_{k=0}^{b-1} (1 - (u + k) / n)`.
+#[cfg(feature = "log")]
+fn expected_attempts<K>(buckets: &[(usize, Vec<K>)], used: &[bool]) -> f64 {
+	let n = used.len() as f64;
+	let mut used = used.iter().filter(|&&used| used).count() as f64;
+	let mut attempts = 0.0;
+	for (_, bucket) in buckets.iter().rev() {
+		if bucket.is_empty() {
+			continue;
+		}
+		let mut p = 1.0;
+		for k in 0..bucket.len() {
+			p *= 1.0 - (used + k as f64) / n;
+		}
+		attempts += 1.0 / p;
+		used += bucket.len() as f64;
+	}
+	attempts
+}
+
+/// Error building a Minimally Perfect Hash Function.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BuildError {
+	/// The `seeds_len` argument is zero.
+	ZeroSeedsLen,
+	/// There are fewer free slots than keys.
+	NotEnoughSlots { keys_len: usize, free: usize },
+	/// No seed below `max_seed` avoids hash collisions for a bucket.
+	SeedNotFound { bucket: usize, bucket_len: usize, max_seed: u32 },
+	/// The seed iterator of [`build_with_seeds`] ran out before a seed avoided hash collisions for a bucket.
+	SeedsExhausted { bucket: usize, bucket_len: usize, tried: u32 },
+}
+
+impl core::fmt::Display for BuildError {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		match *self {
+			BuildError::ZeroSeedsLen => f.write_str("seeds_len is zero, it must be at least 1"),
+			BuildError::NotEnoughSlots { keys_len, free } => write!(f, "{} keys do not fit in the {} free slots, mark fewer slots as used or make room for every key", keys_len, free),
+			BuildError::SeedNotFound { bucket, bucket_len, max_seed } => write!(f, "no seed below max_seed {} avoids collisions for the {} keys of bucket {}, try a larger seeds_len or max_seed, or check for duplicate keys", max_seed, bucket_len, bucket),
+			BuildError::SeedsExhausted { bucket, bucket_len, tried } => write!(f, "none of the {} seeds avoids collisions for the {} keys of bucket {}, try a larger seeds_len or more seeds, or check for duplicate keys", tried, bucket_len, bucket),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BuildError {}
+
+/// Outcome of the seed search for a single bucket, see [`build_report`].
+#[cfg(feature = "std")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BucketResult {
+	/// No keys went in the bucket, its seed is `u32::MAX`.
+	Empty,
+	/// Found a seed avoiding collisions for the `len` keys of the bucket.
+	Found { len: usize, seed: u32 },
+	/// No seed below `max_seed` avoids collisions for the `len` keys of the bucket.
+	NotFound { len: usize },
+}
+
+/// Result of the seed search for every bucket, see [`build_report`].
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuildReport {
+	/// The outcome per bucket in bucket order.
+	pub buckets: Vec<BucketResult>,
+}
+
+#[cfg(feature = "std")]
+impl BuildReport {
+	/// Returns the number of buckets without a seed.
+	pub fn failed(&self) -> usize {
+		self.buckets.iter().filter(|result| matches!(result, BucketResult::NotFound { .. })).count()
+	}
+	/// Returns the seeds if a seed was found for every bucket, the same seeds as [`build`].
+	pub fn seeds(&self) -> Option<Seeds> {
+		self.buckets.iter().map(|&result| match result {
+			BucketResult::Empty => Some(u32::MAX),
+			BucketResult::Found { seed, .. } => Some(seed),
+			BucketResult::NotFound { .. } => None,
+		}).collect::<Option<Vec<u32>>>().map(Seeds::from)
+	}
+}
+
+/// Seed search of a single bucket, see [`build_verbose`].
+#[cfg(feature = "std")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BucketInfo {
+	/// Index of the bucket in the seeds.
+	pub bucket_index: usize,
+	/// Number of keys in the bucket.
+	pub bucket_size: usize,
+	/// The seed found for the bucket.
+	pub seed_found: u32,
+	/// Number of seeds tried including the one found.
+	pub attempts: u32,
+}
 
 // Checks if the hashs with given seed are not already used and marks them as used.
-fn check_seed(seed: u32, bucket: &[&str], used: &mut [bool]) -> bool {
-	for &item in bucket {
-		let h = hash(item.as_bytes(), seed) as usize % used.len();
+#[cfg(feature = "std")]
+fn check_seed<K: AsRef<[u8]>>(seed: u32, bucket: &[K], used: &mut [bool]) -> bool {
+	for item in bucket {
+		let h = hash(item.as_ref(), seed) as usize % used.len();
 		if used[h] {
 			return false;
 		}
@@ -34,10 +247,13 @@ fn check_seed(seed: u32, bucket: &[&str], used: &mut [bool]) -> bool {
 /// * `seeds_len` is the length of the intermediary list of seeds to avoid hash collisions.
 ///   A smaller number relative to the number of keys means that bruteforcing seeds may take significantly longer.
 ///   This value must be strictly greater than 0 or an `Err` is returned.
+///   More seeds than keys only wastes memory on empty buckets, a warning is logged with the `log` feature.
 ///
 ///   An interesting special case of `seeds_len = 1` this means there's a single hash function which is minimally perfect.
 ///   The returned seeds has a single value which can be passed directly to `hash(key, seed)`.
 ///   This only works for small sets of keys as the likelyhood of such a seed existing drops exponetially with the number of keys.
+///   Each seed maps `n` keys to distinct indices with probability `n! / n^n`, roughly `e^-n`,
+///   so on average `n^n / n!` seeds are tried: a handful for 2 or 3 keys, hundreds for 8 keys and about a million for 16 keys.
 ///
 /// * `max_seed` is the cut-off for bruteforce searching of seeds which avoid hash collisions.
 ///   If the this number is reached the search stops and this function returns an `Err`.
@@ -69,15 +285,302 @@ fn check_seed(seed: u32, bucket: &[&str], used: &mut [bool]) -> bool {
 /// 3: cat
 /// 0: dog
 /// ```
-pub fn build(keys: &[&str], seeds_len: usize, max_seed: u32) -> Result<Vec<u32>, ()> {
+///
+/// A single seed for a small set of keys:
+///
+/// ```
+/// const KEYS: &[&str] = &["a", "b"];
+///
+/// // Every seed has a 1 in 2 chance to map the keys to distinct indices
+/// let seeds = mphf::build(KEYS, 1, 100).unwrap();
+/// let seed = seeds[0];
+///
+/// let a = mphf::hash(b"a", seed) as usize % KEYS.len();
+/// let b = mphf::hash(b"b", seed) as usize % KEYS.len();
+/// assert_ne!(a, b);
+/// assert_eq!(mphf::index("a", &seeds, KEYS.len()), Some(a));
+/// ```
+#[cfg(feature = "std")]
+pub fn build<S: AsRef<str>>(keys: &[S], seeds_len: usize, max_seed: u32) -> Result<Seeds, BuildError> {
+	let mut used = vec![false; keys.len()];
+	build_into_used(keys, seeds_len, max_seed, &mut used)
+}
+
+/// Estimates the memory in bytes of a table before building it.
+///
+/// The seeds take 4 bytes each and the keys are estimated at `avg_key_len` bytes each,
+/// add the size of the values and any per key overhead of the container storing the keys.
+/// Saturates at `usize::MAX` instead of overflowing.
+///
+/// # Examples
+///
+/// ```
+/// // A million keys of about 12 bytes with a seed per 4 keys
+/// const SPACE: usize = mphf::approximate_space_bytes(1_000_000, 250_000, 12);
+/// assert_eq!(SPACE, 13_000_000);
+/// ```
+#[cfg(feature = "std")]
+pub const fn approximate_space_bytes(keys_len: usize, seeds_len: usize, avg_key_len: usize) -> usize {
+	seeds_len.saturating_mul(4).saturating_add(keys_len.saturating_mul(avg_key_len))
+}
+
+/// Builds the seeds table without stopping at the first bucket for which no seed is found.
+///
+/// Like [`build`] but the remaining buckets are still searched after a failure, which shows how many buckets fail for the given arguments.
+/// The buckets which failed leave their slots free for the later buckets so the later seeds may differ from a successful build.
+///
+/// Returns `Err` only for invalid arguments, the buckets without a seed are reported as [`BucketResult::NotFound`].
+///
+/// # Examples
+///
+/// ```
+/// let keys = (0..100).map(|i| format!("key{}", i)).collect::<Vec<String>>();
+///
+/// // Not enough seeds to avoid collisions
+/// let report = mphf::build_report(&keys, 10, 100).unwrap();
+/// assert!(report.failed() > 0);
+/// assert_eq!(report.seeds(), None);
+///
+/// let report = mphf::build_report(&keys, 25, 100000).unwrap();
+/// assert_eq!(report.failed(), 0);
+/// assert_eq!(report.seeds(), mphf::build(&keys, 25, 100000).ok());
+/// ```
+#[cfg(feature = "std")]
+pub fn build_report<S: AsRef<str>>(keys: &[S], seeds_len: usize, max_seed: u32) -> Result<BuildReport, BuildError> {
+	let keys = keys.iter().map(|key| key.as_ref()).collect::<Vec<&str>>();
+	let mut used = vec![false; keys.len()];
+	let mut infos = Vec::new();
+	match build_impl(&keys, seeds_len, &mut used, default_bucket, |_| 0..max_seed, Some(&mut infos)) {
+		Ok(_) | Err(BuildError::SeedNotFound { .. }) => (),
+		Err(err) => return Err(err),
+	}
+	let mut buckets = vec![BucketResult::Empty; seeds_len];
+	for info in infos {
+		buckets[info.bucket_index] = match info.seed_found {
+			u32::MAX => BucketResult::NotFound { len: info.bucket_size },
+			seed => BucketResult::Found { len: info.bucket_size, seed },
+		};
+	}
+	Ok(BuildReport { buckets })
+}
+
+/// Builds the seeds like [`build`] and reports the seed search of every bucket.
+///
+/// The buckets are listed in the order they are searched, the largest first, empty buckets are left out.
+/// The bucket with the most attempts is the one that takes the longest, see [`build_report`] for a report of the buckets when the build fails.
+///
+/// # Examples
+///
+/// ```
+/// let keys = (0..100).map(|i| format!("key{}", i)).collect::<Vec<String>>();
+/// let (seeds, buckets) = mphf::build_verbose(&keys, 25, 100000).unwrap();
+/// assert_eq!(seeds, mphf::build(&keys, 25, 100000).unwrap());
+///
+/// let hardest = buckets.iter().max_by_key(|bucket| bucket.attempts).unwrap();
+/// assert_eq!(seeds[hardest.bucket_index], hardest.seed_found);
+/// ```
+#[cfg(feature = "std")]
+pub fn build_verbose<S: AsRef<str>>(keys: &[S], seeds_len: usize, max_seed: u32) -> Result<(Seeds, Vec<BucketInfo>), BuildError> {
+	let keys = keys.iter().map(|key| key.as_ref()).collect::<Vec<&str>>();
+	let mut used = vec![false; keys.len()];
+	let mut infos = Vec::new();
+	let seeds = build_impl(&keys, seeds_len, &mut used, default_bucket, |_| 0..max_seed, Some(&mut infos))?;
+	Ok((seeds, infos))
+}
+
+/// Builds the seeds table trying the candidate seeds in a pseudo-random order.
+///
+/// Like [`build`] but each bucket tries `max_seed` candidates from a sequence derived from `initial_seed` and the bucket index
+/// instead of the seeds `0..max_seed`, which may find seeds faster for key sets where the small seeds happen to collide.
+/// The seeds are any value other than `u32::MAX` and are looked up as usual with [`index`].
+///
+/// The same `initial_seed` always builds the same seeds, an `initial_seed` of zero is the sequential search of [`build`].
+///
+/// # Examples
+///
+/// ```
+/// const KEYS: &[&str] = &["hello", "goodbye", "cat", "dog"];
+///
+/// let seeds = mphf::build_seeded(KEYS, 2, 10000, 0x5eed).unwrap();
+/// assert_eq!(seeds, mphf::build_seeded(KEYS, 2, 10000, 0x5eed).unwrap());
+/// assert_eq!(mphf::build_seeded(KEYS, 2, 10000, 0), mphf::build(KEYS, 2, 10000));
+///
+/// let mut seen = [false; 4];
+/// for &key in KEYS {
+/// 	let index = mphf::index(key, &seeds, KEYS.len()).unwrap();
+/// 	assert!(!seen[index]);
+/// 	seen[index] = true;
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub fn build_seeded<S: AsRef<str>>(keys: &[S], seeds_len: usize, max_seed: u32, initial_seed: u64) -> Result<Seeds, BuildError> {
+	let keys = keys.iter().map(|key| key.as_ref()).collect::<Vec<&str>>();
+	let mut used = vec![false; keys.len()];
+	build_impl(&keys, seeds_len, &mut used, default_bucket, |bucket| (0..max_seed).map(move |attempt| candidate_seed(initial_seed, bucket, attempt)), None)
+}
+
+// Returns the seed to try for the given attempt.
+// Sequential without an initial seed, otherwise the splitmix64 finalizer of a counter unique to the bucket and attempt.
+#[cfg(feature = "std")]
+#[inline]
+fn candidate_seed(initial_seed: u64, bucket: usize, attempt: u32) -> u32 {
+	if initial_seed == 0 {
+		return attempt;
+	}
+	let mut z = initial_seed.wrapping_add(((bucket as u64) << 32 | attempt as u64).wrapping_mul(0x9E3779B97F4A7C15));
+	z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+	z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+	z ^= z >> 31;
+	// u32::MAX marks an empty bucket
+	return u32::min((z >> 32) as u32, u32::MAX - 1);
+}
+
+/// Builds the seeds table trying the seeds of an iterator for every bucket.
+///
+/// Like [`build`] but each bucket tries the seeds from a clone of `seeds` in order instead of `0..max_seed`,
+/// eg. a random sequence or one skipping seeds known to be poor for the keys.
+/// `build(keys, seeds_len, max_seed)` is `build_with_seeds(keys, seeds_len, 0..max_seed)`.
+/// The seed `u32::MAX` marks empty buckets and is skipped.
+///
+/// Returns [`BuildError::SeedsExhausted`] when the iterator runs out before a seed avoids hash collisions,
+/// limit an endless iterator with [`Iterator::take`].
+///
+/// # Examples
+///
+/// ```
+/// const KEYS: &[&str] = &["hello", "goodbye", "cat", "dog"];
+///
+/// // The odd seeds only
+/// let seeds = mphf::build_with_seeds(KEYS, 2, (1..).step_by(2).take(10000)).unwrap();
+/// assert!(seeds.iter().all(|&seed| seed % 2 == 1));
+///
+/// assert_eq!(mphf::build_with_seeds(KEYS, 2, 0..10000), mphf::build(KEYS, 2, 10000));
+/// assert!(mphf::build_with_seeds(&["a", "a"], 1, 0..10).is_err());
+/// ```
+#[cfg(feature = "std")]
+pub fn build_with_seeds<S: AsRef<str>, I: Iterator<Item = u32> + Clone>(keys: &[S], seeds_len: usize, seeds: I) -> Result<Seeds, BuildError> {
+	let keys = keys.iter().map(|key| key.as_ref()).collect::<Vec<&str>>();
+	let mut used = vec![false; keys.len()];
+	build_impl(&keys, seeds_len, &mut used, default_bucket, |_| seeds.clone().filter(|&seed| seed != u32::MAX), None).map_err(|err| match err {
+		BuildError::SeedNotFound { bucket, bucket_len, max_seed } => BuildError::SeedsExhausted { bucket, bucket_len, tried: max_seed },
+		err => err,
+	})
+}
+
+/// Builds the seeds table into an index space shared with other tables.
+///
+/// Like [`build`] but the keys are hashed into the slots of `used` instead of `0..keys.len()`.
+/// Slots already marked as used are avoided and the slots taken by the keys are marked on success.
+/// Building several key sets with the same `used` slice results in tables which map all their keys to distinct indices,
+/// look up keys with `index(key, &seeds, used.len())`.
+///
+/// On failure `used` is left unchanged.
+///
+/// # Examples
+///
+/// ```
+/// let fruits = &["apple", "banana", "cherry"];
+/// let colors = &["red", "green"];
+///
+/// let mut used = vec![false; fruits.len() + colors.len()];
+/// let fruit_seeds = mphf::build_into_used(fruits, 1, 100000, &mut used).unwrap();
+/// let color_seeds = mphf::build_into_used(colors, 1, 100000, &mut used).unwrap();
+/// assert!(used.iter().all(|&used| used));
+///
+/// // Every key maps to its own slot of a single values array
+/// let mut values = [""; 5];
+/// for &key in fruits {
+/// 	values[mphf::index(key, &fruit_seeds, used.len()).unwrap()] = key;
+/// }
+/// for &key in colors {
+/// 	values[mphf::index(key, &color_seeds, used.len()).unwrap()] = key;
+/// }
+/// assert!(values.iter().all(|value| !value.is_empty()));
+/// ```
+#[cfg(feature = "std")]
+pub fn build_into_used<S: AsRef<str>>(keys: &[S], seeds_len: usize, max_seed: u32, used: &mut [bool]) -> Result<Seeds, BuildError> {
+	let keys = keys.iter().map(|key| key.as_ref()).collect::<Vec<&str>>();
+	build_impl(&keys, seeds_len, used, default_bucket, |_| 0..max_seed, None)
+}
+
+/// Builds the seeds table with a custom assignment of keys to buckets.
+///
+/// Like [`build`] but each key goes in bucket `bucket_fn(key) % seeds_len` instead of `hash(key, 0) % seeds_len`.
+/// A cheap function which spreads the keys evenly over the buckets for the specific key set can speed up the build.
+///
+/// Look up keys with [`index_with_bucket_fn`] and the same `bucket_fn`, the other lookup functions assume the default assignment.
+///
+/// # Examples
+///
+/// ```
+/// const METHODS: &[&str] = &["GET", "HEAD", "POST", "PUT", "DELETE", "CONNECT", "OPTIONS", "TRACE", "PATCH"];
+/// let bucket_fn = |key: &str| key.len() + key.as_bytes()[0] as usize;
+///
+/// let seeds = mphf::build_with_bucket_fn(METHODS, 4, 100000, bucket_fn).unwrap();
+/// let mut seen = [false; 9];
+/// for &method in METHODS {
+/// 	let index = mphf::index_with_bucket_fn(method, &seeds, METHODS.len(), bucket_fn).unwrap();
+/// 	assert!(!seen[index]);
+/// 	seen[index] = true;
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub fn build_with_bucket_fn<S: AsRef<str>, F: Fn(&str) -> usize>(keys: &[S], seeds_len: usize, max_seed: u32, bucket_fn: F) -> Result<Seeds, BuildError> {
+	let keys = keys.iter().map(|key| key.as_ref()).collect::<Vec<&str>>();
+	let mut used = vec![false; keys.len()];
+	build_impl(&keys, seeds_len, &mut used, bucket_fn, |_| 0..max_seed, None)
+}
+
+/// Builds the seeds table over byte string keys.
+///
+/// Like [`build`] for keys which need not be valid UTF-8, look up the keys with [`index_bytes`].
+/// A string hashes the same as its bytes, the seeds are the same as [`build`] over the keys as strings.
+///
+/// # Examples
+///
+/// ```
+/// const TAGS: &[&[u8]] = &[b"\x00\x01", b"\xff", b"\xfe\xff", b"tag"];
+///
+/// let seeds = mphf::build_bytes(TAGS, 2, 10000).unwrap();
+/// let mut seen = [false; 4];
+/// for &tag in TAGS {
+/// 	let index = mphf::index_bytes(tag, &seeds, TAGS.len()).unwrap();
+/// 	assert!(!seen[index]);
+/// 	seen[index] = true;
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub fn build_bytes(keys: &[&[u8]], seeds_len: usize, max_seed: u32) -> Result<Seeds, BuildError> {
+	let mut used = vec![false; keys.len()];
+	build_impl(keys, seeds_len, &mut used, |key: &[u8]| hash(key, 0) as usize, |_| 0..max_seed, None)
+}
+
+#[cfg(feature = "std")]
+#[inline]
+fn default_bucket(key: &str) -> usize {
+	hash(key.as_bytes(), 0) as usize
+}
+
+#[cfg(feature = "std")]
+// Each bucket tries the seeds of `candidates(bucket)` in order, running out is reported as SeedNotFound below the number of seeds tried
+fn build_impl<K: Copy + AsRef<[u8]>, F: Fn(K) -> usize, C: Fn(usize) -> I, I: Iterator<Item = u32>>(keys: &[K], seeds_len: usize, used: &mut [bool], bucket_fn: F, candidates: C, mut report: Option<&mut Vec<BucketInfo>>) -> Result<Seeds, BuildError> {
 	if seeds_len == 0 {
-		return Err(());
+		return Err(BuildError::ZeroSeedsLen);
+	}
+	let free = used.iter().filter(|&&used| !used).count();
+	if keys.len() > free {
+		return Err(BuildError::NotEnoughSlots { keys_len: keys.len(), free });
+	}
+	// Not wrong but the extra seeds are wasted on empty buckets, likely the arguments are swapped
+	#[cfg(feature = "log")]
+	if seeds_len > usize::max(keys.len(), 1) {
+		log::warn!("mphf: seeds_len {} exceeds the number of keys {}, at least {} seeds are wasted on empty buckets", seeds_len, keys.len(), seeds_len - keys.len());
 	}
 
 	// First pass over the input keys, bucket them by their hash
 	let mut buckets = vec![(0usize, vec![]); seeds_len];
 	for &key in keys {
-		let h = hash(key.as_bytes(), 0) as usize % buckets.len();
+		let h = bucket_fn(key) % buckets.len();
 		buckets[h].0 = h as usize;
 		buckets[h].1.push(key);
 	}
@@ -86,12 +589,21 @@ pub fn build(keys: &[&str], seeds_len: usize, max_seed: u32) -> Result<Vec<u32>,
 	let mut seeds = vec![u32::MAX; buckets.len()];
 
 	// Caches used to detect hash collisions
-	let mut used = vec![false; keys.len()];
-	let mut tmp = vec![false; keys.len()];
+	let mut slots = used.to_vec();
+	let mut tmp = used.to_vec();
 
 	// Sort the buckets by the number of collisions
 	// This will speed up bruteforcing a seed that breaks the collisions
-	buckets.sort_unstable_by_key(|bucket| bucket.1.len());
+	// Ties are broken by the bucket index so the seeds do not depend on the order of the input keys
+	buckets.sort_unstable_by_key(|bucket| (bucket.1.len(), bucket.0));
+
+	#[cfg(feature = "log")]
+	log::info!("mphf: building {} keys in {} buckets, expecting ~{:.0} seed attempts", keys.len(), seeds_len, expected_attempts(&buckets, used));
+	#[cfg(feature = "log")]
+	let mut attempts = 0u64;
+
+	// Reports every bucket instead of stopping at the first failure
+	let mut error = None;
 
 	// Bruteforce a seed which avoids a hash collision with
 	for &(index, ref bucket) in buckets.iter().rev() {
@@ -99,27 +611,57 @@ pub fn build(keys: &[&str], seeds_len: usize, max_seed: u32) -> Result<Vec<u32>,
 			continue;
 		}
 
-		let mut seed = 0;
-		while seed < max_seed {
+		let mut attempt = 0u32;
+		let mut found = false;
+		for seed in candidates(index) {
+			#[cfg(feature = "log")]
+			{
+				attempts += 1;
+			}
+			attempt = attempt.saturating_add(1);
 			// Initialize the buffer for checking available seeds
-			tmp.copy_from_slice(&used);
+			tmp.copy_from_slice(&slots);
 			if check_seed(seed, bucket, &mut tmp) {
 				// Found a seed without hash collisions
 				seeds[index] = seed;
-				used.copy_from_slice(&tmp);
+				slots.copy_from_slice(&tmp);
+				if let Some(report) = report.as_deref_mut() {
+					report.push(BucketInfo { bucket_index: index, bucket_size: bucket.len(), seed_found: seed, attempts: attempt });
+				}
+				found = true;
 				break;
 			}
-			seed += 1;
 		}
-		if seed == max_seed {
-			return Err(());
+		if !found {
+			let err = BuildError::SeedNotFound { bucket: index, bucket_len: bucket.len(), max_seed: attempt };
+			match report.as_deref_mut() {
+				Some(report) => {
+					// Seeds are never u32::MAX, it marks the bucket without a seed
+					report.push(BucketInfo { bucket_index: index, bucket_size: bucket.len(), seed_found: u32::MAX, attempts: attempt });
+					error.get_or_insert(err);
+				},
+				None => {
+					#[cfg(feature = "log")]
+					log::info!("mphf: gave up after {} seed attempts", attempts);
+					return Err(err);
+				},
+			}
 		}
 	}
+	if let Some(err) = error {
+		return Err(err);
+	}
 
-	return Ok(seeds);
+	#[cfg(feature = "log")]
+	log::info!("mphf: found all seeds after {} seed attempts", attempts);
+
+	used.copy_from_slice(&slots);
+	return Ok(Seeds::from(seeds));
 }
 
 /// Reorders the list of keys and values into their minimally perfect hash order.
+///
+/// Returns `None` if the lengths differ or the seeds do not map the keys to unique indices, the order is unspecified then.
 pub fn reorder<T>(keys: &mut [&str], seeds: &[u32], mut values: Option<&mut [T]>) -> Option<()> {
 	// If given the set of keys and values must have the same length
 	if let Some(values) = &values {
@@ -140,6 +682,10 @@ pub fn reorder<T>(keys: &mut [&str], seeds: &[u32], mut values: Option<&mut [T]>
 			if i == j {
 				break;
 			}
+			// Swapping with a key already in place would never end
+			if index(keys[j], seeds, values_len)? == j {
+				return None;
+			}
 			if let Some(values) = &mut values {
 				values.swap(i, j);
 			}
@@ -150,20 +696,351 @@ pub fn reorder<T>(keys: &mut [&str], seeds: &[u32], mut values: Option<&mut [T]>
 	Some(())
 }
 
+/// Reorders the key value pairs into their minimally perfect hash order.
+///
+/// Like [`reorder`] for the keys and values stored together, eg. in a `Vec<(&str, T)>` or a `Box<[(&str, T)]>`.
+///
+/// # Examples
+///
+/// ```
+/// let mut pairs = vec![("hello", 1), ("goodbye", 2), ("cat", 3), ("dog", 4)];
+/// let seeds = mphf::build(&["hello", "goodbye", "cat", "dog"], 2, 10000).unwrap();
+/// mphf::reorder_pairs(&mut pairs, &seeds).unwrap();
+/// for (i, &(key, _)) in pairs.iter().enumerate() {
+/// 	assert_eq!(mphf::index(key, &seeds, pairs.len()), Some(i));
+/// }
+/// ```
+pub fn reorder_pairs<T>(pairs: &mut [(&str, T)], seeds: &[u32]) -> Option<()> {
+	let len = pairs.len();
+	for i in 0..len {
+		loop {
+			let j = index(pairs[i].0, seeds, len)?;
+			if i == j {
+				break;
+			}
+			// Swapping with a key already in place would never end
+			if index(pairs[j].0, seeds, len)? == j {
+				return None;
+			}
+			pairs.swap(i, j);
+		}
+	}
+	Some(())
+}
+
+/// Returns the permutation which reorders the keys into their minimally perfect hash order.
+///
+/// `perm[i]` is the original index of the key at mphf index `i`, the keys and values are left untouched.
+/// Apply it to any number of parallel arrays with `perm.iter().map(|&i| array[i].clone()).collect()`.
+///
+/// Returns `None` if the seeds do not map the keys to unique indices.
+#[cfg(feature = "std")]
+pub fn reorder_indices<S: AsRef<str>>(keys: &[S], seeds: &[u32]) -> Option<Vec<usize>> {
+	let mut perm = vec![usize::MAX; keys.len()];
+	for (i, key) in keys.iter().enumerate() {
+		let j = index(key.as_ref(), seeds, keys.len())?;
+		if perm[j] != usize::MAX {
+			return None;
+		}
+		perm[j] = i;
+	}
+	Some(perm)
+}
+
 /// Returns the index of the given key in the mphf table.
+///
+/// Returns `None` if the seeds or `values_len` are empty, a table over zero keys cannot contain any key.
 #[inline]
 pub fn index(key: &str, seeds: &[u32], values_len: usize) -> Option<usize> {
-	let key = key.as_bytes();
-	let h0 = hash(key, 0) as usize % seeds.len();
-	let &seed = seeds.get(h0)?;
+	index_bytes(key.as_bytes(), seeds, values_len)
+}
+/// Returns the index of the given byte string key in the mphf table built by [`build_bytes`].
+#[inline]
+pub fn index_bytes(key: &[u8], seeds: &[u32], values_len: usize) -> Option<usize> {
+	if seeds.is_empty() || values_len == 0 {
+		return None;
+	}
+	let &seed = seeds.get(hash(key, 0) as usize % seeds.len())?;
 	if seed == u32::MAX {
 		return None;
 	}
 	return Some(hash(key, seed) as usize % values_len);
 }
+/// Returns the index of the given key in the mphf table built by [`build_with_bucket_fn`].
+#[inline]
+pub fn index_with_bucket_fn<F: Fn(&str) -> usize>(key: &str, seeds: &[u32], values_len: usize, bucket_fn: F) -> Option<usize> {
+	if seeds.is_empty() || values_len == 0 {
+		return None;
+	}
+	let h0 = bucket_fn(key) % seeds.len();
+	let &seed = seeds.get(h0)?;
+	if seed == u32::MAX {
+		return None;
+	}
+	return Some(hash(key.as_bytes(), seed) as usize % values_len);
+}
 /// Gets the value of the given key in the mphf table.
 #[inline]
 pub fn get<'a, T>(key: &str, seeds: &[u32], values: &'a [T]) -> Option<&'a T> {
 	let index = index(key, seeds, values.len())?;
 	values.get(index)
 }
+/// Gets a copy of the value of the given key in the mphf table.
+///
+/// # Examples
+///
+/// ```
+/// const KEYS: &[&str] = &["hello", "goodbye", "cat", "dog"];
+/// let seeds = mphf::build(KEYS, 2, 10000).unwrap();
+/// let mut values = [1u32, 2, 3, 4];
+/// mphf::reorder(&mut KEYS.to_vec(), &seeds, Some(&mut values)).unwrap();
+/// assert_eq!(mphf::get_copied("cat", &seeds, &values), Some(3));
+/// ```
+#[inline]
+pub fn get_copied<T: Copy>(key: &str, seeds: &[u32], values: &[T]) -> Option<T> {
+	get(key, seeds, values).copied()
+}
+/// Gets the value of the given key in the mphf table or the fallback if the key has no index.
+///
+/// Same as [`get`] followed by `unwrap_or(fallback)`.
+/// Like [`get`] the key is not compared, a key outside the table may still get some value.
+///
+/// # Examples
+///
+/// ```
+/// const KEYS: &[&str] = &["hello", "goodbye", "cat", "dog"];
+/// let seeds = mphf::build(KEYS, 2, 10000).unwrap();
+/// let mut values = ["greeting", "farewell", "animal", "animal"];
+/// mphf::reorder(&mut KEYS.to_vec(), &seeds, Some(&mut values)).unwrap();
+/// assert_eq!(*mphf::get_with_fallback("cat", &seeds, &values, &"unknown"), "animal");
+/// assert_eq!(*mphf::get_with_fallback("cat", &[], &values, &"unknown"), "unknown");
+/// ```
+#[inline]
+pub fn get_with_fallback<'a, T>(key: &str, seeds: &[u32], values: &'a [T], fallback: &'a T) -> &'a T {
+	get(key, seeds, values).unwrap_or(fallback)
+}
+/// Gets the value of the given byte string key in the mphf table built by [`build_bytes`].
+#[inline]
+pub fn get_bytes<'a, T>(key: &[u8], seeds: &[u32], values: &'a [T]) -> Option<&'a T> {
+	let index = index_bytes(key, seeds, values.len())?;
+	values.get(index)
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_approximate_space_bytes() {
+	let keys = (0..100).map(|i| format!("key{:02}", i)).collect::<Vec<String>>();
+	let seeds = build(&keys, 25, 100000).unwrap();
+	assert_eq!(approximate_space_bytes(keys.len(), 25, 5), seeds.len() * 4 + keys.iter().map(|key| key.len()).sum::<usize>());
+	assert_eq!(approximate_space_bytes(0, 0, 100), 0);
+	assert_eq!(approximate_space_bytes(usize::MAX, 1, 2), usize::MAX);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_build_verbose() {
+	let keys = (0..100).map(|i| format!("key{}", i)).collect::<Vec<String>>();
+	let (seeds, buckets) = build_verbose(&keys, 30, 100000).unwrap();
+	assert_eq!(seeds, build(&keys, 30, 100000).unwrap());
+	// Every non-empty bucket once, largest first
+	assert_eq!(buckets.len(), seeds.iter().filter(|&&seed| seed != u32::MAX).count());
+	assert_eq!(buckets.iter().map(|bucket| bucket.bucket_size).sum::<usize>(), keys.len());
+	assert!(buckets.windows(2).all(|pair| pair[0].bucket_size >= pair[1].bucket_size));
+	for bucket in &buckets {
+		assert_eq!(seeds[bucket.bucket_index], bucket.seed_found);
+		// The search is sequential from zero
+		assert_eq!(bucket.attempts, bucket.seed_found + 1);
+	}
+	assert_eq!(build_verbose(&["a", "a"], 1, 1000).unwrap_err(), BuildError::SeedNotFound { bucket: 0, bucket_len: 2, max_seed: 1000 });
+	assert_eq!(build_verbose::<&str>(&[], 2, 1000).unwrap().1, []);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_build_report() {
+	// Only the bucket with the duplicate key fails
+	let keys = ["a", "b", "c", "d", "e", "f", "g", "h", "a"];
+	let report = build_report(&keys, 4, 10000).unwrap();
+	assert_eq!(report.buckets.len(), 4);
+	assert_eq!(report.failed(), 1);
+	assert_eq!(report.seeds(), None);
+	let bucket = hash(b"a", 0) as usize % 4;
+	assert!(matches!(report.buckets[bucket], BucketResult::NotFound { len } if len >= 2));
+	assert_eq!(build(&keys, 4, 10000), Err(BuildError::SeedNotFound { bucket, bucket_len: report.buckets.iter().map(|result| match result {
+		BucketResult::NotFound { len } => *len,
+		_ => 0,
+	}).sum(), max_seed: 10000 }));
+
+	let report = build_report(&keys[..8], 8, 10000).unwrap();
+	assert!(report.buckets.contains(&BucketResult::Empty));
+	assert_eq!(report.seeds(), Some(build(&keys[..8], 8, 10000).unwrap()));
+	assert_eq!(build_report(&keys, 0, 10000), Err(BuildError::ZeroSeedsLen));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_build_error_display() {
+	assert_eq!(BuildError::ZeroSeedsLen.to_string(), "seeds_len is zero, it must be at least 1");
+	assert_eq!(BuildError::NotEnoughSlots { keys_len: 3, free: 2 }.to_string(), "3 keys do not fit in the 2 free slots, mark fewer slots as used or make room for every key");
+	assert_eq!(
+		BuildError::SeedNotFound { bucket: 3, bucket_len: 7, max_seed: 10000 }.to_string(),
+		"no seed below max_seed 10000 avoids collisions for the 7 keys of bucket 3, try a larger seeds_len or max_seed, or check for duplicate keys",
+	);
+}
+
+#[test]
+fn test_index_empty() {
+	assert_eq!(index("key", &[], 1), None);
+	assert_eq!(index("key", &[0], 0), None);
+	assert_eq!(get::<u32>("key", &[0], &[]), None);
+	assert_eq!(get_copied::<u32>("key", &[0], &[]), None);
+	assert_eq!(index_bytes(b"key", &[], 1), None);
+	assert_eq!(get_bytes::<u32>(b"key", &[0], &[]), None);
+}
+
+// The first seed tried already works for a single key, no special case needed
+#[cfg(feature = "std")]
+#[test]
+fn test_build_single_key() {
+	let seeds = build(&["only"], 1, 1).unwrap();
+	assert_eq!(seeds.as_slice(), &[0]);
+	assert_eq!(index("only", &seeds, 1), Some(0));
+	let (_, infos) = build_verbose(&["only"], 1, 1).unwrap();
+	assert_eq!(infos.iter().map(|info| info.attempts).collect::<Vec<u32>>(), [1]);
+	// With more buckets the others stay empty
+	let seeds = build(&["only"], 4, 1).unwrap();
+	assert_eq!(seeds.iter().filter(|&&seed| seed == 0).count(), 1);
+	assert_eq!(index("only", &seeds, 1), Some(0));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_build_owned_keys() {
+	use std::{borrow::Cow, sync::Arc};
+	let keys = ["hello", "goodbye", "cat", "dog"];
+	let seeds = build(&keys, 2, 10000).unwrap();
+	let strings = keys.iter().map(|&key| String::from(key)).collect::<Vec<String>>();
+	assert_eq!(build(&strings, 2, 10000).unwrap(), seeds);
+	let arcs = keys.iter().map(|&key| Arc::from(key)).collect::<Vec<Arc<str>>>();
+	assert_eq!(build(&arcs, 2, 10000).unwrap(), seeds);
+	let cows = [Cow::Borrowed("hello"), Cow::Owned(String::from("goodbye")), Cow::Borrowed("cat"), Cow::Borrowed("dog")];
+	assert_eq!(build(&cows, 2, 10000).unwrap(), seeds);
+	assert_eq!(build_seeded(&cows, 2, 10000, 0).unwrap(), seeds);
+	assert_eq!(reorder_indices(&strings, &seeds), reorder_indices(&keys, &seeds));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_build_bytes() {
+	let keys: [&[u8]; 5] = [b"\x00", b"\xff", b"a\x00b", b"\xff\xfe", b"tag"];
+	let seeds = build_bytes(&keys, 2, 10000).unwrap();
+	let mut seen = [false; 5];
+	for &key in &keys {
+		let index = index_bytes(key, &seeds, keys.len()).unwrap();
+		assert!(!seen[index]);
+		seen[index] = true;
+	}
+
+	// Strings and their bytes build the same seeds
+	let keys = ["hello", "goodbye", "cat", "dog"];
+	let bytes = keys.iter().map(|key| key.as_bytes()).collect::<Vec<&[u8]>>();
+	assert_eq!(build_bytes(&bytes, 2, 10000), build(&keys, 2, 10000));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_reorder_pairs() {
+	let keys = ["hello", "goodbye", "cat", "dog"];
+	let seeds = build(&keys, 2, 10000).unwrap();
+	let mut reordered = keys;
+	let mut values = [1, 2, 3, 4];
+	reorder(&mut reordered, &seeds, Some(&mut values)).unwrap();
+
+	let mut pairs = keys.iter().copied().zip(1..).collect::<Box<[(&str, i32)]>>();
+	reorder_pairs(&mut pairs, &seeds).unwrap();
+	assert_eq!(pairs.iter().map(|&(key, _)| key).collect::<Vec<_>>(), reordered);
+	assert_eq!(pairs.iter().map(|&(_, value)| value).collect::<Vec<_>>(), values);
+
+	// Duplicate keys always map to the same index
+	assert_eq!(reorder_pairs(&mut [("b", 1), ("a", 2), ("a", 3)], &build(&["a", "b", "c"], 1, 10000).unwrap()), None);
+	assert_eq!(reorder(&mut ["b", "a", "a"], &build(&["a", "b", "c"], 1, 10000).unwrap(), None::<&mut [()]>), None);
+	assert_eq!(reorder_pairs(&mut [("a", 1)], &[]), None);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_reorder_indices() {
+	let keys = ["hello", "goodbye", "cat", "dog"];
+	let seeds = build(&keys, 2, 10000).unwrap();
+	let perm = reorder_indices(&keys, &seeds).unwrap();
+
+	let mut reordered = keys;
+	let mut values = [1, 2, 3, 4];
+	reorder(&mut reordered, &seeds, Some(&mut values)).unwrap();
+	assert_eq!(perm.iter().map(|&i| keys[i]).collect::<Vec<_>>(), reordered);
+	assert_eq!(perm.iter().map(|&i| i + 1).collect::<Vec<_>>(), values);
+
+	// Duplicate keys always map to the same index
+	assert_eq!(reorder_indices(&["a", "a"], &[0]), None);
+	assert_eq!(reorder_indices(&keys, &[]), None);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_build_into_used() {
+	assert_eq!(build(&["a"], 0, 100), Err(BuildError::ZeroSeedsLen));
+
+	let mut used = vec![true, false, true];
+	assert_eq!(build_into_used(&["a", "b"], 1, 100, &mut used), Err(BuildError::NotEnoughSlots { keys_len: 2, free: 1 }));
+	let seeds = build_into_used(&["a"], 1, 100, &mut used).unwrap();
+	assert_eq!(index("a", &seeds, used.len()), Some(1));
+	assert_eq!(used, [true; 3]);
+
+	// Failing leaves the used slots alone
+	let mut used = vec![false; 2];
+	assert_eq!(build_into_used(&["a", "b"], 1, 0, &mut used), Err(BuildError::SeedNotFound { bucket: 0, bucket_len: 2, max_seed: 0 }));
+	assert_eq!(used, [false; 2]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_build_seeded() {
+	let keys = (0..1000).map(|i| format!("key{}", i)).collect::<Vec<String>>();
+	let keys = keys.iter().map(|key| key.as_str()).collect::<Vec<&str>>();
+	let seeds = build_seeded(&keys, 250, 1_000_000, 42).unwrap();
+	assert_eq!(seeds, build_seeded(&keys, 250, 1_000_000, 42).unwrap());
+	assert_ne!(seeds, build_seeded(&keys, 250, 1_000_000, 43).unwrap());
+	assert_eq!(build_seeded(&keys, 250, 1_000_000, 0), build(&keys, 250, 1_000_000));
+	// The candidates are not limited to 0..max_seed
+	assert!(seeds.iter().any(|&seed| seed != u32::MAX && seed >= 1_000_000));
+	assert_eq!(reorder_indices(&keys, &seeds).map(|perm| perm.len()), Some(keys.len()));
+
+	assert_eq!(build_seeded(&["a", "a"], 1, 1000, 42), Err(BuildError::SeedNotFound { bucket: 0, bucket_len: 2, max_seed: 1000 }));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_build_with_seeds() {
+	let keys = (0..1000).map(|i| format!("key{}", i)).collect::<Vec<String>>();
+	assert_eq!(build_with_seeds(&keys, 250, 0..1_000_000), build(&keys, 250, 1_000_000));
+	let seeds = build_with_seeds(&keys, 250, (0..u32::MAX).rev().take(1_000_000)).unwrap();
+	assert!(seeds.iter().all(|&seed| seed == u32::MAX || seed >= u32::MAX - 1_000_000));
+	assert_eq!(reorder_indices(&keys.iter().map(String::as_str).collect::<Vec<&str>>(), &seeds).map(|perm| perm.len()), Some(keys.len()));
+
+	// u32::MAX is skipped and not counted
+	assert_eq!(build_with_seeds(&["a", "a"], 1, [u32::MAX, 1, 2].iter().copied()), Err(BuildError::SeedsExhausted { bucket: 0, bucket_len: 2, tried: 2 }));
+	assert_eq!(build_with_seeds(&["a", "b"], 1, None.into_iter()), Err(BuildError::SeedsExhausted { bucket: 0, bucket_len: 2, tried: 0 }));
+	assert_eq!(build_with_seeds(&["a"], 0, 0..10), Err(BuildError::ZeroSeedsLen));
+}
+
+#[cfg(feature = "log")]
+#[test]
+fn test_expected_attempts() {
+	assert_eq!(expected_attempts(&[(0, vec!["a"])], &[false]), 1.0);
+	// The second bucket only has one free slot left out of two
+	assert_eq!(expected_attempts(&[(0, vec!["a"]), (1, vec!["b"])], &[false; 2]), 3.0);
+	// Both keys in one bucket must land in distinct slots
+	assert_eq!(expected_attempts(&[(0, vec!["a", "b"]), (1, vec![])], &[false; 2]), 2.0);
+	// Slots taken by other tables
+	assert_eq!(expected_attempts(&[(0, vec!["a"])], &[true, false]), 2.0);
+}
@@ -0,0 +1,220 @@
+use super::hash;
+
+/// Builds a static lookup table at compile time.
+///
+/// Expands to a module with the seeds, keys and values in mphf order along with the lookup functions:
+///
+/// * `get(key) -> Option<&'static V>` returns the value of the key.
+/// * `contains(key) -> bool` returns true if the key is in the table.
+/// * `index(key) -> Option<usize>` returns the index of the key in `KEYS` and `VALUES`.
+/// * `iter()` iterates over the keys and values in mphf order.
+///
+/// Lookups compare the key, keys outside the set return `None`.
+/// The values are `&'static str` unless a type is given after the name, the type must be `Copy`.
+///
+/// The seeds are bruteforced in `const` context, duplicate keys fail to compile.
+/// Intended for small tables, large tables are built faster with the `codegen` module in a build script.
+///
+/// # Examples
+///
+/// ```
+/// mphf::lookup_table!(colors => {
+/// 	"red" => "#ff0000",
+/// 	"green" => "#00ff00",
+/// 	"blue" => "#0000ff",
+/// });
+///
+/// mphf::lookup_table!(pub ports: u16 => {
+/// 	"http" => 80,
+/// 	"https" => 443,
+/// });
+///
+/// assert_eq!(colors::get("green"), Some(&"#00ff00"));
+/// assert_eq!(colors::get("yellow"), None);
+/// assert!(ports::contains("https"));
+/// assert_eq!(ports::iter().count(), 2);
+/// ```
+#[macro_export]
+macro_rules! lookup_table {
+	($vis:vis $name:ident => { $($key:expr => $value:expr),* $(,)? }) => {
+		$crate::lookup_table!($vis $name: &'static str => { $($key => $value),* });
+	};
+	($vis:vis $name:ident: $ty:ty => { $($key:expr => $value:expr),* $(,)? }) => {
+		$vis mod $name {
+			#[allow(unused_imports)]
+			use super::*;
+
+			const LEN: usize = <[&str]>::len(&[$($key),*]);
+			const SEEDS_LEN: usize = $crate::__lookup_table::seeds_len(LEN);
+			const INPUT_KEYS: [&str; LEN] = [$($key),*];
+			const INPUT_VALUES: [$ty; LEN] = [$($value),*];
+			const BUILT_SEEDS: [u32; SEEDS_LEN] = $crate::__lookup_table::build(&INPUT_KEYS);
+
+			pub static SEEDS: [u32; SEEDS_LEN] = BUILT_SEEDS;
+			pub static KEYS: [&str; LEN] = $crate::__lookup_table::reorder(&INPUT_KEYS, &INPUT_KEYS, &BUILT_SEEDS);
+			pub static VALUES: [$ty; LEN] = $crate::__lookup_table::reorder(&INPUT_KEYS, &INPUT_VALUES, &BUILT_SEEDS);
+
+			#[allow(dead_code)]
+			#[inline]
+			pub fn index(key: &str) -> Option<usize> {
+				let index = $crate::index(key, &SEEDS, LEN)?;
+				if KEYS[index] != key {
+					return None;
+				}
+				Some(index)
+			}
+			#[allow(dead_code)]
+			#[inline]
+			pub fn contains(key: &str) -> bool {
+				index(key).is_some()
+			}
+			#[allow(dead_code)]
+			#[inline]
+			pub fn get(key: &str) -> Option<&'static $ty> {
+				Some(&VALUES[index(key)?])
+			}
+			#[allow(dead_code)]
+			#[inline]
+			pub fn iter() -> impl Iterator<Item = (&'static str, &'static $ty)> {
+				KEYS.iter().copied().zip(VALUES.iter())
+			}
+		}
+	};
+}
+
+// Brute force budget per bucket, the buckets are small enough this is never reached for distinct keys in practice.
+const MAX_SEED: u32 = 1 << 24;
+
+/// Number of seeds for a table of `len` keys.
+#[doc(hidden)]
+pub const fn seeds_len(len: usize) -> usize {
+	if len < 2 { 1 } else { len / 2 }
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+	let (a, b) = (a.as_bytes(), b.as_bytes());
+	if a.len() != b.len() {
+		return false;
+	}
+	let mut i = 0;
+	while i < a.len() {
+		if a[i] != b[i] {
+			return false;
+		}
+		i += 1;
+	}
+	true
+}
+
+/// Builds the seeds in `const` context.
+///
+/// Produces the same seeds as [`build`](crate::build) with the same `seeds_len`.
+#[doc(hidden)]
+pub const fn build<const N: usize, const S: usize>(keys: &[&str; N]) -> [u32; S] {
+	// Duplicate keys can never be disambiguated
+	let mut i = 0;
+	while i < N {
+		let mut j = i + 1;
+		while j < N {
+			if str_eq(keys[i], keys[j]) {
+				panic!("lookup_table: duplicate key");
+			}
+			j += 1;
+		}
+		i += 1;
+	}
+
+	let mut buckets = [0usize; N];
+	let mut sizes = [0usize; S];
+	let mut i = 0;
+	while i < N {
+		buckets[i] = hash(keys[i].as_bytes(), 0) as usize % S;
+		sizes[buckets[i]] += 1;
+		i += 1;
+	}
+
+	let mut seeds = [u32::MAX; S];
+	let mut done = [false; S];
+	let mut used = [false; N];
+	loop {
+		// Same order as the runtime build: largest buckets first, ties broken by the highest index
+		let mut bucket = S;
+		let mut b = 0;
+		while b < S {
+			if !done[b] && sizes[b] > 0 && (bucket == S || sizes[b] >= sizes[bucket]) {
+				bucket = b;
+			}
+			b += 1;
+		}
+		if bucket == S {
+			break;
+		}
+		done[bucket] = true;
+
+		let mut seed = 0;
+		loop {
+			if seed == MAX_SEED {
+				panic!("lookup_table: no seed found");
+			}
+			let mut tmp = used;
+			let mut ok = true;
+			let mut i = 0;
+			while i < N {
+				if buckets[i] == bucket {
+					let h = hash(keys[i].as_bytes(), seed) as usize % N;
+					if tmp[h] {
+						ok = false;
+						break;
+					}
+					tmp[h] = true;
+				}
+				i += 1;
+			}
+			if ok {
+				used = tmp;
+				seeds[bucket] = seed;
+				break;
+			}
+			seed += 1;
+		}
+	}
+	seeds
+}
+
+/// Moves the items in mphf order of their keys.
+#[doc(hidden)]
+pub const fn reorder<T: Copy, const N: usize, const S: usize>(keys: &[&str; N], items: &[T; N], seeds: &[u32; S]) -> [T; N] {
+	let mut out = *items;
+	let mut i = 0;
+	while i < N {
+		let key = keys[i].as_bytes();
+		let seed = seeds[hash(key, 0) as usize % S];
+		out[hash(key, seed) as usize % N] = items[i];
+		i += 1;
+	}
+	out
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_lookup_table() {
+	lookup_table!(numbers: i32 => {
+		"one" => 1, "two" => 2, "three" => 3, "four" => 4, "five" => 5,
+		"six" => 6, "seven" => 7, "eight" => 8, "nine" => 9, "ten" => 10,
+	});
+	for (key, &value) in numbers::iter() {
+		assert_eq!(numbers::get(key), Some(&value));
+	}
+	assert_eq!(numbers::get("seven"), Some(&7));
+	assert_eq!(numbers::get("eleven"), None);
+	assert_eq!(numbers::index("zero"), None);
+
+	// Same seeds as the runtime build
+	let keys = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten"];
+	assert_eq!(crate::build(&keys, seeds_len(keys.len()), MAX_SEED).unwrap().as_slice(), &numbers::SEEDS);
+
+	lookup_table!(empty => {});
+	assert_eq!(empty::get("key"), None);
+	lookup_table!(single => { "key" => "value" });
+	assert_eq!(single::get("key"), Some(&"value"));
+}
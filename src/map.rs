@@ -0,0 +1,862 @@
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use super::{BuildError, Mphf, Seeds};
+
+/// Map from a static set of string keys to values.
+///
+/// Built once with a Minimally Perfect Hash Function, the keys and values are stored in mphf order.
+/// Lookups compare the key so keys outside the set return `None`.
+/// The map remembers its `max_seed` to rebuild the mphf when entries are removed.
+///
+/// The keys are any type which borrows as `str`, eg. `&str`, `String`, `Box<str>` or `Arc<str>` shared with other threads.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MphfMap<K, V> {
+	mphf: Mphf,
+	max_seed: u32,
+	keys: Vec<K>,
+	values: Vec<V>,
+}
+
+impl<K: AsRef<str>, V> MphfMap<K, V> {
+	/// Builds the map from its entries.
+	///
+	/// See [`build`](crate::build) for the meaning of the arguments.
+	/// Duplicate keys cannot be disambiguated and fail to build.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let map = mphf::MphfMap::build(vec![("cat", 4), ("bird", 2), ("snake", 0)], 1, 100000).unwrap();
+	/// assert_eq!(map.get("cat"), Some(&4));
+	/// assert_eq!(map.get("dog"), None);
+	/// ```
+	pub fn build<I: IntoIterator<Item = (K, V)>>(entries: I, seeds_len: usize, max_seed: u32) -> Result<MphfMap<K, V>, BuildError> {
+		let (keys, values): (Vec<K>, Vec<V>) = entries.into_iter().unzip();
+		let mphf = Mphf::build(&keys, seeds_len, max_seed)?;
+		let mut map = MphfMap { mphf, max_seed, keys, values };
+		map.reorder();
+		Ok(map)
+	}
+
+	/// Retains only the entries for which the predicate returns true.
+	///
+	/// Removing entries rebuilds the mphf with the same number of seeds and `max_seed` the map was built with.
+	/// The map is left unchanged if the rebuild fails.
+	pub fn retain<F: FnMut(&K, &V) -> bool>(&mut self, mut pred: F) -> Result<(), BuildError> {
+		let keep = self.iter().map(|(key, value)| pred(key, value)).collect::<Vec<bool>>();
+		if keep.iter().all(|&keep| keep) {
+			return Ok(());
+		}
+		let mphf = {
+			let keys = self.keys.iter().zip(&keep).filter(|&(_, &keep)| keep).map(|(key, _)| key.as_ref()).collect::<Vec<&str>>();
+			Mphf::build(&keys, self.mphf.seeds().len(), self.max_seed)?
+		};
+		let mut kept = keep.iter();
+		self.keys.retain(|_| *kept.next().unwrap());
+		let mut kept = keep.iter();
+		self.values.retain(|_| *kept.next().unwrap());
+		self.mphf = mphf;
+		self.reorder();
+		Ok(())
+	}
+
+	// Moves the entries in mphf order.
+	fn reorder(&mut self) {
+		for i in 0..self.keys.len() {
+			loop {
+				let j = self.mphf.index(self.keys[i].as_ref()).unwrap();
+				if i == j {
+					break;
+				}
+				self.keys.swap(i, j);
+				self.values.swap(i, j);
+			}
+		}
+	}
+
+	/// Returns the index of the given key.
+	#[inline]
+	pub fn index(&self, key: &str) -> Option<usize> {
+		let index = self.mphf.index(key)?;
+		if self.keys[index].as_ref() != key {
+			return None;
+		}
+		Some(index)
+	}
+	/// Returns true if the map contains the key.
+	#[inline]
+	pub fn contains_key(&self, key: &str) -> bool {
+		self.index(key).is_some()
+	}
+	/// Gets the value of the given key.
+	#[inline]
+	pub fn get(&self, key: &str) -> Option<&V> {
+		let index = self.index(key)?;
+		Some(&self.values[index])
+	}
+	/// Gets the value of the given key mutably.
+	#[inline]
+	pub fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+		let index = self.index(key)?;
+		Some(&mut self.values[index])
+	}
+	/// Gets the values of N keys mutably at once.
+	///
+	/// Returns `None` if any key is not in the map or if the same key is given more than once.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut map = mphf::MphfMap::build(vec![("alice", 10), ("bob", 5)], 1, 100000).unwrap();
+	/// if let Some([alice, bob]) = map.get_disjoint_mut(["alice", "bob"]) {
+	/// 	*alice -= 3;
+	/// 	*bob += 3;
+	/// }
+	/// assert_eq!(map.get("bob"), Some(&8));
+	/// assert!(map.get_disjoint_mut(["alice", "alice"]).is_none());
+	/// ```
+	pub fn get_disjoint_mut<const N: usize>(&mut self, keys: [&str; N]) -> Option<[&mut V; N]> {
+		let mut indices = [0; N];
+		for (index, key) in indices.iter_mut().zip(keys) {
+			*index = self.index(key)?;
+		}
+		self.values.get_disjoint_mut(indices).ok()
+	}
+
+	/// Builds a map over some of the keys borrowing the entries of this map.
+	///
+	/// The number of seeds keeps the ratio of seeds to keys of this map, the `max_seed` is the same.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let animals = mphf::MphfMap::build(vec![("cat", 4), ("bird", 2), ("snake", 0), ("spider", 8)], 2, 100000).unwrap();
+	/// let pets = animals.subset(&["cat", "snake"]).unwrap();
+	/// assert_eq!(pets.get("cat"), Some(&&4));
+	/// assert_eq!(pets.get("bird"), None);
+	/// assert_eq!(animals.subset(&["cat", "dog"]).unwrap_err(), mphf::SubsetError::MissingKey { index: 1 });
+	/// ```
+	pub fn subset(&self, keys: &[&str]) -> Result<MphfMap<&K, &V>, SubsetError> {
+		let mut seen = vec![false; self.len()];
+		let mut entries = Vec::with_capacity(keys.len());
+		for (index, &key) in keys.iter().enumerate() {
+			let i = self.index(key).ok_or(SubsetError::MissingKey { index })?;
+			if seen[i] {
+				return Err(SubsetError::DuplicateKey { index });
+			}
+			seen[i] = true;
+			entries.push((&self.keys[i], &self.values[i]));
+		}
+		let seeds_len = usize::max(1, self.mphf.seeds().len() * keys.len() / usize::max(1, self.len()));
+		MphfMap::build(entries, seeds_len, self.max_seed).map_err(SubsetError::Build)
+	}
+}
+
+/// Error building a subset of a map, see [`MphfMap::subset`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SubsetError {
+	/// The key at `index` of the requested keys is not in the map.
+	MissingKey { index: usize },
+	/// The key at `index` of the requested keys is requested before.
+	DuplicateKey { index: usize },
+	/// Building the mphf over the subset failed.
+	Build(BuildError),
+}
+
+impl std::fmt::Display for SubsetError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			SubsetError::MissingKey { index } => write!(f, "key {} of the subset is not in the map", index),
+			SubsetError::DuplicateKey { index } => write!(f, "key {} of the subset is a duplicate", index),
+			SubsetError::Build(error) => write!(f, "failed to build the subset: {}", error),
+		}
+	}
+}
+
+impl std::error::Error for SubsetError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			SubsetError::Build(error) => Some(error),
+			_ => None,
+		}
+	}
+}
+
+impl<K, V> MphfMap<K, V> {
+	/// Returns the mphf over the keys.
+	#[inline]
+	pub fn mphf(&self) -> &Mphf {
+		&self.mphf
+	}
+	/// Returns the maximum seed used to rebuild the mphf.
+	#[inline]
+	pub fn max_seed(&self) -> u32 {
+		self.max_seed
+	}
+	/// Returns the number of entries.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.keys.len()
+	}
+	/// Returns true if the map has no entries.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.keys.is_empty()
+	}
+	/// Returns the keys in mphf order.
+	#[inline]
+	pub fn keys(&self) -> &[K] {
+		&self.keys
+	}
+	/// Returns the values in mphf order.
+	#[inline]
+	pub fn values(&self) -> &[V] {
+		&self.values
+	}
+	/// Returns the entries in mphf order.
+	#[inline]
+	pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+		self.keys.iter().zip(self.values.iter())
+	}
+	/// Transforms the values keeping the keys and the mphf.
+	///
+	/// Nothing is rehashed, the new values stay in the mphf order of their keys.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let map = mphf::MphfMap::build(vec![("cat", "4"), ("bird", "2")], 1, 100000).unwrap();
+	/// let map = map.map_values(|legs| legs.parse::<u32>().unwrap());
+	/// assert_eq!(map.get("cat"), Some(&4));
+	/// ```
+	pub fn map_values<U, F: FnMut(V) -> U>(self, f: F) -> MphfMap<K, U> {
+		MphfMap {
+			mphf: self.mphf,
+			max_seed: self.max_seed,
+			keys: self.keys,
+			values: self.values.into_iter().map(f).collect(),
+		}
+	}
+}
+
+/// An empty map, the same as collecting no entries.
+impl<K, V> Default for MphfMap<K, V> {
+	fn default() -> MphfMap<K, V> {
+		// A single empty bucket is a valid table over no keys
+		let mphf = Mphf::from_seeds(Seeds::from(vec![u32::MAX]), 0).unwrap();
+		MphfMap { mphf, max_seed: 1_000_000, keys: Vec::new(), values: Vec::new() }
+	}
+}
+
+/// Hashes the entries sorted by key.
+///
+/// The hash depends only on the entries, maps with the same entries hash the same however their mphf was built.
+/// Equal maps have the same entries so they hash the same.
+impl<K: AsRef<str>, V: Hash> Hash for MphfMap<K, V> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		let mut order = (0..self.keys.len()).collect::<Vec<usize>>();
+		order.sort_unstable_by(|&a, &b| self.keys[a].as_ref().cmp(self.keys[b].as_ref()));
+		state.write_usize(order.len());
+		for i in order {
+			self.keys[i].as_ref().hash(state);
+			self.values[i].hash(state);
+		}
+	}
+}
+
+/// Collects the entries with a quarter as many seeds as keys and a `max_seed` of `1_000_000`.
+///
+/// # Panics
+///
+/// Panics if the mphf cannot be built, eg. with duplicate keys.
+/// Use [`MphfMap::build`] to handle the error.
+///
+/// # Examples
+///
+/// ```
+/// let map: mphf::MphfMap<_, _> = vec![("cat", 4), ("bird", 2), ("snake", 0)].into_iter().collect();
+/// assert_eq!(map.get("bird"), Some(&2));
+/// ```
+impl<K: AsRef<str>, V> FromIterator<(K, V)> for MphfMap<K, V> {
+	fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> MphfMap<K, V> {
+		let entries = iter.into_iter().collect::<Vec<(K, V)>>();
+		let seeds_len = usize::max(1, entries.len() / 4);
+		let keys_len = entries.len();
+		match MphfMap::build(entries, seeds_len, 1_000_000) {
+			Ok(map) => map,
+			Err(err) => panic!("failed to build MphfMap over {} keys with {} seeds: {:?}", keys_len, seeds_len, err),
+		}
+	}
+}
+
+#[cfg(feature = "postcard")]
+mod persist {
+	use std::io;
+	use crate::binary::{DecodeError, LoadError};
+	use crate::crc32::Crc32;
+	use super::*;
+
+	const MAGIC: [u8; 4] = *b"MPHM";
+	const VERSION: u8 = 2;
+	const MAX_SECTION_LEN: usize = 1 << 28;
+
+	fn write_section<W: io::Write>(w: &mut W, crc: &mut Crc32, bytes: &[u8]) -> io::Result<()> {
+		let len = (bytes.len() as u32).to_le_bytes();
+		crc.update(&len);
+		crc.update(bytes);
+		w.write_all(&len)?;
+		w.write_all(bytes)
+	}
+	fn read_section<R: io::Read>(r: &mut R, crc: &mut Crc32) -> Result<Vec<u8>, LoadError> {
+		let mut len = [0u8; 4];
+		r.read_exact(&mut len)?;
+		crc.update(&len);
+		let len = u32::from_le_bytes(len) as usize;
+		if len > MAX_SECTION_LEN {
+			return Err(DecodeError::TooLarge(len).into());
+		}
+		let mut bytes = vec![0u8; len];
+		r.read_exact(&mut bytes)?;
+		crc.update(&bytes);
+		Ok(bytes)
+	}
+
+	fn encode_keys<K: AsRef<str>>(keys: &[K]) -> Vec<u8> {
+		let mut bytes = Vec::new();
+		for key in keys {
+			let key = key.as_ref().as_bytes();
+			bytes.extend_from_slice(&(key.len() as u32).to_le_bytes());
+			bytes.extend_from_slice(key);
+		}
+		bytes
+	}
+	fn decode_keys<K: From<String>>(mut bytes: &[u8], len: usize) -> Result<Vec<K>, DecodeError> {
+		let mut keys = Vec::with_capacity(len.min(bytes.len() / 4));
+		while !bytes.is_empty() {
+			if bytes.len() < 4 {
+				return Err(DecodeError::Truncated);
+			}
+			let key_len = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize;
+			let end = key_len.checked_add(4).ok_or(DecodeError::Truncated)?;
+			let key = bytes.get(4..end).ok_or(DecodeError::Truncated)?;
+			let key = std::str::from_utf8(key).map_err(|_| DecodeError::Invalid)?;
+			keys.push(K::from(String::from(key)));
+			bytes = &bytes[end..];
+		}
+		Ok(keys)
+	}
+
+	/// Persistence of the whole map, requires the `postcard` feature.
+	///
+	/// The map is stored as:
+	///
+	/// * Magic bytes `MPHM`, the format version `2` and three reserved zero bytes.
+	/// * The `max_seed` used to rebuild the mphf, not present in version `1`.
+	/// * The mphf table in the [binary format](crate::binary) with the digest of the keys.
+	/// * The keys section: its length in bytes followed by each key as its length and UTF-8 bytes.
+	/// * The values section: its length in bytes followed by the values encoded with [postcard](https://docs.rs/postcard).
+	/// * The CRC-32 of everything except the mphf table, which has its own checksum.
+	///
+	/// All lengths are 32-bit little-endian and the keys and values are in mphf order.
+	impl<K: AsRef<str>, V: serde::Serialize> MphfMap<K, V> {
+		/// Writes the map including its keys and values.
+		pub fn save_to<W: io::Write>(&self, mut w: W) -> io::Result<()> {
+			let mut crc = Crc32::new();
+			let header = [MAGIC[0], MAGIC[1], MAGIC[2], MAGIC[3], VERSION, 0, 0, 0];
+			crc.update(&header);
+			w.write_all(&header)?;
+			let max_seed = self.max_seed.to_le_bytes();
+			crc.update(&max_seed);
+			w.write_all(&max_seed)?;
+			self.mphf.write_to_with_keys(&self.keys, &mut w)?;
+			write_section(&mut w, &mut crc, &encode_keys(&self.keys))?;
+			let values = postcard::to_allocvec(&self.values).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+			write_section(&mut w, &mut crc, &values)?;
+			w.write_all(&crc.finish().to_le_bytes())
+		}
+	}
+
+	impl<K: AsRef<str> + From<String>, V: serde::de::DeserializeOwned> MphfMap<K, V> {
+		/// Reads a map written by [`save_to`](MphfMap::save_to).
+		///
+		/// The keys are verified against the digest recorded in the table and every key must map to its own index,
+		/// a table mismatched with its keys is rejected.
+		/// Maps written in version `1` did not store their `max_seed`, they are rebuilt with the largest possible `max_seed`.
+		pub fn load_from<R: io::Read>(mut r: R) -> Result<MphfMap<K, V>, LoadError> {
+			let mut crc = Crc32::new();
+			let mut header = [0u8; 8];
+			r.read_exact(&mut header)?;
+			crc.update(&header);
+			if header[0..4] != MAGIC {
+				return Err(DecodeError::BadMagic.into());
+			}
+			if header[4] != 1 && header[4] != VERSION {
+				return Err(DecodeError::UnsupportedVersion(header[4]).into());
+			}
+			if header[5..8] != [0, 0, 0] {
+				return Err(DecodeError::Invalid.into());
+			}
+			let max_seed = if header[4] >= 2 {
+				let mut max_seed = [0u8; 4];
+				r.read_exact(&mut max_seed)?;
+				crc.update(&max_seed);
+				u32::from_le_bytes(max_seed)
+			}
+			else {
+				u32::MAX
+			};
+
+			let (mphf, digest) = Mphf::read(&mut r, &crate::binary::Limits::default())?;
+			let keys = read_section(&mut r, &mut crc)?;
+			let values = read_section(&mut r, &mut crc)?;
+			let mut checksum = [0u8; 4];
+			r.read_exact(&mut checksum)?;
+			if u32::from_le_bytes(checksum) != crc.finish() {
+				return Err(DecodeError::ChecksumMismatch.into());
+			}
+
+			let keys = decode_keys::<K>(&keys, mphf.len())?;
+			let values = postcard::from_bytes::<Vec<V>>(&values).map_err(|_| DecodeError::Invalid)?;
+			if keys.len() != mphf.len() || values.len() != mphf.len() {
+				return Err(DecodeError::Invalid.into());
+			}
+			if digest != 0 && crate::binary::key_digest(&keys) != digest {
+				return Err(DecodeError::KeyMismatch.into());
+			}
+			for (i, key) in keys.iter().enumerate() {
+				if mphf.index(key.as_ref()) != Some(i) {
+					return Err(DecodeError::Invalid.into());
+				}
+			}
+			Ok(MphfMap { mphf, max_seed, keys, values })
+		}
+	}
+}
+
+#[cfg(feature = "serde")]
+mod json {
+	use std::fmt;
+	use std::marker::PhantomData;
+	use serde::de::{self, Deserialize, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+	use serde::ser::{Serialize, SerializeStruct, Serializer};
+	use crate::binary::DecodeError;
+	use crate::Seeds;
+	use super::*;
+
+	const VERSION: u64 = 1;
+	const HASH: &str = "murmur3_32";
+	const FIELDS: &[&str] = &["version", "hash", "max_seed", "seeds", "keys", "values"];
+
+	struct Keys<'a, K>(&'a [K]);
+
+	impl<'a, K: AsRef<str>> Serialize for Keys<'a, K> {
+		fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+			serializer.collect_seq(self.0.iter().map(|key| key.as_ref()))
+		}
+	}
+
+	/// Serializes the fields in the documented order, requires the `serde` feature.
+	impl<K: AsRef<str>, V: Serialize> Serialize for MphfMap<K, V> {
+		fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+			let mut state = serializer.serialize_struct("MphfMap", FIELDS.len())?;
+			state.serialize_field("version", &VERSION)?;
+			state.serialize_field("hash", HASH)?;
+			state.serialize_field("max_seed", &self.max_seed)?;
+			state.serialize_field("seeds", self.mphf.seeds().as_slice())?;
+			state.serialize_field("keys", &Keys(&self.keys))?;
+			state.serialize_field("values", &self.values)?;
+			state.end()
+		}
+	}
+
+	// Validates the deserialized fields and moves the entries to their index.
+	fn assemble<K: AsRef<str> + From<String>, V, E: de::Error>(version: u64, hash: String, max_seed: u32, seeds: Vec<u32>, keys: Vec<String>, values: Vec<V>) -> Result<MphfMap<K, V>, E> {
+		if version != VERSION {
+			return Err(E::custom(format_args!("unsupported mphf format version {}", version)));
+		}
+		if hash != HASH {
+			return Err(E::custom(format_args!("unsupported mphf hash kind {:?}", hash)));
+		}
+		if keys.len() != values.len() {
+			return Err(E::custom(format_args!("{} keys but {} values", keys.len(), values.len())));
+		}
+		let mphf = Mphf::from_seeds(Seeds::from(seeds), keys.len()).ok_or_else(|| E::custom(DecodeError::Invalid))?;
+		let mut used = vec![false; keys.len()];
+		for key in &keys {
+			match mphf.index(key) {
+				Some(index) if !used[index] => used[index] = true,
+				_ => return Err(E::custom(format_args!("key {:?} does not map to a unique index", key))),
+			}
+		}
+
+		let keys = keys.into_iter().map(K::from).collect();
+		let mut map = MphfMap { mphf, max_seed, keys, values };
+		map.reorder();
+		Ok(map)
+	}
+
+	fn element<'de, T: Deserialize<'de>, A: SeqAccess<'de>>(seq: &mut A, index: usize) -> Result<T, A::Error> {
+		seq.next_element()?.ok_or_else(|| de::Error::invalid_length(index, &"an mphf map with 6 fields"))
+	}
+
+	struct MapVisitor<K, V>(PhantomData<(K, V)>);
+
+	impl<'de, K: AsRef<str> + From<String>, V: Deserialize<'de>> Visitor<'de> for MapVisitor<K, V> {
+		type Value = MphfMap<K, V>;
+
+		fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			f.write_str("an mphf map")
+		}
+
+		// Formats which are not self-describing store the fields in order
+		fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<MphfMap<K, V>, A::Error> {
+			let version = element(&mut seq, 0)?;
+			let hash = element(&mut seq, 1)?;
+			let max_seed = element(&mut seq, 2)?;
+			let seeds = element(&mut seq, 3)?;
+			let keys = element(&mut seq, 4)?;
+			let values = element(&mut seq, 5)?;
+			assemble(version, hash, max_seed, seeds, keys, values)
+		}
+
+		fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<MphfMap<K, V>, A::Error> {
+			let (mut version, mut hash, mut max_seed, mut seeds, mut keys, mut values) = (None, None, None, None, None, None);
+			while let Some(name) = map.next_key::<String>()? {
+				match &*name {
+					"version" => version = Some(map.next_value()?),
+					"hash" => hash = Some(map.next_value()?),
+					"max_seed" => max_seed = Some(map.next_value()?),
+					"seeds" => seeds = Some(map.next_value()?),
+					"keys" => keys = Some(map.next_value()?),
+					"values" => values = Some(map.next_value()?),
+					_ => {
+						map.next_value::<IgnoredAny>()?;
+					},
+				}
+			}
+			let version = version.ok_or_else(|| de::Error::missing_field("version"))?;
+			let hash = hash.ok_or_else(|| de::Error::missing_field("hash"))?;
+			let seeds = seeds.ok_or_else(|| de::Error::missing_field("seeds"))?;
+			let keys = keys.ok_or_else(|| de::Error::missing_field("keys"))?;
+			let values = values.ok_or_else(|| de::Error::missing_field("values"))?;
+			assemble(version, hash, max_seed.unwrap_or(u32::MAX), seeds, keys, values)
+		}
+	}
+
+	/// Deserializes and verifies a serialized map, requires the `serde` feature.
+	///
+	/// The keys may be listed in any order, each entry is moved to the index its key maps to.
+	/// The seeds are verified to map every key to a unique index, seeds which do not belong to the keys are rejected.
+	/// The `max_seed` is optional and defaults to the largest possible `max_seed`.
+	impl<'de, K: AsRef<str> + From<String>, V: Deserialize<'de>> Deserialize<'de> for MphfMap<K, V> {
+		fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<MphfMap<K, V>, D::Error> {
+			deserializer.deserialize_struct("MphfMap", FIELDS, MapVisitor(PhantomData))
+		}
+	}
+
+	/// JSON export of the whole map for debugging and tooling, requires the `json` feature.
+	///
+	/// The map is stored as an object with the format version, the hash kind, the `max_seed`, the seeds and the keys and values in mphf order:
+	///
+	/// ```text
+	/// {"version":1,"hash":"murmur3_32","max_seed":100000,"seeds":[...],"keys":[...],"values":[...]}
+	/// ```
+	///
+	/// The same fields are used by the `Serialize` and `Deserialize` implementations with other formats.
+	#[cfg(feature = "json")]
+	impl<K: AsRef<str>, V: Serialize> MphfMap<K, V> {
+		/// Exports the map as JSON.
+		pub fn to_json_string(&self) -> serde_json::Result<String> {
+			serde_json::to_string(self)
+		}
+	}
+
+	#[cfg(feature = "json")]
+	impl<K: AsRef<str> + From<String>, V: serde::de::DeserializeOwned> MphfMap<K, V> {
+		/// Imports a map exported by [`to_json_string`](MphfMap::to_json_string).
+		///
+		/// See the `Deserialize` implementation for the validation.
+		pub fn from_json_str(s: &str) -> serde_json::Result<MphfMap<K, V>> {
+			serde_json::from_str(s)
+		}
+	}
+}
+
+#[test]
+fn test_map() {
+	let map = MphfMap::build(vec![("hello", 1), ("goodbye", 2), ("cat", 3), ("dog", 4)], 2, 10000).unwrap();
+	assert_eq!(map.len(), 4);
+	assert_eq!(map.get("hello"), Some(&1));
+	assert_eq!(map.get("dog"), Some(&4));
+	assert_eq!(map.get("bird"), None);
+	assert!(map.contains_key("cat"));
+	for (i, (&key, _)) in map.iter().enumerate() {
+		assert_eq!(map.index(key), Some(i));
+	}
+
+	let mut map = map;
+	*map.get_mut("cat").unwrap() = 30;
+	assert_eq!(map.get("cat"), Some(&30));
+
+	assert_eq!(MphfMap::build(vec![("a", 1), ("a", 2)], 1, 1000).err(), Some(BuildError::SeedNotFound { bucket: 0, bucket_len: 2, max_seed: 1000 }));
+}
+
+#[test]
+fn test_map_from_iter() {
+	let map = (0..1000).map(|i| (format!("key{}", i), i)).collect::<MphfMap<String, i32>>();
+	assert_eq!(map.len(), 1000);
+	assert_eq!(map.mphf().seeds().len(), 250);
+	assert_eq!(map.max_seed(), 1_000_000);
+	for i in 0..1000 {
+		assert_eq!(map.get(&format!("key{}", i)), Some(&i));
+	}
+
+	let empty = std::iter::empty::<(&str, i32)>().collect::<MphfMap<_, _>>();
+	assert!(empty.is_empty());
+	assert_eq!(empty.get("key"), None);
+}
+
+#[test]
+#[should_panic(expected = "failed to build MphfMap")]
+fn test_map_from_iter_duplicate() {
+	let _ = vec![("a", 1), ("a", 2)].into_iter().collect::<MphfMap<_, _>>();
+}
+
+#[test]
+fn test_map_values() {
+	let map = (0..100).map(|i| (format!("key{}", i), i.to_string())).collect::<MphfMap<String, String>>();
+	let mphf = map.mphf().clone();
+	let map = map.map_values(|value| value.parse::<i32>().unwrap() * 2);
+	assert_eq!(map.mphf(), &mphf);
+	assert_eq!(map.max_seed(), 1_000_000);
+	for i in 0..100 {
+		assert_eq!(map.get(&format!("key{}", i)), Some(&(i * 2)));
+	}
+}
+
+#[test]
+fn test_map_get_disjoint_mut() {
+	let mut map = (0..100).map(|i| (format!("key{}", i), i)).collect::<MphfMap<String, i32>>();
+	let [a, b, c] = map.get_disjoint_mut(["key1", "key50", "key99"]).unwrap();
+	std::mem::swap(a, c);
+	*b = -1;
+	assert_eq!((map.get("key1"), map.get("key50"), map.get("key99")), (Some(&99), Some(&-1), Some(&1)));
+	assert!(map.get_disjoint_mut(["key1", "key100"]).is_none());
+	assert!(map.get_disjoint_mut(["key2", "key3", "key2"]).is_none());
+	assert!(map.get_disjoint_mut::<0>([]).is_some());
+}
+
+#[test]
+fn test_map_arc_keys() {
+	use std::sync::Arc;
+
+	// Interned keys shared with the rest of the program
+	let keys = (0..100).map(|i| Arc::<str>::from(format!("key{}", i))).collect::<Vec<Arc<str>>>();
+	let map = keys.iter().cloned().zip(0..).collect::<MphfMap<Arc<str>, u32>>();
+	assert!(map.keys().iter().all(|key| Arc::strong_count(key) == 2));
+
+	std::thread::scope(|scope| {
+		for offset in 0..4 {
+			let (map, keys) = (&map, &keys);
+			scope.spawn(move || {
+				for (i, key) in keys.iter().enumerate().skip(offset) {
+					assert_eq!(map.get(key), Some(&(i as u32)));
+				}
+				assert_eq!(map.get("key100"), None);
+			});
+		}
+	});
+}
+
+#[test]
+fn test_map_subset() {
+	let entries = (0..100).map(|i| (format!("key{}", i), i)).collect::<Vec<(String, i32)>>();
+	let map = MphfMap::build(entries, 25, 100000).unwrap();
+	let keys = (0..100).filter(|i| i % 4 == 0).map(|i| format!("key{}", i)).collect::<Vec<String>>();
+	let keys = keys.iter().map(String::as_str).collect::<Vec<&str>>();
+	let subset = map.subset(&keys).unwrap();
+	assert_eq!(subset.len(), 25);
+	assert_eq!(subset.mphf().seeds().len(), 6);
+	assert_eq!(subset.max_seed(), 100000);
+	for i in 0..100 {
+		assert_eq!(subset.get(&format!("key{}", i)).copied(), if i % 4 == 0 { Some(&i) } else { None });
+	}
+	// The entries are borrowed from the map
+	assert!(std::ptr::eq(*subset.get("key8").unwrap(), map.get("key8").unwrap()));
+
+	assert_eq!(map.subset(&[]).unwrap().len(), 0);
+	assert_eq!(map.subset(&["key1", "key100"]).unwrap_err(), SubsetError::MissingKey { index: 1 });
+	assert_eq!(map.subset(&["key1", "key2", "key1"]).unwrap_err(), SubsetError::DuplicateKey { index: 2 });
+	let mut map = map;
+	map.max_seed = 0;
+	assert!(matches!(map.subset(&["key1", "key2", "key3"]), Err(SubsetError::Build(BuildError::SeedNotFound { .. }))));
+}
+
+#[test]
+fn test_map_retain() {
+	let entries = (0..100).map(|i| (format!("key{}", i), i)).collect::<Vec<(String, i32)>>();
+	let mut map = MphfMap::build(entries, 25, 100000).unwrap();
+	map.retain(|_, &value| value % 3 == 0).unwrap();
+	assert_eq!(map.len(), 34);
+	assert_eq!(map.mphf().seeds().len(), 25);
+	assert_eq!(map.max_seed(), 100000);
+	for i in 0..100 {
+		assert_eq!(map.get(&format!("key{}", i)), if i % 3 == 0 { Some(&i) } else { None });
+	}
+	for (i, (key, _)) in map.iter().enumerate() {
+		assert_eq!(map.index(key), Some(i));
+	}
+
+	// A failed rebuild leaves the map unchanged
+	let mut map = MphfMap::build(vec![("a", 1), ("b", 2), ("c", 3)], 1, 100000).unwrap();
+	map.max_seed = 0;
+	let before = map.clone();
+	assert!(map.retain(|&key, _| key != "b").is_err());
+	assert_eq!(map, before);
+	map.retain(|_, _| true).unwrap();
+	assert_eq!(map, before);
+}
+
+#[test]
+fn test_map_default() {
+	let map = MphfMap::<&str, u32>::default();
+	assert_eq!(map.get("cat"), None);
+	assert_eq!(map.get(""), None);
+	assert_eq!(map.len(), 0);
+	assert!(map.is_empty());
+	assert_eq!(map.iter().count(), 0);
+	assert_eq!(map, Vec::new().into_iter().collect());
+}
+
+#[test]
+fn test_map_hash() {
+	let hash = |map: &MphfMap<String, u32>| {
+		let mut hasher = std::collections::hash_map::DefaultHasher::new();
+		map.hash(&mut hasher);
+		hasher.finish()
+	};
+	let entries = (0..100).map(|i| (format!("key{}", i), i)).collect::<Vec<(String, u32)>>();
+	let a = MphfMap::build(entries.clone(), 25, 100000).unwrap();
+	let b = MphfMap::build(entries.iter().rev().cloned(), 40, 100000).unwrap();
+	assert_ne!(a.keys(), b.keys());
+	assert_eq!(hash(&a), hash(&b));
+	assert_eq!(hash(&a), hash(&a.clone()));
+
+	// Every entry contributes to the hash
+	let c = a.clone().map_values(|value| if value == 50 { 0 } else { value });
+	assert_ne!(hash(&a), hash(&c));
+	let d = MphfMap::build(entries[1..].to_vec(), 25, 100000).unwrap();
+	assert_ne!(hash(&a), hash(&d));
+}
+
+#[cfg(feature = "postcard")]
+#[test]
+fn test_map_persist() {
+	use crate::binary::{DecodeError, LoadError};
+
+	#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+	struct Color {
+		name: String,
+		rgb: (u8, u8, u8),
+		aliases: Vec<String>,
+	}
+	let color = |name: &str, rgb, aliases: &[&str]| Color { name: name.into(), rgb, aliases: aliases.iter().map(|&s| s.into()).collect() };
+
+	let map = MphfMap::build(vec![
+		(String::from("red"), color("Red", (255, 0, 0), &["scarlet"])),
+		(String::from("green"), color("Green", (0, 255, 0), &[])),
+		(String::from("blue"), color("Blue", (0, 0, 255), &["azure", "navy"])),
+	], 1, 100000).unwrap();
+
+	let mut bytes = Vec::new();
+	map.save_to(&mut bytes).unwrap();
+	let loaded = MphfMap::<String, Color>::load_from(&bytes[..]).unwrap();
+	assert_eq!(loaded, map);
+	assert_eq!(loaded.get("blue").unwrap().aliases, ["azure", "navy"]);
+
+	// Any corruption is rejected
+	for i in 0..bytes.len() {
+		let mut bytes = bytes.clone();
+		bytes[i] ^= 0x01;
+		assert!(MphfMap::<String, Color>::load_from(&bytes[..]).is_err(), "flipped byte {}", i);
+	}
+	assert!(matches!(MphfMap::<String, Color>::load_from(&bytes[..bytes.len() - 1]), Err(LoadError::Decode(DecodeError::Truncated))));
+
+	// Seeds from another table do not match the keys
+	let other = Mphf::build(&["a", "b", "c"], 1, 100000).unwrap();
+	let mut mismatched = bytes[..12].to_vec();
+	other.write_to(&mut mismatched).unwrap();
+	mismatched.extend_from_slice(&bytes[12 + map.mphf().to_bytes().len()..]);
+	assert!(matches!(MphfMap::<String, Color>::load_from(&mismatched[..]), Err(LoadError::Decode(DecodeError::Invalid))));
+	// Caught by the digest when the table records its own keys
+	let mut mismatched = bytes[..12].to_vec();
+	other.write_to_with_keys(&["a", "b", "c"], &mut mismatched).unwrap();
+	mismatched.extend_from_slice(&bytes[12 + map.mphf().to_bytes().len()..]);
+	assert!(matches!(MphfMap::<String, Color>::load_from(&mismatched[..]), Err(LoadError::Decode(DecodeError::KeyMismatch))));
+
+	// Version 1 without the max_seed
+	let mut v1 = bytes[..8].to_vec();
+	v1[4] = 1;
+	v1.extend_from_slice(&bytes[12..bytes.len() - 4]);
+	let mut crc = crate::crc32::Crc32::new();
+	crc.update(&v1[..8]);
+	crc.update(&v1[8 + map.mphf().to_bytes().len()..]);
+	v1.extend_from_slice(&crc.finish().to_le_bytes());
+	let loaded = MphfMap::<String, Color>::load_from(&v1[..]).unwrap();
+	assert_eq!(loaded.max_seed(), u32::MAX);
+	assert_eq!(loaded.keys(), map.keys());
+	assert_eq!(loaded.values(), map.values());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_map_json() {
+	let map = MphfMap::build(vec![
+		(String::from("red"), 0xff0000),
+		(String::from("green"), 0x00ff00),
+		(String::from("blue"), 0x0000ff),
+		(String::from("cyan"), 0x00ffff),
+	], 2, 100000).unwrap();
+
+	let json = map.to_json_string().unwrap();
+	assert!(json.starts_with(r#"{"version":1,"hash":"murmur3_32","max_seed":100000,"seeds":["#));
+	assert_eq!(MphfMap::<String, u32>::from_json_str(&json).unwrap(), map);
+
+	// Entries out of mphf order are moved to their index
+	let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+	value["keys"].as_array_mut().unwrap().reverse();
+	value["values"].as_array_mut().unwrap().reverse();
+	assert_eq!(MphfMap::<String, u32>::from_json_str(&value.to_string()).unwrap(), map);
+	value.as_object_mut().unwrap().remove("max_seed");
+	assert_eq!(MphfMap::<String, u32>::from_json_str(&value.to_string()).unwrap().max_seed(), u32::MAX);
+
+	// Inconsistent tables are rejected
+	let reject = |edit: &dyn Fn(&mut serde_json::Value)| {
+		let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+		edit(&mut value);
+		MphfMap::<String, u32>::from_json_str(&value.to_string()).unwrap_err().to_string()
+	};
+	assert!(reject(&|v| v["version"] = 2.into()).contains("version 2"));
+	assert!(reject(&|v| v["hash"] = "fnv".into()).contains("hash kind"));
+	assert!(reject(&|v| { v["values"].as_array_mut().unwrap().pop(); }).contains("4 keys but 3 values"));
+	assert!(reject(&|v| v["keys"][0] = "magenta".into()).contains("unique index"));
+	assert!(reject(&|v| v["seeds"] = serde_json::json!([])).contains("invalid"));
+	assert!(reject(&|v| { v.as_object_mut().unwrap().remove("seeds"); }).contains("missing field `seeds`"));
+	assert!(MphfMap::<String, u32>::from_json_str("[1, 2, 3]").is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_map_serde() {
+	let map = (0..100).map(|i| (format!("key{}", i), i)).collect::<MphfMap<String, u32>>();
+
+	// Not self-describing, the fields are stored in order
+	let bytes = postcard::to_allocvec(&map).unwrap();
+	assert_eq!(postcard::from_bytes::<MphfMap<String, u32>>(&bytes).unwrap(), map);
+
+	let value = serde_json::to_value(&map).unwrap();
+	assert_eq!(serde_json::from_value::<MphfMap<String, u32>>(value.clone()).unwrap(), map);
+	let mut value = value;
+	value["seeds"][0] = (map.mphf().seeds()[0] ^ 1).into();
+	assert!(serde_json::from_value::<MphfMap<String, u32>>(value).unwrap_err().to_string().contains("unique index"));
+}
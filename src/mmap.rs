@@ -0,0 +1,111 @@
+use std::{fs, path::Path};
+use super::{Mphf, MphfRef};
+use super::binary::{DecodeError, Limits, LoadError};
+
+/// Memory-mapped [`Mphf`] table in the [binary format](crate::binary), requires the `mmap` feature.
+///
+/// Keeps the file mapped for as long as it is alive, the seeds are read directly from the mapping.
+/// The file must not be modified while it is mapped.
+#[derive(Debug)]
+pub struct MmapMphf {
+	mmap: memmap2::Mmap,
+	len: usize,
+}
+
+impl Mphf {
+	/// Maps the file and validates it as a table in the [binary format](crate::binary).
+	///
+	/// Touches every page of the file to verify the checksum, see [`open_mmap_unchecked`](Mphf::open_mmap_unchecked) to defer it.
+	pub fn open_mmap<P: AsRef<Path>>(path: P) -> Result<MmapMphf, LoadError> {
+		MmapMphf::open(path.as_ref(), true)
+	}
+
+	/// Maps the file and validates it as a table in the [binary format](crate::binary) without verifying the checksum.
+	///
+	/// The header is still validated.
+	/// Call [`verify`](MmapMphf::verify) later if the file may be corrupted.
+	pub fn open_mmap_unchecked<P: AsRef<Path>>(path: P) -> Result<MmapMphf, LoadError> {
+		MmapMphf::open(path.as_ref(), false)
+	}
+}
+
+impl MmapMphf {
+	fn open(path: &Path, verify: bool) -> Result<MmapMphf, LoadError> {
+		let file = fs::File::open(path)?;
+		// Safety: the mapping is read-only, modifying the file while mapped is documented as not allowed
+		let mmap = unsafe { memmap2::Mmap::map(&file)? };
+		let len = MphfRef::decode(&mmap, verify, &Limits::NONE)?.len();
+		Ok(MmapMphf { mmap, len })
+	}
+
+	/// Verifies the checksum of the mapped table.
+	pub fn verify(&self) -> Result<(), DecodeError> {
+		MphfRef::decode(&self.mmap, true, &Limits::NONE).map(|_| ())
+	}
+
+	/// Returns the borrowed table backed by the mapping.
+	#[inline]
+	pub fn view(&self) -> MphfRef<'_> {
+		MphfRef::from_validated(&self.mmap)
+	}
+
+	/// Returns the number of keys.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.len
+	}
+	/// Returns true if built over an empty set of keys.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Returns the index of the given key.
+	#[inline]
+	pub fn index(&self, key: &str) -> Option<usize> {
+		self.view().index(key)
+	}
+	/// Gets the value of the given key from the values in mphf order.
+	#[inline]
+	pub fn get<'v, T>(&self, key: &str, values: &'v [T]) -> Option<&'v T> {
+		values.get(self.index(key)?)
+	}
+}
+
+#[test]
+fn test_mmap() {
+	fn assert_send_sync<T: Send + Sync>() {}
+	assert_send_sync::<MmapMphf>();
+
+	let keys = (0..100).map(|i| format!("key{}", i)).collect::<Vec<String>>();
+	let keys = keys.iter().map(|key| key.as_str()).collect::<Vec<&str>>();
+	let mphf = Mphf::build(&keys, 25, 100000).unwrap();
+
+	let path = std::env::temp_dir().join(format!("mphf-test-mmap-{}.bin", std::process::id()));
+	fs::write(&path, mphf.to_bytes()).unwrap();
+	let mapped = Mphf::open_mmap(&path).unwrap();
+	assert_eq!(mapped.len(), mphf.len());
+	assert_eq!(mapped.view().to_owned(), mphf);
+	std::thread::scope(|s| {
+		s.spawn(|| {
+			for &key in &keys {
+				assert_eq!(mapped.index(key), mphf.index(key));
+			}
+		});
+	});
+	drop(mapped);
+
+	// Corrupted seeds are rejected eagerly or by a later verify
+	let mut bytes = mphf.to_bytes();
+	*bytes.last_mut().unwrap() ^= 0x01;
+	fs::write(&path, &bytes).unwrap();
+	assert!(matches!(Mphf::open_mmap(&path), Err(LoadError::Decode(DecodeError::ChecksumMismatch))));
+	let mapped = Mphf::open_mmap_unchecked(&path).unwrap();
+	assert_eq!(mapped.verify(), Err(DecodeError::ChecksumMismatch));
+	drop(mapped);
+
+	fs::write(&path, &bytes[..10]).unwrap();
+	assert!(matches!(Mphf::open_mmap_unchecked(&path), Err(LoadError::Decode(DecodeError::Truncated))));
+	fs::remove_file(&path).unwrap();
+	assert!(matches!(Mphf::open_mmap(&path), Err(LoadError::Io(_))));
+}
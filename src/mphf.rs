@@ -0,0 +1,240 @@
+use super::{BuildError, Seeds};
+
+/// Minimally Perfect Hash Function over a set of keys.
+///
+/// Owns the seeds table alongside the number of keys it was built for.
+/// Every key in the set maps to a unique index in range `0..len()`.
+///
+/// Keys outside the set still map to some index (or `None`), the caller is responsible for verifying the key.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct Mphf {
+	seeds: Seeds,
+	len: usize,
+}
+
+impl Mphf {
+	/// Builds the mphf over the input keys.
+	///
+	/// See [`build`](crate::build) for the meaning of the arguments.
+	pub fn build<S: AsRef<str>>(keys: &[S], seeds_len: usize, max_seed: u32) -> Result<Mphf, BuildError> {
+		let seeds = crate::build(keys, seeds_len, max_seed)?;
+		Ok(Mphf { seeds, len: keys.len() })
+	}
+
+	/// Wraps an existing seeds table for a set of `len` keys.
+	///
+	/// Returns `None` if the seeds table cannot possibly belong to a set of `len` keys.
+	/// This catches obviously corrupt tables but cannot verify the seeds match the keys.
+	pub fn from_seeds(seeds: Seeds, len: usize) -> Option<Mphf> {
+		if seeds.is_empty() {
+			return None;
+		}
+		// Every used bucket holds at least one key
+		let used = seeds.iter().filter(|&&seed| seed != u32::MAX).count();
+		if used > len || (len > 0 && used == 0) {
+			return None;
+		}
+		Some(Mphf { seeds, len })
+	}
+
+	/// Returns the seeds table.
+	#[inline]
+	pub fn seeds(&self) -> &Seeds {
+		&self.seeds
+	}
+	/// Unwraps the seeds table.
+	#[inline]
+	pub fn into_seeds(self) -> Seeds {
+		self.seeds
+	}
+	/// Returns the number of keys.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.len
+	}
+	/// Returns true if built over an empty set of keys.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Returns the index of the given key.
+	#[inline]
+	pub fn index(&self, key: &str) -> Option<usize> {
+		crate::index(key, &self.seeds, self.len)
+	}
+	/// Gets the value of the given key from the values in mphf order.
+	#[inline]
+	pub fn get<'a, T>(&self, key: &str, values: &'a [T]) -> Option<&'a T> {
+		values.get(self.index(key)?)
+	}
+}
+
+/// Lookups directly on the archived representation.
+///
+/// Validate untrusted archives with `rkyv::access`, lookups never index out of bounds even if the archived table is inconsistent.
+#[cfg(feature = "rkyv")]
+impl ArchivedMphf {
+	/// Returns the number of keys.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.len.to_native() as usize
+	}
+	/// Returns true if built over an empty set of keys.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Returns the index of the given key.
+	#[inline]
+	pub fn index(&self, key: &str) -> Option<usize> {
+		let len = self.len();
+		if self.seeds.is_empty() || len == 0 {
+			return None;
+		}
+		let key = key.as_bytes();
+		let h0 = crate::hash(key, 0) as usize % self.seeds.len();
+		let seed = self.seeds.get(h0)?;
+		if seed == u32::MAX {
+			return None;
+		}
+		return Some(crate::hash(key, seed) as usize % len);
+	}
+	/// Gets the value of the given key from the values in mphf order.
+	#[inline]
+	pub fn get<'a, T>(&self, key: &str, values: &'a [T]) -> Option<&'a T> {
+		values.get(self.index(key)?)
+	}
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+	use std::fmt;
+	use serde::de::{self, Deserialize, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+	use serde::ser::{Serialize, SerializeStruct, Serializer};
+	use super::*;
+
+	const FIELDS: &[&str] = &["seeds", "len"];
+
+	/// Serializes the seeds and the number of keys, requires the `serde` feature.
+	impl Serialize for Mphf {
+		fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+			let mut state = serializer.serialize_struct("Mphf", FIELDS.len())?;
+			state.serialize_field("seeds", &self.seeds)?;
+			state.serialize_field("len", &self.len)?;
+			state.end()
+		}
+	}
+
+	// Checks the deserialized fields the same as Mphf::from_seeds.
+	fn assemble<E: de::Error>(seeds: Seeds, len: usize) -> Result<Mphf, E> {
+		Mphf::from_seeds(seeds, len).ok_or_else(|| E::custom(format_args!("seeds do not belong to a table of {} keys", len)))
+	}
+
+	struct MphfVisitor;
+
+	impl<'de> Visitor<'de> for MphfVisitor {
+		type Value = Mphf;
+
+		fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			f.write_str("an mphf")
+		}
+
+		// Formats which are not self-describing store the fields in order
+		fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Mphf, A::Error> {
+			let seeds = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &"an mphf with 2 fields"))?;
+			let len = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &"an mphf with 2 fields"))?;
+			assemble(seeds, len)
+		}
+
+		fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Mphf, A::Error> {
+			let (mut seeds, mut len) = (None, None);
+			while let Some(name) = map.next_key::<String>()? {
+				match &*name {
+					"seeds" => seeds = Some(map.next_value()?),
+					"len" => len = Some(map.next_value()?),
+					_ => {
+						map.next_value::<IgnoredAny>()?;
+					},
+				}
+			}
+			let seeds = seeds.ok_or_else(|| de::Error::missing_field("seeds"))?;
+			let len = len.ok_or_else(|| de::Error::missing_field("len"))?;
+			assemble(seeds, len)
+		}
+	}
+
+	/// Deserializes and checks a serialized mphf, requires the `serde` feature.
+	///
+	/// Rejects the seeds with the same checks as [`Mphf::from_seeds`].
+	impl<'de> Deserialize<'de> for Mphf {
+		fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Mphf, D::Error> {
+			deserializer.deserialize_struct("Mphf", FIELDS, MphfVisitor)
+		}
+	}
+}
+
+#[test]
+fn test_mphf() {
+	const KEYS: &[&str] = &["hello", "goodbye", "cat", "dog"];
+	let mphf = Mphf::build(KEYS, 2, 10000).unwrap();
+	assert_eq!(mphf.len(), KEYS.len());
+
+	let mut seen = [false; 4];
+	for &key in KEYS {
+		let index = mphf.index(key).unwrap();
+		assert!(!seen[index]);
+		seen[index] = true;
+	}
+
+	assert_eq!(Mphf::from_seeds(mphf.seeds().clone(), 4), Some(mphf));
+	assert_eq!(Mphf::from_seeds(Seeds::default(), 0), None);
+	assert_eq!(Mphf::from_seeds(Seeds::from(vec![0, 1]), 1), None);
+	assert_eq!(Mphf::from_seeds(Seeds::from(vec![u32::MAX]), 1), None);
+	assert!(Mphf::from_seeds(Seeds::from(vec![u32::MAX]), 0).unwrap().index("cat").is_none());
+}
+
+#[cfg(feature = "rkyv")]
+#[test]
+fn test_mphf_rkyv() {
+	let keys = (0..100).map(|i| format!("key{}", i)).collect::<Vec<String>>();
+	let keys = keys.iter().map(|key| key.as_str()).collect::<Vec<&str>>();
+	let mphf = Mphf::build(&keys, 25, 100000).unwrap();
+
+	let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&mphf).unwrap();
+	let archived = rkyv::access::<ArchivedMphf, rkyv::rancor::Error>(&bytes).unwrap();
+	assert_eq!(archived.len(), mphf.len());
+	for &key in &keys {
+		assert_eq!(archived.index(key), mphf.index(key));
+	}
+	assert_eq!(archived.index("not a key"), mphf.index("not a key"));
+
+	let deserialized = rkyv::deserialize::<Mphf, rkyv::rancor::Error>(archived).unwrap();
+	assert_eq!(deserialized, mphf);
+
+	// Truncated archives fail validation
+	assert!(rkyv::access::<ArchivedMphf, rkyv::rancor::Error>(&bytes[..bytes.len() - 4]).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_mphf_serde() {
+	let mphf = Mphf::build(&["hello", "goodbye", "cat", "dog"], 2, 10000).unwrap();
+
+	let json = serde_json::to_string(&mphf).unwrap();
+	assert_eq!(json, r#"{"seeds":[0,1],"len":4}"#);
+	assert_eq!(serde_json::from_str::<Mphf>(&json).unwrap(), mphf);
+	let bytes = postcard::to_allocvec(&mphf).unwrap();
+	assert_eq!(postcard::from_bytes::<Mphf>(&bytes).unwrap(), mphf);
+
+	// Tampered payloads are rejected the same as by from_seeds
+	assert!(serde_json::from_str::<Mphf>(r#"{"seeds":[],"len":0}"#).is_err());
+	assert!(serde_json::from_str::<Mphf>(r#"{"seeds":[0,1],"len":1}"#).unwrap_err().to_string().contains("do not belong"));
+	assert!(serde_json::from_str::<Mphf>(r#"{"seeds":[4294967295],"len":3}"#).is_err());
+	assert!(serde_json::from_str::<Mphf>(r#"{"seeds":[0,1]}"#).is_err());
+	let mut tampered = bytes.clone();
+	*tampered.last_mut().unwrap() = 1;
+	assert!(postcard::from_bytes::<Mphf>(&tampered).is_err());
+}
@@ -33,6 +33,22 @@ pub const fn hash(s: &[u8], seed: u32) -> u32 {
 	fmix32(h ^ s.len() as u32)
 }
 
+/// MurmurHash3 32-bit variant of a single `u32` key.
+///
+/// Same result as [`hash`] over the little-endian bytes of the key without the loop over the input.
+#[inline]
+pub const fn hash_u32(key: u32, seed: u32) -> u32 {
+	const C1: u32 = 0xcc9e2d51;
+	const C2: u32 = 0x1b873593;
+
+	let k = key.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+	let mut h = seed ^ k;
+	h = h.rotate_left(13);
+	h = h.wrapping_mul(5).wrapping_add(0xe6546b64);
+
+	fmix32(h ^ 4)
+}
+
 #[inline]
 const fn fmix32(mut h: u32) -> u32 {
 	h ^= h >> 16;
@@ -65,3 +81,13 @@ fn test_murmurhash3_vectors() {
 		assert_eq!(expected, hash(input, seed));
 	}
 }
+
+#[test]
+fn test_hash_u32() {
+	for &key in &[0, 1, 0x87654321, 0xffffffff, 0x12345678] {
+		for &seed in &[0, 1, 567, 0x5082EDEE, 0xffffffff] {
+			assert_eq!(hash_u32(key, seed), hash(&key.to_le_bytes(), seed));
+		}
+	}
+	assert_eq!(hash_u32(0x87654321, 0), 0xF55B516B);
+}
@@ -0,0 +1,139 @@
+use std::{fmt, ops};
+
+/// Table of seeds for a Minimally Perfect Hash Function.
+///
+/// Returned by [`build`](crate::build), entries for empty buckets are set to `u32::MAX`.
+///
+/// Dereferences to `[u32]` so it can be passed directly to the functions taking a seeds slice.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct Seeds(Vec<u32>);
+
+impl Seeds {
+	/// Returns the seeds as a slice.
+	#[inline]
+	pub fn as_slice(&self) -> &[u32] {
+		&self.0
+	}
+	/// Returns the number of seeds.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+	/// Returns true if there are no seeds.
+	///
+	/// Looking up any key in an empty seeds table returns `None`.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+	/// Unwraps the seeds.
+	#[inline]
+	pub fn into_vec(self) -> Vec<u32> {
+		self.0
+	}
+}
+
+impl From<Vec<u32>> for Seeds {
+	#[inline]
+	fn from(seeds: Vec<u32>) -> Seeds {
+		Seeds(seeds)
+	}
+}
+impl From<Seeds> for Vec<u32> {
+	#[inline]
+	fn from(seeds: Seeds) -> Vec<u32> {
+		seeds.0
+	}
+}
+
+impl ops::Deref for Seeds {
+	type Target = [u32];
+	#[inline]
+	fn deref(&self) -> &[u32] {
+		&self.0
+	}
+}
+impl AsRef<[u32]> for Seeds {
+	#[inline]
+	fn as_ref(&self) -> &[u32] {
+		&self.0
+	}
+}
+impl ops::Index<usize> for Seeds {
+	type Output = u32;
+	#[inline]
+	fn index(&self, index: usize) -> &u32 {
+		&self.0[index]
+	}
+}
+
+impl fmt::Debug for Seeds {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Seeds {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.0.serialize(serializer)
+	}
+}
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Seeds {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Seeds, D::Error> {
+		// An empty seeds table is the same as the default, looking up a key in it returns None
+		Vec::<u32>::deserialize(deserializer).map(Seeds)
+	}
+}
+
+#[cfg(feature = "rkyv")]
+impl ArchivedSeeds {
+	/// Returns the number of seeds.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+	/// Returns true if there are no seeds.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+	/// Returns the seed at the given index.
+	#[inline]
+	pub fn get(&self, index: usize) -> Option<u32> {
+		self.0.get(index).map(|seed| seed.to_native())
+	}
+}
+
+#[test]
+fn test_seeds_slice() {
+	let seeds = Seeds::from(vec![3, u32::MAX, 7]);
+	assert_eq!(seeds.as_slice(), &[3, u32::MAX, 7]);
+	assert_eq!(seeds.len(), 3);
+	assert!(!seeds.is_empty());
+	assert_eq!(seeds[2], 7);
+	assert_eq!(format!("{:?}", seeds), "[3, 4294967295, 7]");
+	assert!(Seeds::default().is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_seeds_serde() {
+	let seeds = crate::build(&["hello", "goodbye", "cat", "dog"], 2, 10000).unwrap();
+
+	let json = serde_json::to_string(&seeds).unwrap();
+	assert_eq!(serde_json::from_str::<Seeds>(&json).unwrap(), seeds);
+
+	let bytes = postcard::to_allocvec(&seeds).unwrap();
+	assert_eq!(postcard::from_bytes::<Seeds>(&bytes).unwrap(), seeds);
+
+	// The default seeds round trip
+	assert_eq!(serde_json::from_str::<Seeds>(&serde_json::to_string(&Seeds::default()).unwrap()).unwrap(), Seeds::default());
+	assert_eq!(crate::index("cat", &serde_json::from_str::<Seeds>("[]").unwrap(), 4), None);
+
+	// Tampered payloads are rejected
+	assert!(serde_json::from_str::<Seeds>("[1, -1]").is_err());
+	assert!(postcard::from_bytes::<Seeds>(&bytes[..bytes.len() - 1]).is_err());
+}
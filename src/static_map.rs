@@ -0,0 +1,153 @@
+use core::fmt;
+
+/// Map with string keys built at compile time by the [`map!`](crate::map) macro.
+///
+/// The keys and values are stored in mphf order, lookups compare the key and return `None` for keys outside the map.
+///
+/// # Examples
+///
+/// ```
+/// static KEYWORDS: mphf::Map<&str, u32> = mphf::map! {
+/// 	"if" => 0,
+/// 	"else" => 1,
+/// 	"while" => 2,
+/// };
+///
+/// assert_eq!(KEYWORDS.get("else"), Some(&1));
+/// assert_eq!(KEYWORDS.get("loop"), None);
+/// assert_eq!(KEYWORDS.len(), 3);
+/// ```
+pub struct Map<K: 'static, V: 'static> {
+	#[doc(hidden)]
+	pub __seeds: &'static [u32],
+	#[doc(hidden)]
+	pub __keys: &'static [K],
+	#[doc(hidden)]
+	pub __values: &'static [V],
+}
+
+impl<V> Map<&'static str, V> {
+	/// Returns the index of the key in [`keys`](Map::keys) and [`values`](Map::values).
+	#[inline]
+	pub fn index(&self, key: &str) -> Option<usize> {
+		let index = crate::index(key, self.__seeds, self.__keys.len())?;
+		if self.__keys[index] != key {
+			return None;
+		}
+		Some(index)
+	}
+	/// Returns the value of the key.
+	#[inline]
+	pub fn get(&self, key: &str) -> Option<&'static V> {
+		Some(&self.__values[self.index(key)?])
+	}
+	/// Returns the stored key and the value of the key.
+	#[inline]
+	pub fn get_entry(&self, key: &str) -> Option<(&'static str, &'static V)> {
+		let index = self.index(key)?;
+		Some((self.__keys[index], &self.__values[index]))
+	}
+	/// Returns true if the key is in the map.
+	#[inline]
+	pub fn contains_key(&self, key: &str) -> bool {
+		self.index(key).is_some()
+	}
+}
+
+impl<K, V> Map<K, V> {
+	/// Returns the number of entries.
+	#[inline]
+	pub const fn len(&self) -> usize {
+		self.__keys.len()
+	}
+	/// Returns true if the map has no entries.
+	#[inline]
+	pub const fn is_empty(&self) -> bool {
+		self.__keys.is_empty()
+	}
+	/// Returns the keys in mphf order.
+	#[inline]
+	pub const fn keys(&self) -> &'static [K] {
+		self.__keys
+	}
+	/// Returns the values in mphf order.
+	#[inline]
+	pub const fn values(&self) -> &'static [V] {
+		self.__values
+	}
+	/// Iterates over the entries in mphf order.
+	#[inline]
+	pub fn entries(&self) -> impl Iterator<Item = (&'static K, &'static V)> {
+		self.__keys.iter().zip(self.__values.iter())
+	}
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for Map<K, V> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_map().entries(self.entries()).finish()
+	}
+}
+
+/// Set of strings built at compile time by the [`set!`](crate::set) macro.
+///
+/// The keys are stored in mphf order, [`index_of`](Set::index_of) is a dense index usable as a small id of the key.
+///
+/// # Examples
+///
+/// ```
+/// static RESERVED: mphf::Set<&str> = mphf::set! { "fn", "let", "match" };
+///
+/// assert!(RESERVED.contains("let"));
+/// assert!(!RESERVED.contains("lent"));
+///
+/// // A side table indexed by the dense index
+/// let mut uses = [0; 3];
+/// uses[RESERVED.index_of("match").unwrap()] += 1;
+/// ```
+pub struct Set<K: 'static> {
+	#[doc(hidden)]
+	pub __seeds: &'static [u32],
+	#[doc(hidden)]
+	pub __keys: &'static [K],
+}
+
+impl Set<&'static str> {
+	/// Returns the index of the key in [`keys`](Set::keys), every key of the set has a distinct index below [`len`](Set::len).
+	#[inline]
+	pub fn index_of(&self, key: &str) -> Option<usize> {
+		let index = crate::index(key, self.__seeds, self.__keys.len())?;
+		if self.__keys[index] != key {
+			return None;
+		}
+		Some(index)
+	}
+	/// Returns true if the key is in the set.
+	#[inline]
+	pub fn contains(&self, key: &str) -> bool {
+		self.index_of(key).is_some()
+	}
+}
+
+impl<K> Set<K> {
+	/// Returns the number of keys.
+	#[inline]
+	pub const fn len(&self) -> usize {
+		self.__keys.len()
+	}
+	/// Returns true if the set has no keys.
+	#[inline]
+	pub const fn is_empty(&self) -> bool {
+		self.__keys.is_empty()
+	}
+	/// Returns the keys in mphf order, the key at an index is the key [`index_of`](Set::index_of) returns the index for.
+	#[inline]
+	pub const fn keys(&self) -> &'static [K] {
+		self.__keys
+	}
+}
+
+impl<K: fmt::Debug> fmt::Debug for Set<K> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_set().entries(self.__keys).finish()
+	}
+}
@@ -0,0 +1,134 @@
+use std::{fmt, fs, io, path::Path};
+
+/// Error reading the keys and values from a TOML file.
+#[derive(Debug)]
+pub enum TomlError {
+	/// Reading the file failed.
+	Io(io::Error),
+	/// The file is not valid TOML.
+	Parse(toml::de::Error),
+	/// The document does not have exactly one top-level array of tables.
+	NoTableArray,
+	/// The table at `index` of the array has no such field.
+	MissingField { index: usize, field: String },
+	/// The field of the table at `index` is not a string, number or boolean.
+	InvalidField { index: usize, field: String },
+}
+
+impl From<io::Error> for TomlError {
+	fn from(err: io::Error) -> TomlError {
+		TomlError::Io(err)
+	}
+}
+impl From<toml::de::Error> for TomlError {
+	fn from(err: toml::de::Error) -> TomlError {
+		TomlError::Parse(err)
+	}
+}
+
+impl fmt::Display for TomlError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			TomlError::Io(err) => write!(f, "error reading toml file: {}", err),
+			TomlError::Parse(err) => write!(f, "error parsing toml file: {}", err),
+			TomlError::NoTableArray => f.write_str("expected exactly one top-level array of tables"),
+			TomlError::MissingField { index, field } => write!(f, "entry {} has no field {:?}", index, field),
+			TomlError::InvalidField { index, field } => write!(f, "field {:?} of entry {} is not a string, number or boolean", field, index),
+		}
+	}
+}
+
+impl std::error::Error for TomlError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			TomlError::Io(err) => Some(err),
+			TomlError::Parse(err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+/// Reads the keys and values from an array of tables in a TOML file, requires the `toml` feature.
+///
+/// The document must have exactly one top-level array of tables, each table gives a key from its `key_field` and a value from its `value_field`.
+/// Strings are taken as is, numbers and booleans are written as in TOML.
+/// The keys and values are returned in the order of the file, ready to be passed to [`build`](crate::build) or the `codegen` options.
+///
+/// # Examples
+///
+/// ```toml
+/// [[command]]
+/// name = "help"
+/// handler = "show_help"
+///
+/// [[command]]
+/// name = "list"
+/// handler = "list_items"
+/// ```
+///
+/// ```no_run
+/// let (keys, values) = mphf::build_from_toml("commands.toml".as_ref(), "name", "handler").unwrap();
+/// let seeds = mphf::build(&keys, 1, 100000).unwrap();
+/// ```
+pub fn build_from_toml(path: &Path, key_field: &str, value_field: &str) -> Result<(Vec<String>, Vec<String>), TomlError> {
+	parse(&fs::read_to_string(path)?, key_field, value_field)
+}
+
+fn parse(s: &str, key_field: &str, value_field: &str) -> Result<(Vec<String>, Vec<String>), TomlError> {
+	let document: toml::Table = s.parse()?;
+	let mut arrays = document.values().filter_map(|value| match value {
+		toml::Value::Array(array) if !array.is_empty() && array.iter().all(toml::Value::is_table) => Some(array),
+		_ => None,
+	});
+	let array = match (arrays.next(), arrays.next()) {
+		(Some(array), None) => array,
+		_ => return Err(TomlError::NoTableArray),
+	};
+
+	let mut keys = Vec::with_capacity(array.len());
+	let mut values = Vec::with_capacity(array.len());
+	for (index, table) in array.iter().enumerate() {
+		keys.push(field(table, index, key_field)?);
+		values.push(field(table, index, value_field)?);
+	}
+	Ok((keys, values))
+}
+
+fn field(table: &toml::Value, index: usize, field: &str) -> Result<String, TomlError> {
+	match table.get(field) {
+		Some(toml::Value::String(s)) => Ok(s.clone()),
+		Some(value @ (toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::Boolean(_))) => Ok(value.to_string()),
+		Some(_) => Err(TomlError::InvalidField { index, field: String::from(field) }),
+		None => Err(TomlError::MissingField { index, field: String::from(field) }),
+	}
+}
+
+#[test]
+fn test_build_from_toml() {
+	let toml = r#"
+		title = "commands"
+
+		[[command]]
+		name = "help"
+		handler = "show_help"
+
+		[[command]]
+		name = "list"
+		handler = 2
+	"#;
+	let (keys, values) = parse(toml, "name", "handler").unwrap();
+	assert_eq!(keys, ["help", "list"]);
+	assert_eq!(values, ["show_help", "2"]);
+
+	let path = std::env::temp_dir().join(format!("mphf-test-toml-{}.toml", std::process::id()));
+	fs::write(&path, toml).unwrap();
+	assert_eq!(build_from_toml(&path, "name", "handler").unwrap(), (keys, values));
+	fs::remove_file(&path).unwrap();
+	assert!(matches!(build_from_toml(&path, "name", "handler"), Err(TomlError::Io(_))));
+
+	assert!(matches!(parse(toml, "name", "missing"), Err(TomlError::MissingField { index: 0, .. })));
+	assert!(matches!(parse("[[a]]\nname = [1]\n", "name", "name"), Err(TomlError::InvalidField { index: 0, .. })));
+	assert!(matches!(parse("[[a]]\nname = 'x'\n[[b]]\nname = 'y'\n", "name", "name"), Err(TomlError::NoTableArray)));
+	assert!(matches!(parse("name = 'x'\n", "name", "name"), Err(TomlError::NoTableArray)));
+	assert!(matches!(parse("[[a]\n", "name", "name"), Err(TomlError::Parse(_))));
+}
@@ -0,0 +1,116 @@
+#![cfg(feature = "cli")]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use mphf::codegen::{CodegenError, Options};
+
+type Generate = fn(&Options) -> Result<String, CodegenError>;
+
+fn temp_dir(name: &str) -> PathBuf {
+	let dir = std::env::temp_dir().join(format!("mphf-test-cli-{}-{}", name, std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	dir
+}
+
+fn mphf_gen(dir: &Path, args: &[&str]) -> Output {
+	Command::new(env!("CARGO_BIN_EXE_mphf-gen")).current_dir(dir).args(args).output().unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+	String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn test_cli_formats() {
+	let dir = temp_dir("formats");
+	fs::write(dir.join("keys.txt"), "fn\nlet\nmatch\n").unwrap();
+	fs::write(dir.join("values.txt"), "Fn\r\nLet\r\nMatch\r\n").unwrap();
+	let keys = ["fn", "let", "match"];
+	let values = ["Fn", "Let", "Match"];
+	let options = Options { name: "keywords", keys: &keys, values: &values, seeds_len: 2, max_seed: 100000, ..Default::default() };
+
+	let args = ["--name", "keywords", "--keys", "keys.txt", "--values", "values.txt", "--seeds-len", "2", "--max-seed", "100000"];
+	let output = mphf_gen(&dir, &[&args[..], &["-o", "keywords.rs"]].concat());
+	assert!(output.status.success(), "{}", stderr(&output));
+	assert!(output.stdout.is_empty());
+	assert!(stderr(&output).contains("3 keys in 2 buckets"), "{}", stderr(&output));
+	assert_eq!(fs::read_to_string(dir.join("keywords.rs")).unwrap(), options.rust().unwrap());
+
+	let formats: [(&str, Generate); 6] = [
+		("rust", |options| options.rust()),
+		("c", |options| options.c()),
+		("cpp", |options| options.cpp()),
+		("go", |options| options.go("keywords")),
+		("typescript", |options| options.typescript()),
+		("python", |options| options.python()),
+	];
+	for (format, generate) in formats {
+		let output = mphf_gen(&dir, &[&args[..], &["--format", format]].concat());
+		assert!(output.status.success(), "{}: {}", format, stderr(&output));
+		assert_eq!(String::from_utf8(output.stdout).unwrap(), generate(&options).unwrap(), "{}", format);
+	}
+	let output = mphf_gen(&dir, &[&args[..], &["--format", "json"]].concat());
+	assert_eq!(String::from_utf8(output.stdout).unwrap(), options.json().unwrap());
+
+	// The values default to none
+	let output = mphf_gen(&dir, &["--name", "keywords", "--keys", "keys.txt", "--seeds-len", "2", "--max-seed", "100000"]);
+	let code = String::from_utf8(output.stdout).unwrap();
+	assert_eq!(code, Options { has_values: false, ..options }.rust().unwrap());
+	fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_cli_csv() {
+	let dir = temp_dir("csv");
+	fs::write(dir.join("pairs.csv"), "key,value\nred,#f00\n\"green, light\",#9f9\n").unwrap();
+	let output = mphf_gen(&dir, &["--name", "colors", "--csv", "pairs.csv", "--header", "--seeds-len", "1", "--max-seed", "100000", "--verify-keys"]);
+	assert!(output.status.success(), "{}", stderr(&output));
+	let options = Options {
+		name: "colors",
+		keys: &["red", "green, light"],
+		values: &["#f00", "#9f9"],
+		seeds_len: 1,
+		max_seed: 100000,
+		verify_keys: true,
+		..Default::default()
+	};
+	assert_eq!(String::from_utf8(output.stdout).unwrap(), options.rust().unwrap());
+	fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_cli_errors() {
+	let dir = temp_dir("errors");
+	fs::write(dir.join("keys.txt"), "a\nb\na\n").unwrap();
+	fs::write(dir.join("many.txt"), (0..100).map(|i| format!("key{}\n", i)).collect::<String>()).unwrap();
+	fs::write(dir.join("pairs.csv"), "a,1\nb,2\na,3\n").unwrap();
+
+	let output = mphf_gen(&dir, &["--name", "t", "--keys", "keys.txt", "--seeds-len", "1", "--max-seed", "100"]);
+	assert_eq!(output.status.code(), Some(1));
+	assert!(stderr(&output).contains("duplicate key \"a\" on lines 1 and 3"), "{}", stderr(&output));
+
+	let output = mphf_gen(&dir, &["--name", "t", "--csv", "pairs.csv", "--seeds-len", "1", "--max-seed", "100"]);
+	assert_eq!(output.status.code(), Some(1));
+	assert!(stderr(&output).contains("key \"a\" appears in rows 1 and 3"), "{}", stderr(&output));
+
+	let output = mphf_gen(&dir, &["--name", "t", "--keys", "many.txt", "--seeds-len", "1", "--max-seed", "2", "-o", "t.rs"]);
+	assert_eq!(output.status.code(), Some(1));
+	assert!(stderr(&output).contains("failed to build mphf \"t\" over 100 keys"), "{}", stderr(&output));
+	assert!(!dir.join("t.rs").exists());
+
+	let output = mphf_gen(&dir, &["--name", "t", "--keys", "missing.txt", "--seeds-len", "1", "--max-seed", "100"]);
+	assert_eq!(output.status.code(), Some(1));
+	assert!(stderr(&output).contains("error reading missing.txt"), "{}", stderr(&output));
+
+	for args in [&["--name", "t", "--keys", "keys.txt", "--seeds-len", "x"][..], &["--bogus"], &["--name"]] {
+		let output = mphf_gen(&dir, args);
+		assert_eq!(output.status.code(), Some(2), "{:?}", args);
+		assert!(stderr(&output).contains("Usage: mphf-gen"));
+	}
+	let output = mphf_gen(&dir, &["--name", "t", "--keys", "many.txt", "--seeds-len", "1", "--max-seed", "100", "--format", "cobol"]);
+	assert!(stderr(&output).contains("unknown format \"cobol\""));
+	assert_eq!(output.status.code(), Some(1));
+	assert!(mphf_gen(&dir, &["--help"]).status.success());
+	fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,718 @@
+#![cfg(feature = "codegen")]
+
+use mphf::codegen::{CodegenFormat, CppStandard, Indent, ItemKind, KeyNormalizer, Options, RustEdition, Value};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Method {
+	Get,
+	Head,
+	Post,
+	Delete,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Route {
+	pub path: &'static str,
+	pub methods: &'static [Method],
+}
+
+// Generated by the options below, the tests check they are up to date
+include!("codegen/methods.rs");
+include!("codegen/routes.rs");
+include!("codegen/ports.rs");
+include!("codegen/routes_const.rs");
+
+// The runtime re-exported from a facade module
+mod deps {
+	pub use mphf;
+}
+include!("codegen/reexport.rs");
+
+// Does not depend on mphf
+include!("codegen/standalone.rs");
+
+// Seeds stored as u8 with empty buckets
+include!("codegen/narrow.rs");
+
+// An enum over the keys without values
+include!("codegen/commands.rs");
+
+// Case insensitive keys
+include!("codegen/headers.rs");
+
+// The generated items are documented, a public table without a doc comment allows the lint
+/// Tables checked by the missing_docs lint.
+pub mod documented_harness {
+	#![deny(missing_docs)]
+	include!("codegen/documented.rs");
+	include!("codegen/undocumented.rs");
+}
+
+// Arrays with an element per line and a few elements per line
+include!("codegen/lines.rs");
+include!("codegen/wide.rs");
+
+// String values copied out of every accessor, clippy lints the generated code through these includes
+include!("codegen/unchecked.rs");
+
+// The table is an implementation detail of the module including it
+mod private {
+	include!("codegen/colors.rs");
+
+	pub fn color(name: &str) -> Option<&'static str> {
+		colors::value(name)
+	}
+}
+
+fn methods_options() -> Options<'static> {
+	Options {
+		name: "methods",
+		keys: &["GET", "HEAD", "POST", "DELETE"],
+		values: &["Method::Get", "Method::Head", "Method::Post", "super::Method::Delete"],
+		value_type: "Method",
+		verify_keys: true,
+		has_unchecked: true,
+		seeds_len: 2,
+		max_seed: 100000,
+		..Options::default()
+	}
+}
+
+fn routes_options() -> Options<'static> {
+	Options {
+		name: "routes",
+		keys: &["index", "login", "upload"],
+		values: &[
+			"Route { path: \"/\", methods: &[Method::Get, Method::Head] }",
+			"Route { path: \"/login\", methods: &[Method::Get, Method::Post] }",
+			"Route { path: \"/upload\", methods: &[Method::Post] }",
+		],
+		value_type: "Route",
+		copy_values: false,
+		has_entry: true,
+		has_unchecked: true,
+		seeds_len: 1,
+		max_seed: 100000,
+		..Options::default()
+	}
+}
+
+fn routes_const_options() -> Options<'static> {
+	Options { name: "routes_const", item_kind: ItemKind::Const, ..routes_options() }
+}
+
+fn colors_options() -> Options<'static> {
+	Options {
+		name: "colors",
+		visibility: "",
+		keys: &["red", "green", "blue"],
+		values: &["#f00", "#0f0", "#00f"],
+		seeds_len: 1,
+		max_seed: 100000,
+		..Options::default()
+	}
+}
+
+fn reexport_options() -> Options<'static> {
+	Options { name: "reexport", crate_path: "crate::deps::mphf", ..colors_options() }
+}
+
+fn standalone_options() -> Options<'static> {
+	Options { name: "standalone", visibility: "pub", standalone: true, ..colors_options() }
+}
+
+fn narrow_options() -> Options<'static> {
+	Options { name: "narrow", visibility: "pub", seeds_len: 4, narrow_seeds: true, has_contains: true, has_entry: true, ..colors_options() }
+}
+
+fn commands_options() -> Options<'static> {
+	Options {
+		name: "commands",
+		keys: &["help", "list", "list-all", "get_value", "v2"],
+		seeds_len: 2,
+		max_seed: 100000,
+		has_values: false,
+		emit_enum: Some("Command"),
+		..Options::default()
+	}
+}
+
+fn colors_cpp_options() -> Options<'static> {
+	Options {
+		name: "colors",
+		keys: &["red", "green", "blue", "what??=", "nul\0", ")\""],
+		values: &["#f00", "#0f0", "#00f", "trigraph", "nul", "raw string end"],
+		seeds_len: 2,
+		verify_keys: true,
+		..colors_options()
+	}
+}
+
+fn ports_options() -> Options<'static> {
+	Options {
+		name: "ports",
+		keys: &["ftp", "ssh", "http", "https"],
+		typed_values: Some(&[Value::U32(21), Value::U32(22), Value::U32(80), Value::U32(443)]),
+		seeds_len: 1,
+		max_seed: 100000,
+		has_keys: false,
+		has_contains: true,
+		verify_keys: true,
+		..Options::default()
+	}
+}
+
+fn headers_options() -> Options<'static> {
+	Options {
+		name: "headers",
+		keys: &["Content-Type", "Content-Length", "Accept", "X-Request-Id"],
+		values: &["content type", "content length", "accept", "request id"],
+		key_normalizer: Some(KeyNormalizer::AsciiLowercase),
+		seeds_len: 2,
+		max_seed: 100000,
+		verify_keys: true,
+		has_contains: true,
+		has_entry: true,
+		..Options::default()
+	}
+}
+
+fn documented_options() -> Options<'static> {
+	Options {
+		name: "documented",
+		doc: Some("Colors by their name.\n\nThe values are 24-bit RGB."),
+		keys: &["red", "green", "blue"],
+		typed_values: Some(&[Value::U32(0xff0000), Value::U32(0x00ff00), Value::U32(0x0000ff)]),
+		seeds_len: 1,
+		max_seed: 100000,
+		has_unchecked: true,
+		has_contains: true,
+		has_entry: true,
+		emit_enum: Some("Color"),
+		..Options::default()
+	}
+}
+
+fn undocumented_options() -> Options<'static> {
+	Options { name: "undocumented", doc: None, allow_missing_docs: true, verify_keys: true, emit_enum: None, ..documented_options() }
+}
+
+fn lines_options() -> Options<'static> {
+	Options { name: "lines", format: CodegenFormat { elems_per_line: 1, ..CodegenFormat::default() }, ..commands_options() }
+}
+
+fn wide_options() -> Options<'static> {
+	Options { name: "wide", format: CodegenFormat { elems_per_line: 3, indent: Indent::Spaces(4) }, ..headers_options() }
+}
+
+fn unchecked_options() -> Options<'static> {
+	Options { name: "unchecked", visibility: "pub", has_unchecked: true, has_entry: true, ..colors_options() }
+}
+
+fn generated(name: &str) -> String {
+	let path = format!("{}/tests/codegen/{}.rs", env!("CARGO_MANIFEST_DIR"), name);
+	std::fs::read_to_string(path).unwrap()
+}
+
+// Compares the output with the checked in file, or overwrites the file when MPHF_BLESS is set
+// The files are included above so a bless run needs the old files to compile
+fn check_file(file: &str, code: String) {
+	let path = format!("{}/tests/codegen/{}", env!("CARGO_MANIFEST_DIR"), file);
+	if std::env::var_os("MPHF_BLESS").is_some() {
+		std::fs::write(path, code).unwrap();
+	}
+	else {
+		assert_eq!(code, std::fs::read_to_string(path).unwrap(), "tests/codegen/{} is out of date, rerun with MPHF_BLESS=1", file);
+	}
+}
+fn check_generated(name: &str, options: Options) {
+	check_file(&format!("{}.rs", name), options.rust().unwrap());
+}
+
+#[test]
+fn test_generated_up_to_date() {
+	check_generated("methods", methods_options());
+	check_generated("routes", routes_options());
+	check_generated("ports", ports_options());
+	check_generated("routes_const", routes_const_options());
+	check_generated("colors", colors_options());
+	check_generated("reexport", reexport_options());
+	check_generated("standalone", standalone_options());
+	check_generated("narrow", narrow_options());
+	check_generated("commands", commands_options());
+	check_generated("headers", headers_options());
+	check_generated("documented", documented_options());
+	check_generated("undocumented", undocumented_options());
+	check_generated("lines", lines_options());
+	check_generated("wide", wide_options());
+	check_generated("unchecked", unchecked_options());
+	check_file("colors.hpp", colors_cpp_options().cpp().unwrap());
+	check_file("colors.go", colors_cpp_options().go("colors").unwrap());
+	check_file("colors.ts", colors_cpp_options().typescript().unwrap());
+	check_file("colors.py", colors_cpp_options().python().unwrap());
+	check_file("colors.json", colors_cpp_options().json().unwrap());
+}
+
+#[test]
+fn test_typed_values() {
+	let value: Option<Method> = methods::value("POST");
+	assert_eq!(value, Some(Method::Post));
+	assert_eq!(methods::value("DELETE"), Some(Method::Delete));
+	for (key, value) in methods::iter() {
+		assert_eq!(format!("{:?}", value).to_uppercase(), key);
+	}
+
+	let value: Option<&'static Route> = routes::value("login");
+	assert_eq!(value.unwrap().path, "/login");
+	assert_eq!(routes::value("upload").unwrap().methods, &[Method::Post]);
+	assert_eq!(routes::iter().map(|(_, route)| route.methods.len()).sum::<usize>(), 5);
+
+	let value: Option<u32> = ports::value("https");
+	assert_eq!(value, Some(443));
+	assert_eq!(ports::values().sum::<u32>(), 566);
+	const PORTS: usize = ports::len();
+	assert_eq!(ports::LEN, PORTS);
+	assert!(!ports::is_empty());
+	assert_eq!([0u16; PORTS].len(), ports::KEYS.len());
+
+	assert_eq!(private::color("green"), Some("#0f0"));
+	assert_eq!(reexport::value("blue"), Some("#00f"));
+}
+
+#[test]
+fn test_const_items() {
+	const LOGIN: &Route = &routes_const::VALUES[0];
+	assert_eq!(routes_const::SEEDS, routes::SEEDS);
+	assert_eq!(routes_const::KEYS, routes::KEYS);
+	assert_eq!(routes_const::index("login"), Some(0));
+	assert_eq!(routes_const::value("login"), Some(LOGIN));
+	assert!(routes_const::iter().eq(routes::iter()));
+	assert!(routes_const::values().eq(routes::values()));
+}
+
+#[test]
+fn test_standalone() {
+	let code = generated("standalone");
+	assert!(code.contains(concat!("mphf ", env!("CARGO_PKG_VERSION"))));
+	// Only the inlined module is used
+	assert!(!code.lines().filter(|line| !line.trim_start().starts_with("//")).any(|line| line.replace("self::mphf::", "").contains("mphf::")));
+
+	for (key, value) in standalone::iter() {
+		assert_eq!(reexport::value(key), Some(value));
+	}
+	assert_eq!(standalone::SEEDS, reexport::SEEDS);
+	// The inlined hash matches the library for inputs of every length modulo 4
+	let mut rng = 0x9E3779B97F4A7C15u64;
+	for _ in 0..10000 {
+		rng ^= rng << 13;
+		rng ^= rng >> 7;
+		rng ^= rng << 17;
+		let probe = rng.to_le_bytes().iter().take((rng >> 60) as usize).map(|&byte| char::from(byte)).collect::<String>();
+		assert_eq!(standalone::index(&probe), mphf::index(&probe, &standalone::SEEDS, 3), "{:?}", probe);
+	}
+}
+
+#[test]
+fn test_narrow_seeds() {
+	let seeds: [u8; 4] = narrow::SEEDS;
+	assert!(seeds.contains(&u8::MAX));
+	// Same lookups as the library with the empty marker widened back
+	let wide = seeds.iter().map(|&seed| if seed == u8::MAX { u32::MAX } else { seed as u32 }).collect::<Vec<u32>>();
+	assert_eq!(wide.as_slice(), mphf::build(&["red", "green", "blue"], 4, 100000).unwrap().as_slice());
+	for (key, value) in narrow::iter() {
+		assert_eq!(narrow::value(key), Some(value));
+		assert_eq!(private::color(key), Some(value));
+	}
+	for probe in ["", "r", "yellow", "purple", "cyan", "magenta", "black", "white"] {
+		assert_eq!(narrow::index(probe), mphf::index(probe, &wide, 3), "{:?}", probe);
+	}
+}
+
+#[test]
+fn test_contains_key() {
+	for &key in &["ftp", "ssh", "http", "https"] {
+		assert!(ports::contains_key(key), "{}", key);
+	}
+	// Keys mapping to an index of another key
+	for probe in ["", "smtp", "HTTP", "https ", "gopher", "telnet", "imap", "pop3"] {
+		assert!(!ports::contains_key(probe), "{:?}", probe);
+		assert!(!narrow::contains_key(probe), "{:?}", probe);
+	}
+	for key in narrow::keys() {
+		assert!(narrow::contains_key(key));
+	}
+}
+
+#[test]
+fn test_entry() {
+	let (key, route) = routes::entry("upload").unwrap();
+	assert_eq!(key, "upload");
+	assert_eq!(route.path, "/upload");
+	assert_eq!(routes::entry("download").map(|(key, _)| key), None);
+	assert_eq!(narrow::entry("green"), Some(("green", "#0f0")));
+	assert_eq!(narrow::entry("yellow"), None);
+
+	// The stored key outlives the input
+	let input = String::from("login");
+	let key: &'static str = routes::entry(&input).unwrap().0;
+	drop(input);
+	assert_eq!(key, "login");
+	assert!(std::ptr::eq(key, routes::KEYS[routes::index(key).unwrap()]));
+}
+
+#[test]
+fn test_value_unchecked() {
+	for (key, method) in methods_options().keys.iter().zip(&[Method::Get, Method::Head, Method::Post, Method::Delete]) {
+		// Safety: the keys are in the table
+		assert_eq!(unsafe { methods::value_unchecked(key) }, *method);
+	}
+	let route = unsafe { routes::value_unchecked("login") };
+	assert!(std::ptr::eq(route, routes::value("login").unwrap()));
+	assert_eq!(unsafe { routes_const::value_unchecked("upload") }.path, "/upload");
+	assert_eq!(unsafe { unchecked::value_unchecked("green") }, "#0f0");
+	assert_eq!(unchecked::entry("blue"), Some(("blue", "#00f")));
+}
+
+#[test]
+fn test_docs() {
+	assert_eq!(documented_harness::documented::value("red"), Some(0xff0000));
+	assert_eq!("blue".parse::<documented_harness::documented::Color>().map(|color| color.as_str()), Ok("blue"));
+	assert_eq!(documented_harness::undocumented::value("cyan"), None);
+}
+
+#[test]
+fn test_format() {
+	assert_eq!("list-all".parse(), Ok(lines::Command::ListAll));
+	assert_eq!(lines::keys().collect::<Vec<_>>(), commands::keys().collect::<Vec<_>>());
+	assert_eq!(wide::value("Accept"), headers::value("Accept"));
+
+	// Regenerating the same input is byte identical, the lines have no trailing whitespace
+	for options in [lines_options(), wide_options()].iter() {
+		let code = options.rust().unwrap();
+		assert_eq!(options.rust().unwrap(), code);
+		assert!(code.lines().all(|line| line == line.trim_end()));
+	}
+	let code = Options { keys: &[], values: &[], ..wide_options() }.rust().unwrap();
+	assert!(code.contains("\n    pub static KEYS: [&str; 0] = [];\n"));
+}
+
+#[test]
+fn test_key_normalizer() {
+	for key in ["content-type", "Content-Type", "CONTENT-TYPE", "cOnTeNt-TyPe"] {
+		assert_eq!(headers::value(key), Some("content type"), "{:?}", key);
+		assert!(headers::contains_key(key), "{:?}", key);
+		assert_eq!(headers::entry(key), Some(("content-type", "content type")));
+	}
+	assert_eq!(headers::key("X-REQUEST-ID"), Some("x-request-id"));
+	assert_eq!(headers::index("Accept").map(|i| headers::KEYS[i]), Some("accept"));
+	for probe in ["", "Content-Type ", "Content_Type", "Host"] {
+		assert_eq!(headers::value(probe), None, "{:?}", probe);
+	}
+}
+
+#[test]
+fn test_buildrs() {
+	let out_dir = std::env::temp_dir().join(format!("mphf-test-buildrs-{}", std::process::id()));
+	std::fs::create_dir_all(&out_dir).unwrap();
+	let options = Options {
+		watch_files: &[std::path::Path::new("data/colors.csv"), std::path::Path::new("data/extra colors.csv")],
+		..colors_options()
+	};
+	let mut directives = Vec::new();
+	let path = mphf::codegen::buildrs::generate_into(&options, &out_dir, "colors.rs", &mut directives).unwrap();
+	assert_eq!(path, out_dir.join("colors.rs"));
+	assert_eq!(std::fs::read_to_string(&path).unwrap(), generated("colors"));
+	assert_eq!(String::from_utf8(directives).unwrap(), "cargo:rerun-if-changed=data/colors.csv\ncargo:rerun-if-changed=data/extra colors.csv\n");
+
+	// The inputs are watched even when the build fails
+	let mut directives = Vec::new();
+	let failing = Options { keys: &["red", "red"], values: &["1", "2"], ..options };
+	assert!(matches!(mphf::codegen::buildrs::generate_into(&failing, &out_dir, "colors.rs", &mut directives), Err(mphf::codegen::CodegenError::Build { .. })));
+	assert_eq!(directives.len(), 2 * "cargo:rerun-if-changed=data/colors.csv\n".len() + " extra".len());
+	std::fs::remove_dir_all(&out_dir).unwrap();
+}
+
+#[test]
+fn test_verify_keys() {
+	let probes = ["", "smtp", "gopher", "telnet", "imap", "pop3", "PATCH", "get", "GET "];
+	for probe in probes {
+		assert_eq!(ports::value(probe), None, "{:?}", probe);
+		assert_eq!(ports::index(probe), None, "{:?}", probe);
+		assert_eq!(methods::value(probe), None, "{:?}", probe);
+		assert_eq!(methods::key(probe), None, "{:?}", probe);
+	}
+	assert_eq!(ports::value("ssh"), Some(22));
+	assert_eq!(methods::index("HEAD").map(|i| methods::KEYS[i]), Some("HEAD"));
+	// Unverified tables map foreign keys to some entry
+	assert!(probes.iter().any(|probe| reexport::value(probe).is_some()));
+}
+
+#[test]
+fn test_enum() {
+	use commands::Command;
+	for key in commands::keys() {
+		let command: Command = key.parse().unwrap();
+		assert_eq!(command.as_str(), key);
+		assert_eq!(usize::from(command), commands::index(key).unwrap());
+	}
+	assert_eq!("list-all".parse(), Ok(Command::ListAll));
+	assert_eq!(Command::GetValue.as_str(), "get_value");
+	assert_eq!("ListAll".parse::<Command>(), Err(()));
+	assert_eq!("".parse::<Command>(), Err(()));
+}
+
+#[test]
+fn test_rust_editions() {
+	let dir = std::env::temp_dir().join(format!("mphf-test-editions-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).unwrap();
+	// Standalone tables compile without the mphf crate
+	let tables = [
+		standalone_options(),
+		Options { name: "commands", visibility: "pub", standalone: true, ..commands_options() },
+		Options { name: "entries", visibility: "pub", standalone: true, has_entry: true, has_unchecked: true, narrow_seeds: true, ..colors_options() },
+	];
+	for (edition, rust_edition) in [("2018", RustEdition::Edition2021), ("2021", RustEdition::Edition2021), ("2024", RustEdition::Edition2021), ("2024", RustEdition::Edition2024)] {
+		let code = tables.iter().map(|options| Options { rust_edition, ..*options }.rust().unwrap()).collect::<String>();
+		assert_eq!(code.contains("+ use<>"), rust_edition == RustEdition::Edition2024);
+		let path = dir.join(format!("tables_{}_{:?}.rs", edition, rust_edition));
+		std::fs::write(&path, code).unwrap();
+		let status = std::process::Command::new("rustc")
+			.args(["--edition", edition, "--crate-type", "lib", "-D", "warnings", "--out-dir"])
+			.arg(&dir)
+			.arg(&path)
+			.status();
+		let status = match status {
+			Ok(status) => status,
+			Err(err) => {
+				eprintln!("skipping test_rust_editions, no rustc: {}", err);
+				return;
+			},
+		};
+		assert!(status.success(), "failed to compile {} with edition {}", path.display(), edition);
+	}
+	std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_c_compiled() {
+	let keys = ["alpha", "beta", "what??=", "he\"llo", "back\\slash", "tab\there", "\u{e9}t\u{e9}", "nul\0byte", "", "a longer key with spaces"];
+	let values = ["1", "2", "??)", "\"", "\\", "\t", "\u{e9}", "x", "empty", "long"];
+	let options = Options { name: "test", keys: &keys, values: &values, seeds_len: 3, max_seed: 100000, verify_keys: true, ..Options::default() };
+	let code = options.c().unwrap();
+	let seeds = mphf::build(&keys, 3, 100000).unwrap();
+
+	// Every byte of the harness strings is an octal escape
+	let octal = |s: &str| format!("\"{}\"", s.bytes().map(|byte| format!("\\{:03o}", byte)).collect::<String>());
+	let mut harness = String::from("#include <stdio.h>\n\nint main(void) {\n\tint failed = 0;\n");
+	for (key, value) in keys.iter().zip(&values) {
+		let index = mphf::index(key, &seeds, keys.len()).unwrap();
+		harness.push_str(&format!("\tif (mphf_test_index({k}, {n}) != {i}) failed++;\n", k = octal(key), n = key.len(), i = index));
+		harness.push_str(&format!("\tif (memcmp(mphf_test_value({k}, {n}), {v}, {m}) != 0) failed++;\n", k = octal(key), n = key.len(), v = octal(value), m = value.len() + 1));
+	}
+	for probe in &["gamma", "alph", "nul", "what??", "ALPHA"] {
+		harness.push_str(&format!("\tif (mphf_test_index({k}, {n}) != -1 || mphf_test_value({k}, {n}) != NULL) failed++;\n", k = octal(probe), n = probe.len()));
+	}
+	harness.push_str("\tprintf(\"%d\\n\", failed);\n\treturn failed != 0;\n}\n");
+
+	let dir = std::env::temp_dir().join(format!("mphf-test-c-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).unwrap();
+	std::fs::write(dir.join("test.c"), format!("{}\n{}", code, harness)).unwrap();
+	let exe = dir.join("test");
+	let status = std::process::Command::new("cc")
+		.args(["-std=c99", "-Wall", "-Wextra", "-pedantic", "-Werror", "-o"])
+		.arg(&exe)
+		.arg(dir.join("test.c"))
+		.status();
+	let status = match status {
+		Ok(status) => status,
+		Err(err) => {
+			eprintln!("skipping test_c_compiled, no C compiler: {}", err);
+			return;
+		},
+	};
+	assert!(status.success(), "failed to compile {}", dir.join("test.c").display());
+	let output = std::process::Command::new(&exe).output().unwrap();
+	assert!(output.status.success(), "{} lookups failed", String::from_utf8_lossy(&output.stdout).trim());
+	std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_cpp_compiled() {
+	let dir = std::env::temp_dir().join(format!("mphf-test-cpp-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).unwrap();
+	let keys = ["red", "green", "blue", "what??=", "nul\0", ")\""];
+	let values = ["#f00", "#0f0", "#00f", "trigraph", "nul", "raw string end"];
+	let ports = Options {
+		name: "ports",
+		keys: &["ftp", "ssh", "http"],
+		typed_values: Some(&[Value::I64(-21), Value::I64(i64::MIN), Value::I64(80)]),
+		..ports_options()
+	};
+
+	// Every lookup is checked at compile time
+	let escape = |s: &str| format!("std::string_view(\"{}\", {})", s.bytes().map(|byte| format!("\\{:03o}", byte)).collect::<String>(), s.len());
+	let mut harness = String::from("#include \"colors.hpp\"\n#include \"ports.hpp\"\n\n");
+	let seeds = mphf::build(&keys, 2, 100000).unwrap();
+	for (key, value) in keys.iter().zip(&values) {
+		harness.push_str(&format!("static_assert(colors::index({}) == {});\n", escape(key), mphf::index(key, &seeds, keys.len()).unwrap()));
+		harness.push_str(&format!("static_assert(colors::value({}) == {});\n", escape(key), escape(value)));
+	}
+	for probe in &["yellow", "nul", "", "RED", ")"] {
+		harness.push_str(&format!("static_assert(!colors::value({}));\n", escape(probe)));
+	}
+	harness.push_str("static_assert(ports::value(\"ftp\") == -21);\nstatic_assert(*ports::value(\"ssh\") < 0);\nstatic_assert(ports::LEN == 3);\n");
+	harness.push_str("static_assert(colors::hash(\"hello\", 1) == ");
+	harness.push_str(&format!("{}u);\nint main() {{ return 0; }}\n", mphf::hash(b"hello", 1)));
+	std::fs::write(dir.join("test.cpp"), harness).unwrap();
+
+	for &standard in &[CppStandard::Cpp17, CppStandard::Cpp20] {
+		std::fs::write(dir.join("colors.hpp"), Options { cpp_standard: standard, ..colors_cpp_options() }.cpp().unwrap()).unwrap();
+		std::fs::write(dir.join("ports.hpp"), Options { cpp_standard: standard, ..ports }.cpp().unwrap()).unwrap();
+		let std = if standard == CppStandard::Cpp17 { "-std=c++17" } else { "-std=c++20" };
+		let status = std::process::Command::new("c++")
+			.args([std, "-Wall", "-Wextra", "-pedantic", "-Werror", "-fsyntax-only"])
+			.arg(dir.join("test.cpp"))
+			.status();
+		match status {
+			Ok(status) => assert!(status.success(), "failed to compile {} with {}", dir.join("test.cpp").display(), std),
+			Err(err) => {
+				eprintln!("skipping test_cpp_compiled, no C++ compiler: {}", err);
+				break;
+			},
+		}
+	}
+	std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_go_run() {
+	let dir = std::env::temp_dir().join(format!("mphf-test-go-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).unwrap();
+	let keys = ["red", "green", "blue", "what??=", "nul\0", ")\""];
+	let values = ["#f00", "#0f0", "#00f", "trigraph", "nul", "raw string end"];
+	let seeds = mphf::build(&keys, 2, 100000).unwrap();
+
+	let quote = |s: &str| format!("\"{}\"", s.bytes().map(|byte| format!("\\x{:02x}", byte)).collect::<String>());
+	let mut main = String::from("package main\n\nimport \"os\"\n\nfunc main() {\n\tfailed := false\n");
+	for (key, value) in keys.iter().zip(&values) {
+		let index = mphf::index(key, &seeds, keys.len()).unwrap();
+		main.push_str(&format!("\tif i, ok := Index({}); !ok || i != {} {{\n\t\tfailed = true\n\t}}\n", quote(key), index));
+		main.push_str(&format!("\tif v, ok := Value({}); !ok || v != {} {{\n\t\tfailed = true\n\t}}\n", quote(key), quote(value)));
+	}
+	for probe in &["yellow", "nul", "", "RED"] {
+		main.push_str(&format!("\tif _, ok := Value({}); ok {{\n\t\tfailed = true\n\t}}\n", quote(probe)));
+	}
+	main.push_str(&format!("\tif hash(\"hello\", 1) != {} {{\n\t\tfailed = true\n\t}}\n", mphf::hash(b"hello", 1)));
+	main.push_str("\tif failed {\n\t\tos.Exit(1)\n\t}\n}\n");
+	std::fs::write(dir.join("go.mod"), "module mphftest\n\ngo 1.18\n").unwrap();
+	std::fs::write(dir.join("main.go"), main).unwrap();
+	std::fs::write(dir.join("colors.go"), colors_cpp_options().go("main").unwrap()).unwrap();
+
+	match std::process::Command::new("go").arg("run").arg(".").current_dir(&dir).status() {
+		Ok(status) => assert!(status.success(), "go run failed in {}", dir.display()),
+		Err(err) => eprintln!("skipping test_go_run, no Go toolchain: {}", err),
+	}
+	std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_typescript_node() {
+	let dir = std::env::temp_dir().join(format!("mphf-test-ts-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).unwrap();
+	let keys = ["red", "green", "blue", "caf\u{e9}", "\u{65e5}\u{672c}", "\u{1f600}", "nul\0", ")\""];
+	let values = ["#f00", "#0f0", "#00f", "coffee", "japan", "grin", "nul", "quote"];
+	let options = Options {
+		name: "unicode",
+		keys: &keys,
+		values: &values,
+		seeds_len: 2,
+		verify_keys: true,
+		..colors_options()
+	};
+	let seeds = mphf::build(&keys, 2, 100000).unwrap();
+
+	// Node cannot run TypeScript, the few annotations the backend writes are stripped
+	let mut module = options.typescript().unwrap();
+	for annotation in &[": readonly string[]", ": Uint8Array", ": number | undefined", ": string | undefined", ": number", ": string"] {
+		module = module.replace(annotation, "");
+	}
+	let quote = |s: &str| format!("\"{}\"", s.encode_utf16().map(|unit| format!("\\u{:04x}", unit)).collect::<String>());
+	let mut test = String::from("import assert from \"node:assert\";\nimport * as table from \"./unicode.mjs\";\n\n");
+	for (key, value) in keys.iter().zip(&values) {
+		test.push_str(&format!("assert.strictEqual(table.index({}), {});\n", quote(key), mphf::index(key, &seeds, keys.len()).unwrap()));
+		test.push_str(&format!("assert.strictEqual(table.value({}), {});\n", quote(key), quote(value)));
+	}
+	for probe in &["yellow", "cafe", "", "nul", "\u{1f601}"] {
+		test.push_str(&format!("assert.strictEqual(table.value({}), undefined);\n", quote(probe)));
+	}
+	for &(key, seed) in &[("hello", 1), ("\u{1f600}", 0), ("four", 0xffffffff), ("", 0x5082edee)] {
+		test.push_str(&format!("assert.strictEqual(table.hash(new TextEncoder().encode({}), {}), {});\n", quote(key), seed, mphf::hash(key.as_bytes(), seed)));
+	}
+	std::fs::write(dir.join("unicode.mjs"), module).unwrap();
+	std::fs::write(dir.join("test.mjs"), test).unwrap();
+
+	match std::process::Command::new("node").arg(dir.join("test.mjs")).status() {
+		Ok(status) => assert!(status.success(), "node failed on {}", dir.join("test.mjs").display()),
+		Err(err) => eprintln!("skipping test_typescript_node, no node: {}", err),
+	}
+	std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_python_run() {
+	let dir = std::env::temp_dir().join(format!("mphf-test-py-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).unwrap();
+	let keys = ["red", "green", "blue", "caf\u{e9}", "\u{65e5}\u{672c}", "\u{1f600}", "nul\0", ")\""];
+	let values = ["#f00", "#0f0", "#00f", "coffee", "japan", "grin", "nul", "quote"];
+	let options = Options {
+		name: "unicode",
+		keys: &keys,
+		values: &values,
+		seeds_len: 2,
+		verify_keys: true,
+		..colors_options()
+	};
+	let seeds = mphf::build(&keys, 2, 100000).unwrap();
+
+	// Importing the module runs its own hash asserts
+	let quote = |s: &str| format!("\"{}\"", s.chars().map(|chr| format!("\\U{:08x}", chr as u32)).collect::<String>());
+	let mut test = String::from("import unicode\n\n");
+	for (key, value) in keys.iter().zip(&values) {
+		test.push_str(&format!("assert unicode.index({}) == {}\n", quote(key), mphf::index(key, &seeds, keys.len()).unwrap()));
+		test.push_str(&format!("assert unicode.value({}) == {}\n", quote(key), quote(value)));
+	}
+	for probe in &["yellow", "cafe", "", "nul", "\u{1f601}"] {
+		test.push_str(&format!("assert unicode.value({}) is None\n", quote(probe)));
+	}
+	std::fs::write(dir.join("unicode.py"), options.python().unwrap()).unwrap();
+	std::fs::write(dir.join("test.py"), test).unwrap();
+
+	match std::process::Command::new("python3").arg("test.py").current_dir(&dir).status() {
+		Ok(status) => assert!(status.success(), "python3 failed on {}", dir.join("test.py").display()),
+		Err(err) => eprintln!("skipping test_python_run, no python3: {}", err),
+	}
+	std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_artifact() {
+	let options = colors_cpp_options();
+	let json = options.json().unwrap();
+	let map = mphf::MphfMap::<String, String>::from_json_str(&json).unwrap();
+	assert_eq!(map.len(), options.keys.len());
+	for (key, value) in options.keys.iter().zip(options.values) {
+		assert_eq!(map.get(key).map(String::as_str), Some(*value));
+	}
+
+	// The keys are listed in mphf order
+	let artifact: serde_json::Value = serde_json::from_str(&json).unwrap();
+	let seeds = artifact["seeds"].as_array().unwrap().iter().map(|seed| seed.as_u64().unwrap() as u32).collect::<Vec<u32>>();
+	for (i, key) in artifact["keys"].as_array().unwrap().iter().enumerate() {
+		assert_eq!(mphf::index(key.as_str().unwrap(), &seeds, artifact["values_len"].as_u64().unwrap() as usize), Some(i));
+	}
+
+	let ports = ports_options().json().unwrap();
+	let map = mphf::MphfMap::<String, u32>::from_json_str(&ports).unwrap();
+	assert_eq!(map.get("https"), Some(&443));
+}
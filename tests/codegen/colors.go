@@ -0,0 +1,66 @@
+// Code generated by mphf 0.1.0. DO NOT EDIT.
+// 6 keys with digest 9dc8a00c3b65a112, seeds_len 2, max_seed 100000
+// options: verify_keys = true
+
+package colors
+
+var seeds = []uint32{1, 12}
+var keys = []string{"what??=", "nul\x00", "red", "green", "blue", ")\""}
+var values = []string{"trigraph", "nul", "#f00", "#0f0", "#00f", "raw string end"}
+
+func mix(k uint32) uint32 {
+	k *= 0xcc9e2d51
+	k = k<<15 | k>>17
+	return k * 0x1b873593
+}
+
+// Same as mphf::hash
+func hash(key string, seed uint32) uint32 {
+	h := seed
+	n := len(key)
+	i := 0
+	for ; i+4 <= n; i += 4 {
+		h ^= mix(uint32(key[i]) | uint32(key[i+1])<<8 | uint32(key[i+2])<<16 | uint32(key[i+3])<<24)
+		h = h<<13 | h>>19
+		h = h*5 + 0xe6546b64
+	}
+	if n&3 != 0 {
+		k := uint32(key[i])
+		if n&3 >= 2 {
+			k |= uint32(key[i+1]) << 8
+		}
+		if n&3 == 3 {
+			k |= uint32(key[i+2]) << 16
+		}
+		h ^= mix(k)
+	}
+	h ^= uint32(n)
+	h ^= h >> 16
+	h *= 0x85ebca6b
+	h ^= h >> 13
+	h *= 0xc2b2ae35
+	h ^= h >> 16
+	return h
+}
+
+// Index returns the index of the key in keys and values.
+func Index(key string) (int, bool) {
+	seed := seeds[hash(key, 0)%uint32(len(seeds))]
+	if seed == 0xffffffff {
+		return 0, false
+	}
+	i := int(hash(key, seed) % uint32(len(keys)))
+	if keys[i] != key {
+		return 0, false
+	}
+	return i, true
+}
+
+// Value returns the value of the key.
+func Value(key string) (string, bool) {
+	i, ok := Index(key)
+	if !ok {
+		return "", false
+	}
+	return values[i], true
+}
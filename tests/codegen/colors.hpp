@@ -0,0 +1,64 @@
+// @generated by mphf 0.1.0
+// 6 keys with digest 9dc8a00c3b65a112, seeds_len 2, max_seed 100000
+// options: verify_keys = true
+#pragma once
+#include <array>
+#include <cstddef>
+#include <cstdint>
+#include <optional>
+#include <string_view>
+
+namespace colors {
+	constexpr std::size_t LEN = 6;
+	constexpr std::array<std::uint32_t, 2> SEEDS = {1u,12u,};
+	constexpr std::array<std::string_view, LEN> KEYS = {std::string_view("what\?\?=", 7),std::string_view("nul\000", 4),std::string_view("red", 3),std::string_view("green", 5),std::string_view("blue", 4),std::string_view(")\"", 2),};
+	constexpr std::array<std::string_view, LEN> VALUES = {std::string_view("trigraph", 8),std::string_view("nul", 3),std::string_view("#f00", 4),std::string_view("#0f0", 4),std::string_view("#00f", 4),std::string_view("raw string end", 14),};
+
+	namespace detail {
+		constexpr std::uint32_t byte(std::string_view key, std::size_t i) noexcept {
+			return static_cast<unsigned char>(key[i]);
+		}
+		constexpr std::uint32_t mix(std::uint32_t x) noexcept {
+			x *= 0xcc9e2d51u;
+			x = (x << 15 | x >> 17);
+			return x * 0x1b873593u;
+		}
+	}
+
+	// Same as mphf::hash
+	constexpr std::uint32_t hash(std::string_view key, std::uint32_t seed) noexcept {
+		std::uint32_t x = seed;
+		std::size_t i = 0;
+		for (; i + 4 <= key.size(); i += 4) {
+			x ^= detail::mix(detail::byte(key, i) | detail::byte(key, i + 1) << 8 | detail::byte(key, i + 2) << 16 | detail::byte(key, i + 3) << 24);
+			x = (x << 13 | x >> 19);
+			x = x * 5u + 0xe6546b64u;
+		}
+		if ((key.size() & 3) != 0) {
+			std::uint32_t k = detail::byte(key, i);
+			if ((key.size() & 3) >= 2) k |= detail::byte(key, i + 1) << 8;
+			if ((key.size() & 3) == 3) k |= detail::byte(key, i + 2) << 16;
+			x ^= detail::mix(k);
+		}
+		x ^= static_cast<std::uint32_t>(key.size());
+		x ^= x >> 16;
+		x *= 0x85ebca6bu;
+		x ^= x >> 13;
+		x *= 0xc2b2ae35u;
+		x ^= x >> 16;
+		return x;
+	}
+
+	constexpr std::optional<std::size_t> index(std::string_view key) noexcept {
+		std::uint32_t seed = SEEDS[hash(key, 0) % SEEDS.size()];
+		if (seed == 0xffffffffu) return std::nullopt;
+		std::size_t i = hash(key, seed) % LEN;
+		if (KEYS[i] != key) return std::nullopt;
+		return i;
+	}
+	constexpr std::optional<std::string_view> value(std::string_view key) noexcept {
+		std::optional<std::size_t> i = index(key);
+		if (!i) return std::nullopt;
+		return VALUES[*i];
+	}
+}
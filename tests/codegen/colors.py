@@ -0,0 +1,58 @@
+# @generated by mphf 0.1.0
+# 6 keys with digest 9dc8a00c3b65a112, seeds_len 2, max_seed 100000
+# options: verify_keys = true
+from typing import Optional
+
+SEEDS = [1, 12]
+KEYS = ["what??=", "nul\x00", "red", "green", "blue", ")\""]
+VALUES = ["trigraph", "nul", "#f00", "#0f0", "#00f", "raw string end"]
+LEN = 6
+
+
+def _mix(k: int) -> int:
+    k = (k * 0xcc9e2d51) & 0xffffffff
+    k = ((k << 15) | (k >> 17)) & 0xffffffff
+    return (k * 0x1b873593) & 0xffffffff
+
+
+def _hash(key: bytes, seed: int) -> int:
+    """Same as mphf::hash."""
+    h = seed
+    n = len(key)
+    i = 0
+    while i + 4 <= n:
+        h ^= _mix(int.from_bytes(key[i:i + 4], "little"))
+        h = ((h << 13) | (h >> 19)) & 0xffffffff
+        h = (h * 5 + 0xe6546b64) & 0xffffffff
+        i += 4
+    if n & 3:
+        h ^= _mix(int.from_bytes(key[i:], "little"))
+    h ^= n & 0xffffffff
+    h ^= h >> 16
+    h = (h * 0x85ebca6b) & 0xffffffff
+    h ^= h >> 13
+    h = (h * 0xc2b2ae35) & 0xffffffff
+    h ^= h >> 16
+    return h
+
+
+assert _hash(b"", 0) == 0x00000000
+assert _hash(b"abc", 0) == 0xb3dd93fa
+assert _hash(b"hello world", 1350757870) == 0x5cccfb70
+assert _hash(b"\xff\xff\xff\xff\xff", 4294967295) == 0x2eb7ea88
+
+
+def index(key: str) -> Optional[int]:
+    data = key.encode("utf-8")
+    seed = SEEDS[_hash(data, 0) % len(SEEDS)]
+    if seed == 0xffffffff:
+        return None
+    i = _hash(data, seed) % LEN
+    if KEYS[i] != key:
+        return None
+    return i
+
+
+def value(key: str) -> Optional[str]:
+    i = index(key)
+    return None if i is None else VALUES[i]
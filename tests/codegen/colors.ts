@@ -0,0 +1,55 @@
+// @generated by mphf 0.1.0
+// 6 keys with digest 9dc8a00c3b65a112, seeds_len 2, max_seed 100000
+// options: verify_keys = true
+
+const SEEDS = new Uint32Array([1, 12]);
+export const LEN = 6;
+export const KEYS: readonly string[] = ["what??=", "nul\u{0}", "red", "green", "blue", ")\""];
+export const VALUES: readonly string[] = ["trigraph", "nul", "#f00", "#0f0", "#00f", "raw string end"];
+
+const encoder = new TextEncoder();
+
+function mix(k: number): number {
+	k = Math.imul(k, 0xcc9e2d51);
+	k = k << 15 | k >>> 17;
+	return Math.imul(k, 0x1b873593);
+}
+
+// Same as mphf::hash, the keys are hashed as UTF-8
+export function hash(bytes: Uint8Array, seed: number): number {
+	let h = seed | 0;
+	const n = bytes.length;
+	let i = 0;
+	for (; i + 4 <= n; i += 4) {
+		h ^= mix(bytes[i] | bytes[i + 1] << 8 | bytes[i + 2] << 16 | bytes[i + 3] << 24);
+		h = h << 13 | h >>> 19;
+		h = (Math.imul(h, 5) + 0xe6546b64) | 0;
+	}
+	if ((n & 3) !== 0) {
+		let k = bytes[i];
+		if ((n & 3) >= 2) k |= bytes[i + 1] << 8;
+		if ((n & 3) === 3) k |= bytes[i + 2] << 16;
+		h ^= mix(k);
+	}
+	h ^= n;
+	h ^= h >>> 16;
+	h = Math.imul(h, 0x85ebca6b);
+	h ^= h >>> 13;
+	h = Math.imul(h, 0xc2b2ae35);
+	h ^= h >>> 16;
+	return h >>> 0;
+}
+
+export function index(key: string): number | undefined {
+	const bytes = encoder.encode(key);
+	const seed = SEEDS[hash(bytes, 0) % SEEDS.length];
+	if (seed === 0xffffffff) return undefined;
+	const i = hash(bytes, seed) % LEN;
+	if (KEYS[i] !== key) return undefined;
+	return i;
+}
+
+export function value(key: string): string | undefined {
+	const i = index(key);
+	return i === undefined ? undefined : VALUES[i];
+}
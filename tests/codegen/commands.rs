@@ -0,0 +1,47 @@
+// @generated by mphf 0.1.0
+// 5 keys with digest a8cc5f2d36f4f2a1, seeds_len 2, max_seed 100000
+// options: has_values = false
+pub mod commands {
+	/// The seeds of the buckets, empty buckets have the max value.
+	pub static SEEDS: [u32; 2] = [6,5,];
+	/// The keys in mphf order.
+	pub static KEYS: [&str; 5] = ["list-all","v2","list","help","get_value",];
+	/// The number of keys.
+	pub const LEN: usize = 5;
+	/// Returns the key stored at the index of the key, keys outside the table map to an arbitrary entry or `None`.
+	#[inline] pub fn key(key: &str) -> Option<&'static str> { ::mphf::get(key, &SEEDS, &KEYS).copied() }
+	/// Iterates over the keys in mphf order.
+	#[inline] pub fn keys() -> impl Iterator<Item = &'static str> { KEYS.iter().copied() }
+	/// Returns the index of the key in the arrays, keys outside the table map to an arbitrary entry or `None`.
+	#[inline] pub fn index(key: &str) -> Option<usize> { ::mphf::index(key, &SEEDS, 5) }
+	/// Returns the number of keys.
+	#[inline] pub const fn len() -> usize { LEN }
+	/// Returns true if the table has no keys.
+	#[inline] pub const fn is_empty() -> bool { LEN == 0 }
+	/// A variant per key, the discriminant is the index of the key.
+	#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+	pub enum Command {
+		/// The key "list-all".
+		ListAll = 0,
+		/// The key "v2".
+		V2 = 1,
+		/// The key "list".
+		List = 2,
+		/// The key "help".
+		Help = 3,
+		/// The key "get_value".
+		GetValue = 4,
+	}
+	const VARIANTS: [Command; 5] = [Command::ListAll,Command::V2,Command::List,Command::Help,Command::GetValue,];
+	impl Command {
+		/// Returns the key of the variant.
+		#[inline] pub fn as_str(&self) -> &'static str { KEYS[*self as usize] }
+	}
+	impl core::str::FromStr for Command {
+		type Err = ();
+		#[inline] fn from_str(key: &str) -> Result<Command, ()> { match ::mphf::index(key, &SEEDS, 5) { Some(i) if KEYS[i] == key => Ok(VARIANTS[i]), _ => Err(()) } }
+	}
+	impl From<Command> for usize {
+		#[inline] fn from(value: Command) -> usize { value as usize }
+	}
+}
@@ -0,0 +1,33 @@
+// @generated by mphf 0.1.0
+// 4 keys with digest 052c07d56f4e8d87, seeds_len 2, max_seed 100000
+// options: has_contains = true, has_entry = true, verify_keys = true
+pub mod headers {
+	/// The seeds of the buckets, empty buckets have the max value.
+	pub static SEEDS: [u32; 2] = [0,2,];
+	/// The keys in mphf order.
+	pub static KEYS: [&str; 4] = ["content-length","x-request-id","accept","content-type",];
+	/// The values in the order of their keys.
+	pub static VALUES: [&str; 4] = ["content length","request id","accept","content type",];
+	/// The number of keys.
+	pub const LEN: usize = 4;
+	/// Returns the key stored at the index of the key, returns `None` for keys outside the table.
+	#[inline] pub fn key(key: &str) -> Option<&'static str> { let normalized = key.to_ascii_lowercase(); let key: &str = &normalized; match ::mphf::index(key, &SEEDS, 4) { Some(i) if KEYS[i] == key => Some(KEYS[i]), _ => None } }
+	/// Iterates over the keys in mphf order.
+	#[inline] pub fn keys() -> impl Iterator<Item = &'static str> { KEYS.iter().copied() }
+	/// Returns the value of the key, returns `None` for keys outside the table.
+	#[inline] pub fn value(key: &str) -> Option<&'static str> { let normalized = key.to_ascii_lowercase(); let key: &str = &normalized; match ::mphf::index(key, &SEEDS, 4) { Some(i) if KEYS[i] == key => Some(VALUES[i]), _ => None } }
+	/// Iterates over the values in mphf order.
+	#[inline] pub fn values() -> impl Iterator<Item = &'static str> { VALUES.iter().copied() }
+	/// Returns the index of the key in the arrays, returns `None` for keys outside the table.
+	#[inline] pub fn index(key: &str) -> Option<usize> { let normalized = key.to_ascii_lowercase(); let key: &str = &normalized; match ::mphf::index(key, &SEEDS, 4) { Some(i) if KEYS[i] == key => Some(i), _ => None } }
+	/// Returns true if the key is in the table.
+	#[inline] pub fn contains_key(key: &str) -> bool { let normalized = key.to_ascii_lowercase(); let key: &str = &normalized; matches!(::mphf::index(key, &SEEDS, 4), Some(i) if KEYS[i] == key) }
+	/// Returns the number of keys.
+	#[inline] pub const fn len() -> usize { LEN }
+	/// Returns true if the table has no keys.
+	#[inline] pub const fn is_empty() -> bool { LEN == 0 }
+	/// Returns the stored key and the value of the key, returns `None` for keys outside the table.
+	#[inline] pub fn entry(key: &str) -> Option<(&'static str, &'static str)> { let normalized = key.to_ascii_lowercase(); let key: &str = &normalized; match ::mphf::index(key, &SEEDS, 4) { Some(i) if KEYS[i] == key => Some((KEYS[i], VALUES[i])), _ => None } }
+	/// Iterates over the keys and their values in mphf order.
+	#[inline] pub fn iter() -> impl Iterator<Item = (&'static str, &'static str)> { (0..4).map(|i| (KEYS[i], VALUES[i])) }
+}
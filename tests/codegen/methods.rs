@@ -0,0 +1,37 @@
+// @generated by mphf 0.1.0
+// 4 keys with digest 6bbc8035a33da8cc, seeds_len 2, max_seed 100000
+// options: has_unchecked = true, verify_keys = true
+pub mod methods {
+	#[allow(unused_imports)]
+	use super::*;
+	/// The seeds of the buckets, empty buckets have the max value.
+	pub static SEEDS: [u32; 2] = [9,1,];
+	/// The keys in mphf order.
+	pub static KEYS: [&str; 4] = ["HEAD","GET","DELETE","POST",];
+	/// The values in the order of their keys.
+	pub static VALUES: [Method; 4] = [Method::Head,Method::Get,super::Method::Delete,Method::Post,];
+	/// The number of keys.
+	pub const LEN: usize = 4;
+	/// Returns the key stored at the index of the key, returns `None` for keys outside the table.
+	#[inline] pub fn key(key: &str) -> Option<&'static str> { match ::mphf::index(key, &SEEDS, 4) { Some(i) if KEYS[i] == key => Some(KEYS[i]), _ => None } }
+	/// Iterates over the keys in mphf order.
+	#[inline] pub fn keys() -> impl Iterator<Item = &'static str> { KEYS.iter().copied() }
+	/// Returns the value of the key, returns `None` for keys outside the table.
+	#[inline] pub fn value(key: &str) -> Option<Method> { match ::mphf::index(key, &SEEDS, 4) { Some(i) if KEYS[i] == key => Some(VALUES[i]), _ => None } }
+	/// Iterates over the values in mphf order.
+	#[inline] pub fn values() -> impl Iterator<Item = Method> { VALUES.iter().copied() }
+	/// Returns the value of the key without comparing the key or checking for an empty bucket.
+	///
+	/// # Safety
+	///
+	/// The key must be in the table.
+	#[inline] pub unsafe fn value_unchecked(key: &str) -> Method { unsafe { let i = ::mphf::index(key, &SEEDS, 4).unwrap_unchecked(); *VALUES.get_unchecked(i) } }
+	/// Returns the index of the key in the arrays, returns `None` for keys outside the table.
+	#[inline] pub fn index(key: &str) -> Option<usize> { match ::mphf::index(key, &SEEDS, 4) { Some(i) if KEYS[i] == key => Some(i), _ => None } }
+	/// Returns the number of keys.
+	#[inline] pub const fn len() -> usize { LEN }
+	/// Returns true if the table has no keys.
+	#[inline] pub const fn is_empty() -> bool { LEN == 0 }
+	/// Iterates over the keys and their values in mphf order.
+	#[inline] pub fn iter() -> impl Iterator<Item = (&'static str, Method)> { (0..4).map(|i| (KEYS[i], VALUES[i])) }
+}
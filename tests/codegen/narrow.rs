@@ -0,0 +1,38 @@
+// @generated by mphf 0.1.0
+// 3 keys with digest 2d9db084adfdade6, seeds_len 4, max_seed 100000
+// options: narrow_seeds = true, has_contains = true, has_entry = true
+pub mod narrow {
+	/// The seeds of the buckets, empty buckets have the max value.
+	pub static SEEDS: [u8; 4] = [255,2,0,0,];
+	/// The keys in mphf order.
+	pub static KEYS: [&str; 3] = ["green","red","blue",];
+	/// The values in the order of their keys.
+	pub static VALUES: [&str; 3] = ["#0f0","#f00","#00f",];
+	/// The number of keys.
+	pub const LEN: usize = 3;
+	#[inline] fn lookup(key: &[u8]) -> Option<usize> {
+		let seed = SEEDS[::mphf::hash(key, 0) as usize % 4];
+		if seed == u8::MAX { return None; }
+		Some(::mphf::hash(key, seed as u32) as usize % 3)
+	}
+	/// Returns the key stored at the index of the key, keys outside the table map to an arbitrary entry or `None`.
+	#[inline] pub fn key(key: &str) -> Option<&'static str> { lookup(key.as_bytes()).map(|i| KEYS[i]) }
+	/// Iterates over the keys in mphf order.
+	#[inline] pub fn keys() -> impl Iterator<Item = &'static str> { KEYS.iter().copied() }
+	/// Returns the value of the key, keys outside the table map to an arbitrary entry or `None`.
+	#[inline] pub fn value(key: &str) -> Option<&'static str> { lookup(key.as_bytes()).map(|i| VALUES[i]) }
+	/// Iterates over the values in mphf order.
+	#[inline] pub fn values() -> impl Iterator<Item = &'static str> { VALUES.iter().copied() }
+	/// Returns the index of the key in the arrays, keys outside the table map to an arbitrary entry or `None`.
+	#[inline] pub fn index(key: &str) -> Option<usize> { lookup(key.as_bytes()) }
+	/// Returns true if the key is in the table.
+	#[inline] pub fn contains_key(key: &str) -> bool { matches!(lookup(key.as_bytes()), Some(i) if KEYS[i] == key) }
+	/// Returns the number of keys.
+	#[inline] pub const fn len() -> usize { LEN }
+	/// Returns true if the table has no keys.
+	#[inline] pub const fn is_empty() -> bool { LEN == 0 }
+	/// Returns the stored key and the value of the key, returns `None` for keys outside the table.
+	#[inline] pub fn entry(key: &str) -> Option<(&'static str, &'static str)> { match lookup(key.as_bytes()) { Some(i) if KEYS[i] == key => Some((KEYS[i], VALUES[i])), _ => None } }
+	/// Iterates over the keys and their values in mphf order.
+	#[inline] pub fn iter() -> impl Iterator<Item = (&'static str, &'static str)> { (0..3).map(|i| (KEYS[i], VALUES[i])) }
+}
@@ -0,0 +1,25 @@
+// @generated by mphf 0.1.0
+// 4 keys with digest 2c447d94173f8888, seeds_len 1, max_seed 100000
+// options: has_keys = false, has_contains = true, verify_keys = true
+pub mod ports {
+	/// The seeds of the buckets, empty buckets have the max value.
+	pub static SEEDS: [u32; 1] = [6,];
+	/// The keys in mphf order.
+	pub static KEYS: [&str; 4] = ["ftp","ssh","http","https",];
+	/// The values in the order of their keys.
+	pub static VALUES: [u32; 4] = [21,22,80,443,];
+	/// The number of keys.
+	pub const LEN: usize = 4;
+	/// Returns the value of the key, returns `None` for keys outside the table.
+	#[inline] pub fn value(key: &str) -> Option<u32> { match ::mphf::index(key, &SEEDS, 4) { Some(i) if KEYS[i] == key => Some(VALUES[i]), _ => None } }
+	/// Iterates over the values in mphf order.
+	#[inline] pub fn values() -> impl Iterator<Item = u32> { VALUES.iter().copied() }
+	/// Returns the index of the key in the arrays, returns `None` for keys outside the table.
+	#[inline] pub fn index(key: &str) -> Option<usize> { match ::mphf::index(key, &SEEDS, 4) { Some(i) if KEYS[i] == key => Some(i), _ => None } }
+	/// Returns true if the key is in the table.
+	#[inline] pub fn contains_key(key: &str) -> bool { matches!(::mphf::index(key, &SEEDS, 4), Some(i) if KEYS[i] == key) }
+	/// Returns the number of keys.
+	#[inline] pub const fn len() -> usize { LEN }
+	/// Returns true if the table has no keys.
+	#[inline] pub const fn is_empty() -> bool { LEN == 0 }
+}
@@ -0,0 +1,29 @@
+// @generated by mphf 0.1.0
+// 3 keys with digest 2d9db084adfdade6, seeds_len 1, max_seed 100000
+mod reexport {
+	#![allow(dead_code)]
+	/// The seeds of the buckets, empty buckets have the max value.
+	pub(super) static SEEDS: [u32; 1] = [7,];
+	/// The keys in mphf order.
+	pub(super) static KEYS: [&str; 3] = ["blue","red","green",];
+	/// The values in the order of their keys.
+	pub(super) static VALUES: [&str; 3] = ["#00f","#f00","#0f0",];
+	/// The number of keys.
+	pub(super) const LEN: usize = 3;
+	/// Returns the key stored at the index of the key, keys outside the table map to an arbitrary entry or `None`.
+	#[inline] pub(super) fn key(key: &str) -> Option<&'static str> { crate::deps::mphf::get(key, &SEEDS, &KEYS).copied() }
+	/// Iterates over the keys in mphf order.
+	#[inline] pub(super) fn keys() -> impl Iterator<Item = &'static str> { KEYS.iter().copied() }
+	/// Returns the value of the key, keys outside the table map to an arbitrary entry or `None`.
+	#[inline] pub(super) fn value(key: &str) -> Option<&'static str> { crate::deps::mphf::get(key, &SEEDS, &VALUES).copied() }
+	/// Iterates over the values in mphf order.
+	#[inline] pub(super) fn values() -> impl Iterator<Item = &'static str> { VALUES.iter().copied() }
+	/// Returns the index of the key in the arrays, keys outside the table map to an arbitrary entry or `None`.
+	#[inline] pub(super) fn index(key: &str) -> Option<usize> { crate::deps::mphf::index(key, &SEEDS, 3) }
+	/// Returns the number of keys.
+	#[inline] pub(super) const fn len() -> usize { LEN }
+	/// Returns true if the table has no keys.
+	#[inline] pub(super) const fn is_empty() -> bool { LEN == 0 }
+	/// Iterates over the keys and their values in mphf order.
+	#[inline] pub(super) fn iter() -> impl Iterator<Item = (&'static str, &'static str)> { (0..3).map(|i| (KEYS[i], VALUES[i])) }
+}
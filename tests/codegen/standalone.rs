@@ -0,0 +1,85 @@
+// @generated by mphf 0.1.0
+// 3 keys with digest 2d9db084adfdade6, seeds_len 1, max_seed 100000
+// options: standalone = true
+pub mod standalone {
+	// Lookup functions inlined from mphf 0.1.0, equivalent to mphf::index and mphf::get
+	mod mphf {
+		#![allow(dead_code)]
+		const fn fmix32(mut h: u32) -> u32 {
+			h ^= h >> 16;
+			h = h.wrapping_mul(0x85ebca6b);
+			h ^= h >> 13;
+			h = h.wrapping_mul(0xc2b2ae35);
+			h ^= h >> 16;
+			h
+		}
+		pub const fn hash(s: &[u8], seed: u32) -> u32 {
+			const C1: u32 = 0xcc9e2d51;
+			const C2: u32 = 0x1b873593;
+			let mut h = seed;
+			let mut i = 0;
+			while i < s.len() & !3 {
+				let k = u32::from_le_bytes([s[i], s[i + 1], s[i + 2], s[i + 3]]);
+				h ^= k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+				h = h.rotate_left(13).wrapping_mul(5).wrapping_add(0xe6546b64);
+				i += 4;
+			}
+			if s.len() & 3 != 0 {
+				let k = match s.len() & 3 {
+					3 => u32::from_le_bytes([s[i], s[i + 1], s[i + 2], 0]),
+					2 => u32::from_le_bytes([s[i], s[i + 1], 0, 0]),
+					_ => u32::from_le_bytes([s[i], 0, 0, 0]),
+				};
+				h ^= k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+			}
+			fmix32(h ^ s.len() as u32)
+		}
+		#[inline]
+		pub fn index_bytes(key: &[u8], seeds: &[u32], values_len: usize) -> Option<usize> {
+			if seeds.is_empty() || values_len == 0 {
+				return None;
+			}
+			let seed = seeds[hash(key, 0) as usize % seeds.len()];
+			if seed == u32::MAX {
+				return None;
+			}
+			Some(hash(key, seed) as usize % values_len)
+		}
+		#[inline]
+		pub fn index(key: &str, seeds: &[u32], values_len: usize) -> Option<usize> {
+			index_bytes(key.as_bytes(), seeds, values_len)
+		}
+		#[inline]
+		pub fn get_bytes<'a, T>(key: &[u8], seeds: &[u32], values: &'a [T]) -> Option<&'a T> {
+			values.get(index_bytes(key, seeds, values.len())?)
+		}
+		#[inline]
+		pub fn get<'a, T>(key: &str, seeds: &[u32], values: &'a [T]) -> Option<&'a T> {
+			get_bytes(key.as_bytes(), seeds, values)
+		}
+	}
+	/// The seeds of the buckets, empty buckets have the max value.
+	pub static SEEDS: [u32; 1] = [7,];
+	/// The keys in mphf order.
+	pub static KEYS: [&str; 3] = ["blue","red","green",];
+	/// The values in the order of their keys.
+	pub static VALUES: [&str; 3] = ["#00f","#f00","#0f0",];
+	/// The number of keys.
+	pub const LEN: usize = 3;
+	/// Returns the key stored at the index of the key, keys outside the table map to an arbitrary entry or `None`.
+	#[inline] pub fn key(key: &str) -> Option<&'static str> { self::mphf::get(key, &SEEDS, &KEYS).copied() }
+	/// Iterates over the keys in mphf order.
+	#[inline] pub fn keys() -> impl Iterator<Item = &'static str> { KEYS.iter().copied() }
+	/// Returns the value of the key, keys outside the table map to an arbitrary entry or `None`.
+	#[inline] pub fn value(key: &str) -> Option<&'static str> { self::mphf::get(key, &SEEDS, &VALUES).copied() }
+	/// Iterates over the values in mphf order.
+	#[inline] pub fn values() -> impl Iterator<Item = &'static str> { VALUES.iter().copied() }
+	/// Returns the index of the key in the arrays, keys outside the table map to an arbitrary entry or `None`.
+	#[inline] pub fn index(key: &str) -> Option<usize> { self::mphf::index(key, &SEEDS, 3) }
+	/// Returns the number of keys.
+	#[inline] pub const fn len() -> usize { LEN }
+	/// Returns true if the table has no keys.
+	#[inline] pub const fn is_empty() -> bool { LEN == 0 }
+	/// Iterates over the keys and their values in mphf order.
+	#[inline] pub fn iter() -> impl Iterator<Item = (&'static str, &'static str)> { (0..3).map(|i| (KEYS[i], VALUES[i])) }
+}
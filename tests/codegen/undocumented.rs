@@ -0,0 +1,40 @@
+// @generated by mphf 0.1.0
+// 3 keys with digest 2d9db084adfdade6, seeds_len 1, max_seed 100000
+// options: has_unchecked = true, has_contains = true, has_entry = true, verify_keys = true
+#[allow(missing_docs)]
+pub mod undocumented {
+	/// The seeds of the buckets, empty buckets have the max value.
+	pub static SEEDS: [u32; 1] = [7,];
+	/// The keys in mphf order.
+	pub static KEYS: [&str; 3] = ["blue","red","green",];
+	/// The values in the order of their keys.
+	pub static VALUES: [u32; 3] = [255,16711680,65280,];
+	/// The number of keys.
+	pub const LEN: usize = 3;
+	/// Returns the key stored at the index of the key, returns `None` for keys outside the table.
+	#[inline] pub fn key(key: &str) -> Option<&'static str> { match ::mphf::index(key, &SEEDS, 3) { Some(i) if KEYS[i] == key => Some(KEYS[i]), _ => None } }
+	/// Iterates over the keys in mphf order.
+	#[inline] pub fn keys() -> impl Iterator<Item = &'static str> { KEYS.iter().copied() }
+	/// Returns the value of the key, returns `None` for keys outside the table.
+	#[inline] pub fn value(key: &str) -> Option<u32> { match ::mphf::index(key, &SEEDS, 3) { Some(i) if KEYS[i] == key => Some(VALUES[i]), _ => None } }
+	/// Iterates over the values in mphf order.
+	#[inline] pub fn values() -> impl Iterator<Item = u32> { VALUES.iter().copied() }
+	/// Returns the value of the key without comparing the key or checking for an empty bucket.
+	///
+	/// # Safety
+	///
+	/// The key must be in the table.
+	#[inline] pub unsafe fn value_unchecked(key: &str) -> u32 { unsafe { let i = ::mphf::index(key, &SEEDS, 3).unwrap_unchecked(); *VALUES.get_unchecked(i) } }
+	/// Returns the index of the key in the arrays, returns `None` for keys outside the table.
+	#[inline] pub fn index(key: &str) -> Option<usize> { match ::mphf::index(key, &SEEDS, 3) { Some(i) if KEYS[i] == key => Some(i), _ => None } }
+	/// Returns true if the key is in the table.
+	#[inline] pub fn contains_key(key: &str) -> bool { matches!(::mphf::index(key, &SEEDS, 3), Some(i) if KEYS[i] == key) }
+	/// Returns the number of keys.
+	#[inline] pub const fn len() -> usize { LEN }
+	/// Returns true if the table has no keys.
+	#[inline] pub const fn is_empty() -> bool { LEN == 0 }
+	/// Returns the stored key and the value of the key, returns `None` for keys outside the table.
+	#[inline] pub fn entry(key: &str) -> Option<(&'static str, u32)> { match ::mphf::index(key, &SEEDS, 3) { Some(i) if KEYS[i] == key => Some((KEYS[i], VALUES[i])), _ => None } }
+	/// Iterates over the keys and their values in mphf order.
+	#[inline] pub fn iter() -> impl Iterator<Item = (&'static str, u32)> { (0..3).map(|i| (KEYS[i], VALUES[i])) }
+}
@@ -0,0 +1,41 @@
+// Hostile headers must not cause allocations beyond the configured limits.
+#![cfg(feature = "std")]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use mphf::binary::Limits;
+
+struct Tracking;
+
+static LARGEST: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Tracking {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		LARGEST.fetch_max(layout.size(), Ordering::Relaxed);
+		System.alloc(layout)
+	}
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		System.dealloc(ptr, layout)
+	}
+}
+
+#[global_allocator]
+static GLOBAL: Tracking = Tracking;
+
+#[test]
+fn hostile_headers() {
+	let mphf = mphf::Mphf::build(&["hello", "goodbye", "cat", "dog"], 2, 10000).unwrap();
+	let golden = mphf.to_bytes();
+	let limits = Limits { max_keys: 1000, max_seeds: 1000 };
+
+	for &seeds_len in &[1001, 1 << 20, 1 << 30, u32::MAX] {
+		let mut bytes = golden.clone();
+		bytes[12..16].copy_from_slice(&seeds_len.to_le_bytes());
+
+		LARGEST.store(0, Ordering::Relaxed);
+		assert!(mphf::Mphf::read_from_with_limits(&bytes[..], &limits).is_err());
+		assert!(mphf::Mphf::from_bytes_with_limits(&bytes, &limits).is_err());
+		assert!(mphf::Mphf::from_bytes(&bytes).is_err());
+		assert!(LARGEST.load(Ordering::Relaxed) <= 4 * 1000, "allocated {} bytes", LARGEST.load(Ordering::Relaxed));
+	}
+}
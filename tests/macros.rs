@@ -0,0 +1,180 @@
+#![cfg(feature = "macros")]
+
+use mphf::{Map, Set};
+
+static KEYWORDS: Map<&str, u32> = mphf::map! { "if" => 0, "else" => 1, "while" => 2 };
+
+const COLORS: Map<&str, &str> = mphf::map! {
+	"red" => "#f00",
+	"green" => "#0f0",
+	"blue" => "#00f",
+	"" => "empty",
+	"r\u{e9}sum\u{e9}" => "unicode",
+};
+
+static EMPTY: Map<&str, u8> = mphf::map! {};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Token {
+	If,
+	Else,
+}
+
+static TOKENS: Map<&str, Token> = mphf::map! { "if" => Token::If, "else" => Token::Else, };
+
+#[test]
+fn test_map_lookups() {
+	assert_eq!(KEYWORDS.get("if"), Some(&0));
+	assert_eq!(KEYWORDS.get("else"), Some(&1));
+	assert_eq!(KEYWORDS.get("while"), Some(&2));
+	for probe in ["", "for", "If", "whilst", "else "] {
+		assert_eq!(KEYWORDS.get(probe), None, "{:?}", probe);
+	}
+	assert!(!COLORS.contains_key("yellow"));
+	assert_eq!(COLORS.get(""), Some(&"empty"));
+	assert_eq!(COLORS.get("résumé"), Some(&"unicode"));
+	assert_eq!(COLORS.get_entry("green"), Some(("green", &"#0f0")));
+	assert_eq!(TOKENS.get("else"), Some(&Token::Else));
+
+	// The keys and values are in mphf order
+	for (i, (&key, value)) in COLORS.entries().enumerate() {
+		assert_eq!(COLORS.index(key), Some(i));
+		assert_eq!(COLORS.values()[i], *value);
+	}
+	assert_eq!(COLORS.len(), 5);
+	assert_eq!(KEYWORDS.keys().len(), 3);
+
+	assert!(EMPTY.is_empty());
+	assert_eq!(EMPTY.get(""), None);
+	assert_eq!(format!("{:?}", EMPTY), "{}");
+}
+
+static RESERVED: Set<&str> = mphf::set! { "fn", "let", "match", "mod", "pub", "use", "where", "while" };
+
+static NONE: Set<&str> = mphf::set! {};
+
+#[test]
+fn test_set() {
+	for &key in RESERVED.keys() {
+		assert!(RESERVED.contains(key), "{:?}", key);
+	}
+	for probe in ["", "f", "fn ", "Fn", "mut", "loop", "whale"] {
+		assert!(!RESERVED.contains(probe), "{:?}", probe);
+		assert_eq!(RESERVED.index_of(probe), None, "{:?}", probe);
+	}
+
+	// The indices are a bijection with 0..len
+	let mut seen = [false; 8];
+	for key in ["fn", "let", "match", "mod", "pub", "use", "where", "while"] {
+		let index = RESERVED.index_of(key).unwrap();
+		assert!(!seen[index], "{:?}", key);
+		seen[index] = true;
+		assert_eq!(RESERVED.keys()[index], key);
+	}
+	assert_eq!(RESERVED.len(), 8);
+
+	assert!(NONE.is_empty() && !NONE.contains(""));
+	assert_eq!(format!("{:?}", NONE), "{}");
+}
+
+#[derive(Debug, PartialEq, mphf::FromName)]
+enum Color {
+	Red,
+	Green,
+	#[mphf(rename = "azure")]
+	Blue,
+}
+
+#[derive(Debug, PartialEq, mphf::FromName)]
+#[mphf(rename_all = "snake_case")]
+enum Case {
+	HttpRequest,
+	Get,
+	#[mphf(rename = "Custom Name")]
+	RenamedVariant,
+}
+
+#[derive(Debug, PartialEq, mphf::FromName)]
+enum Empty {}
+
+// Not Copy, the names are looked up by position
+#[derive(Debug, PartialEq, mphf::FromName)]
+#[mphf(rename_all = "kebab-case")]
+enum Large {
+	AlphaAlpha0, BravoAlpha0, CharlieAlpha0, DeltaAlpha0, EchoAlpha0, FoxtrotAlpha0, GolfAlpha0, HotelAlpha0, IndiaAlpha0, JulietAlpha0,
+	AlphaBravo0, BravoBravo0, CharlieBravo0, DeltaBravo0, EchoBravo0, FoxtrotBravo0, GolfBravo0, HotelBravo0, IndiaBravo0, JulietBravo0,
+	AlphaCharlie0, BravoCharlie0, CharlieCharlie0, DeltaCharlie0, EchoCharlie0, FoxtrotCharlie0, GolfCharlie0, HotelCharlie0, IndiaCharlie0, JulietCharlie0,
+	AlphaDelta0, BravoDelta0, CharlieDelta0, DeltaDelta0, EchoDelta0, FoxtrotDelta0, GolfDelta0, HotelDelta0, IndiaDelta0, JulietDelta0,
+	AlphaEcho0, BravoEcho0, CharlieEcho0, DeltaEcho0, EchoEcho0, FoxtrotEcho0, GolfEcho0, HotelEcho0, IndiaEcho0, JulietEcho0,
+	AlphaFoxtrot0, BravoFoxtrot0, CharlieFoxtrot0, DeltaFoxtrot0, EchoFoxtrot0, FoxtrotFoxtrot0, GolfFoxtrot0, HotelFoxtrot0, IndiaFoxtrot0, JulietFoxtrot0,
+	AlphaGolf0, BravoGolf0, CharlieGolf0, DeltaGolf0, EchoGolf0, FoxtrotGolf0, GolfGolf0, HotelGolf0, IndiaGolf0, JulietGolf0,
+	AlphaHotel0, BravoHotel0, CharlieHotel0, DeltaHotel0, EchoHotel0, FoxtrotHotel0, GolfHotel0, HotelHotel0, IndiaHotel0, JulietHotel0,
+	AlphaIndia0, BravoIndia0, CharlieIndia0, DeltaIndia0, EchoIndia0, FoxtrotIndia0, GolfIndia0, HotelIndia0, IndiaIndia0, JulietIndia0,
+	AlphaJuliet0, BravoJuliet0, CharlieJuliet0, DeltaJuliet0, EchoJuliet0, FoxtrotJuliet0, GolfJuliet0, HotelJuliet0, IndiaJuliet0, JulietJuliet0,
+	AlphaAlpha1, BravoAlpha1, CharlieAlpha1, DeltaAlpha1, EchoAlpha1, FoxtrotAlpha1, GolfAlpha1, HotelAlpha1, IndiaAlpha1, JulietAlpha1,
+	AlphaBravo1, BravoBravo1, CharlieBravo1, DeltaBravo1, EchoBravo1, FoxtrotBravo1, GolfBravo1, HotelBravo1, IndiaBravo1, JulietBravo1,
+	AlphaCharlie1, BravoCharlie1, CharlieCharlie1, DeltaCharlie1, EchoCharlie1, FoxtrotCharlie1, GolfCharlie1, HotelCharlie1, IndiaCharlie1, JulietCharlie1,
+	AlphaDelta1, BravoDelta1, CharlieDelta1, DeltaDelta1, EchoDelta1, FoxtrotDelta1, GolfDelta1, HotelDelta1, IndiaDelta1, JulietDelta1,
+	AlphaEcho1, BravoEcho1, CharlieEcho1, DeltaEcho1, EchoEcho1, FoxtrotEcho1, GolfEcho1, HotelEcho1, IndiaEcho1, JulietEcho1,
+	AlphaFoxtrot1, BravoFoxtrot1, CharlieFoxtrot1, DeltaFoxtrot1, EchoFoxtrot1, FoxtrotFoxtrot1, GolfFoxtrot1, HotelFoxtrot1, IndiaFoxtrot1, JulietFoxtrot1,
+	AlphaGolf1, BravoGolf1, CharlieGolf1, DeltaGolf1, EchoGolf1, FoxtrotGolf1, GolfGolf1, HotelGolf1, IndiaGolf1, JulietGolf1,
+	AlphaHotel1, BravoHotel1, CharlieHotel1, DeltaHotel1, EchoHotel1, FoxtrotHotel1, GolfHotel1, HotelHotel1, IndiaHotel1, JulietHotel1,
+	AlphaIndia1, BravoIndia1, CharlieIndia1, DeltaIndia1, EchoIndia1, FoxtrotIndia1, GolfIndia1, HotelIndia1, IndiaIndia1, JulietIndia1,
+	AlphaJuliet1, BravoJuliet1, CharlieJuliet1, DeltaJuliet1, EchoJuliet1, FoxtrotJuliet1, GolfJuliet1, HotelJuliet1, IndiaJuliet1, JulietJuliet1,
+	AlphaAlpha2, BravoAlpha2, CharlieAlpha2, DeltaAlpha2, EchoAlpha2, FoxtrotAlpha2, GolfAlpha2, HotelAlpha2, IndiaAlpha2, JulietAlpha2,
+	AlphaBravo2, BravoBravo2, CharlieBravo2, DeltaBravo2, EchoBravo2, FoxtrotBravo2, GolfBravo2, HotelBravo2, IndiaBravo2, JulietBravo2,
+	AlphaCharlie2, BravoCharlie2, CharlieCharlie2, DeltaCharlie2, EchoCharlie2, FoxtrotCharlie2, GolfCharlie2, HotelCharlie2, IndiaCharlie2, JulietCharlie2,
+	AlphaDelta2, BravoDelta2, CharlieDelta2, DeltaDelta2, EchoDelta2, FoxtrotDelta2, GolfDelta2, HotelDelta2, IndiaDelta2, JulietDelta2,
+	AlphaEcho2, BravoEcho2, CharlieEcho2, DeltaEcho2, EchoEcho2, FoxtrotEcho2, GolfEcho2, HotelEcho2, IndiaEcho2, JulietEcho2,
+}
+
+#[test]
+fn test_from_name() {
+	assert_eq!("Red".parse(), Ok(Color::Red));
+	assert_eq!("azure".parse(), Ok(Color::Blue));
+	assert_eq!("Blue".parse::<Color>(), Err(()));
+	assert_eq!("red".parse::<Color>(), Err(()));
+	assert_eq!(Color::Green.name(), "Green");
+	assert_eq!(Color::Blue.name(), "azure");
+
+	assert_eq!("http_request".parse(), Ok(Case::HttpRequest));
+	assert_eq!("get".parse(), Ok(Case::Get));
+	assert_eq!("Custom Name".parse(), Ok(Case::RenamedVariant));
+	assert_eq!(Case::HttpRequest.name(), "http_request");
+	assert_eq!("".parse::<Empty>(), Err(()));
+
+	assert_eq!("alpha-alpha0".parse(), Ok(Large::AlphaAlpha0));
+	assert_eq!("juliet-echo2".parse(), Ok(Large::JulietEcho2));
+	assert_eq!(Large::DeltaFoxtrot1.name(), "delta-foxtrot1");
+	assert_eq!("AlphaAlpha0".parse::<Large>(), Err(()));
+	assert_eq!("juliet-juliet2".parse::<Large>(), Err(()));
+}
+
+#[test]
+fn test_rename_rules() {
+	macro_rules! check {
+		($rule:literal, $name:literal) => {{
+			#[derive(Debug, PartialEq, mphf::FromName)]
+			#[mphf(rename_all = $rule)]
+			enum Rename {
+				ContentType,
+			}
+			assert_eq!(Rename::ContentType.name(), $name);
+			assert_eq!($name.parse(), Ok(Rename::ContentType));
+		}};
+	}
+	check!("lowercase", "contenttype");
+	check!("UPPERCASE", "CONTENTTYPE");
+	check!("PascalCase", "ContentType");
+	check!("camelCase", "contentType");
+	check!("snake_case", "content_type");
+	check!("SCREAMING_SNAKE_CASE", "CONTENT_TYPE");
+	check!("kebab-case", "content-type");
+	check!("SCREAMING-KEBAB-CASE", "CONTENT-TYPE");
+}
+
+#[test]
+fn test_macros_ui() {
+	let t = trybuild::TestCases::new();
+	t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,39 @@
+// Encodes and decodes a table in a `#![no_std]` crate, run with `--no-default-features` to check the binary format builds without std.
+#![no_std]
+
+use mphf::binary::{self, DecodeError, EncodeError};
+use mphf::MphfRef;
+
+// The table for the keys `hello`, `goodbye`, `cat` and `dog` with two seeds
+const TABLE: [u8; 44] = [
+	b'M', b'P', b'H', b'F', 2, 0, 0, 0,
+	4, 0, 0, 0, 2, 0, 0, 0,
+	4, 0, 0, 0, 0, 0, 0, 0,
+	0, 0, 0, 0, 0, 0, 0, 0,
+	0x37, 0xfc, 0xf8, 0xcd,
+	0, 0, 0, 0, 1, 0, 0, 0,
+];
+
+#[test]
+fn decode() {
+	let view = MphfRef::from_bytes(&TABLE).unwrap();
+	let values = [10, 20, 30, 40];
+	let mut sum = 0;
+	for &key in &["hello", "goodbye", "cat", "dog"] {
+		sum += view.get(key, &values).unwrap();
+	}
+	assert_eq!(sum, 100);
+
+	let mut bytes = TABLE;
+	bytes[4] = 0;
+	assert_eq!(MphfRef::from_bytes(&bytes), Err(DecodeError::UnsupportedVersion(0)));
+	assert_eq!(MphfRef::from_bytes(&TABLE[..40]), Err(DecodeError::Truncated));
+}
+
+#[test]
+fn encode() {
+	let mut buf = [0u8; 64];
+	assert_eq!(binary::encode_into(&[0, 1], 4, &mut buf), Ok(TABLE.len()));
+	assert_eq!(buf[..TABLE.len()], TABLE);
+	assert_eq!(binary::encode_into(&[0, 1], 4, &mut buf[..40]), Err(EncodeError::BufferTooSmall { needed: 44 }));
+}
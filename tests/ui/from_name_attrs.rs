@@ -0,0 +1,20 @@
+#[derive(mphf::FromName)]
+#[mphf(rename_all = "Title Case")]
+enum Rule {
+	A,
+}
+
+#[derive(mphf::FromName)]
+enum Duplicate {
+	First,
+	#[mphf(rename = "First")]
+	Second,
+}
+
+#[derive(mphf::FromName)]
+enum Unknown {
+	#[mphf(alias = "a")]
+	A,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(mphf::FromName)]
+enum Shape {
+	Point,
+	Circle(f32),
+}
+
+fn main() {}
@@ -0,0 +1,4 @@
+#[derive(mphf::FromName)]
+struct Point;
+
+fn main() {}
@@ -0,0 +1,4 @@
+const KEY: &str = "a";
+static MAP: mphf::Map<&str, u32> = mphf::map! { KEY => 1 };
+
+fn main() {}
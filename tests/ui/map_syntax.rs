@@ -0,0 +1,3 @@
+static MAP: mphf::Map<&str, u32> = mphf::map! { "a" => 1 "b" => 2 };
+
+fn main() {}
@@ -0,0 +1,3 @@
+static SET: mphf::Set<&str> = mphf::set! { "a", 1 };
+
+fn main() {}